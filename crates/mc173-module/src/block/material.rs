@@ -387,6 +387,48 @@ pub fn get_fire_burn(id: u8) -> u16 {
     }
 }
 
+/// Get the behavior of a block when pushed or pulled by a piston. Note that piston bases
+/// are movable only when they are not extended, this must be checked by the caller.
+pub fn get_piston_policy(id: u8) -> PistonPolicy {
+    match id {
+        block::BEDROCK |
+        block::OBSIDIAN |
+        block::PISTON_EXT |
+        block::PISTON_MOVING |
+        // Blocks with block entities cannot be moved.
+        block::CHEST |
+        block::FURNACE |
+        block::FURNACE_LIT |
+        block::DISPENSER |
+        block::SPAWNER |
+        block::NOTE_BLOCK |
+        block::JUKEBOX |
+        block::SIGN |
+        block::WALL_SIGN => PistonPolicy::Stop,
+        block::WOOD_DOOR |
+        block::IRON_DOOR |
+        block::BED => PistonPolicy::Break,
+        block::PISTON |
+        block::STICKY_PISTON => PistonPolicy::Push,
+        _ => match get_material(id) {
+            Material::Portal |
+            Material::Piston => PistonPolicy::Stop,
+            Material::Plant |
+            Material::Water |
+            Material::Lava |
+            Material::Leaves |
+            Material::Circuit |
+            Material::Fire |
+            Material::Snow |
+            Material::Cactus |
+            Material::Cake |
+            Material::Cobweb |
+            Material::Pumpkin => PistonPolicy::Break,
+            _ => PistonPolicy::Push,
+        }
+    }
+}
+
 /// Common block properties of blocks.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum Material {
@@ -473,3 +515,14 @@ impl Material {
     }
 
 }
+
+/// The behavior of a block when pushed or pulled by a piston.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PistonPolicy {
+    /// The block is moved by the piston.
+    Push,
+    /// The block is broken when pushed, and cannot be pulled.
+    Break,
+    /// The block cannot be moved, the piston cannot extend.
+    Stop,
+}
@@ -0,0 +1,37 @@
+//! Sign block metadata functions.
+
+use std::f32::consts::{PI, TAU};
+
+use crate::geom::Face;
+
+
+/// Get the face a wall sign is attached to, only valid for wall signs.
+#[inline]
+pub fn get_wall_face(metadata: u8) -> Option<Face> {
+    Some(match metadata {
+        2 => Face::PosZ,
+        3 => Face::NegZ,
+        4 => Face::PosX,
+        5 => Face::NegX,
+        _ => return None
+    })
+}
+
+/// Set the face a wall sign is attached to, only valid for wall signs.
+#[inline]
+pub fn set_wall_face(metadata: &mut u8, face: Face) {
+    *metadata = match face {
+        Face::PosZ => 2,
+        Face::NegZ => 3,
+        Face::PosX => 4,
+        Face::NegX => 5,
+        _ => 0
+    }
+}
+
+/// Set the rotation of a standing sign so that it faces the entity placing it, from the
+/// yaw (in radians) of that entity. The rotation has 16 possible values.
+#[inline]
+pub fn set_yaw(metadata: &mut u8, yaw: f32) {
+    *metadata = (((yaw + PI) * 16.0 / TAU + 0.5).floor() as i32 & 15) as u8;
+}
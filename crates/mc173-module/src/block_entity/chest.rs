@@ -1,10 +1,40 @@
 //! Chest block entity.
 
+use glam::IVec3;
+use spacetimedb::spacetimedb;
+
+use crate::i32vec3::StdbI32Vec3;
 use crate::item::ItemStack;
 
+use super::calc_block_entity_id;
+
 
-#[derive(Debug, Clone, Default)]
-pub struct ChestBlockEntity {
+/// Number of slots in a single chest.
+pub const CHEST_INV_SIZE: usize = 27;
+
+#[spacetimedb(table(public))]
+#[derive(Debug, Clone)]
+pub struct StdbChestBlockEntity {
+    /// The block entity id, computed from its position.
+    #[primarykey]
+    pub block_entity_id: u64,
+    /// The position of the chest in the world.
+    pub pos: StdbI32Vec3,
     /// The inventory of the chest.
-    pub inv: Box<[ItemStack; 27]>,
+    pub inv: Vec<ItemStack>,
+}
+
+impl StdbChestBlockEntity {
+
+    /// Create a new empty chest at the given position.
+    pub fn new(pos: IVec3) -> Self {
+        Self {
+            block_entity_id: calc_block_entity_id(pos),
+            pos: pos.into(),
+            inv: vec![ItemStack::EMPTY; CHEST_INV_SIZE],
+        }
+    }
+
 }
+
+super::impl_block_entity_table!(StdbChestBlockEntity);
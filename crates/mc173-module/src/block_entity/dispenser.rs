@@ -1,21 +1,58 @@
 //! Dispenser block entity.
 
+use glam::IVec3;
+use spacetimedb::spacetimedb;
+
+use crate::i32vec3::StdbI32Vec3;
 use crate::rand::JavaRandom;
 use crate::item::ItemStack;
 
+use super::calc_block_entity_id;
+
 
-#[derive(Debug, Clone, Default)]
-pub struct DispenserBlockEntity {
+/// Number of slots in a dispenser.
+pub const DISPENSER_INV_SIZE: usize = 9;
+
+#[spacetimedb(table(public))]
+#[derive(Debug, Clone)]
+pub struct StdbDispenserBlockEntity {
+    /// The block entity id, computed from its position.
+    #[primarykey]
+    pub block_entity_id: u64,
+    /// The position of the dispenser in the world.
+    pub pos: StdbI32Vec3,
     /// The inventory of the dispenser.
-    pub inv: Box<[ItemStack; 9]>,
+    pub inv: Vec<ItemStack>,
     /// The dispenser has its own RNG.
     pub rand: JavaRandom,
+    /// True when the dispenser was powered on the last redstone notification, an item
+    /// is only dispensed when the dispenser becomes powered.
+    pub powered: bool,
 }
 
-impl DispenserBlockEntity {
+impl StdbDispenserBlockEntity {
+
+    /// Create a new empty and unpowered dispenser at the given position.
+    pub fn new(pos: IVec3) -> Self {
+        Self {
+            block_entity_id: calc_block_entity_id(pos),
+            pos: pos.into(),
+            inv: vec![ItemStack::EMPTY; DISPENSER_INV_SIZE],
+            rand: JavaRandom::default(),
+            powered: false,
+        }
+    }
+
+    /// Update the redstone powered state of this dispenser, returning true if it has just
+    /// become powered, an item should then be dispensed.
+    pub fn set_powered(&mut self, powered: bool) -> bool {
+        let rising = powered && !self.powered;
+        self.powered = powered;
+        rising
+    }
 
     /// Randomly pick a non-empty stack in this dispenser, returning its index if any,
-    /// none if there are only empty stacks in the inventory. 
+    /// none if there are only empty stacks in the inventory.
     pub fn pick_random_index(&mut self) -> Option<usize> {
 
         let mut bound = 0;
@@ -33,5 +70,56 @@ impl DispenserBlockEntity {
         selected_index
 
     }
-    
+
+}
+
+super::impl_block_entity_table!(StdbDispenserBlockEntity);
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::item;
+
+    #[test]
+    fn powered_rising_edge() {
+
+        let mut dispenser = StdbDispenserBlockEntity::new(IVec3::ZERO);
+        assert!(!dispenser.powered);
+
+        // Only dispense when the dispenser becomes powered.
+        assert!(!dispenser.set_powered(false));
+        assert!(dispenser.set_powered(true));
+        assert!(dispenser.powered);
+        assert!(!dispenser.set_powered(true));
+        assert!(!dispenser.set_powered(false));
+        assert!(!dispenser.powered);
+        assert!(dispenser.set_powered(true));
+
+    }
+
+    #[test]
+    fn pick_random_index() {
+
+        let mut dispenser = StdbDispenserBlockEntity::new(IVec3::ZERO);
+        dispenser.rand = JavaRandom::new(0);
+        assert_eq!(dispenser.pick_random_index(), None);
+
+        dispenser.inv[4] = ItemStack::new_single(item::ARROW, 0);
+        for _ in 0..10 {
+            assert_eq!(dispenser.pick_random_index(), Some(4));
+        }
+
+        // Only non-empty stacks are picked, and all of them end up being picked.
+        dispenser.inv[7] = ItemStack::new_single(item::ARROW, 0);
+        let mut picked = [false; DISPENSER_INV_SIZE];
+        for _ in 0..100 {
+            picked[dispenser.pick_random_index().unwrap()] = true;
+        }
+        assert_eq!(picked.iter().filter(|&&p| p).count(), 2);
+        assert!(picked[4] && picked[7]);
+
+    }
+
 }
@@ -1,14 +1,28 @@
 //! Furnace block entity.
 
 use glam::IVec3;
+use spacetimedb::spacetimedb;
 
+use crate::chunk_cache::ChunkCache;
+use crate::i32vec3::StdbI32Vec3;
 use crate::item::{self, ItemStack};
 use crate::world::StdbWorld;
-use crate::smelt;
-
-
-#[derive(Debug, Clone, Default)]
-pub struct FurnaceBlockEntity {
+use crate::{block, smelt};
+
+use super::calc_block_entity_id;
+
+
+#[spacetimedb(table(public))]
+#[spacetimedb(index(btree, dimension_id))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct StdbFurnaceBlockEntity {
+    /// The block entity id, computed from its position.
+    #[primarykey]
+    pub block_entity_id: u64,
+    /// The dimension of the world ticking this furnace.
+    pub dimension_id: i32,
+    /// The position of the furnace in the world.
+    pub pos: StdbI32Vec3,
     /// Input stack of the furnace.
     pub input_stack: ItemStack,
     /// Item stack for fueling the furnace.
@@ -22,15 +36,33 @@ pub struct FurnaceBlockEntity {
     /// Current ticks count since the current item has been added.
     pub smelt_ticks: u16,
     /// Last input stack, used to compare to new one and update the current recipe.
-    last_input_stack: ItemStack,
+    pub last_input_stack: ItemStack,
     /// Last output stack, used to compare to new one and update the current recipe.
-    last_output_stack: ItemStack,
+    pub last_output_stack: ItemStack,
     /// If some recipe has been found for the current input stack, this contains the
     /// future output stack that will be assigned to output stack.
-    active_output_stack: Option<ItemStack>,
+    pub active_output_stack: Option<ItemStack>,
 }
 
-impl FurnaceBlockEntity {
+impl StdbFurnaceBlockEntity {
+
+    /// Create a new empty furnace at the given position.
+    pub fn new(dimension_id: i32, pos: IVec3) -> Self {
+        Self {
+            block_entity_id: calc_block_entity_id(pos),
+            dimension_id,
+            pos: pos.into(),
+            input_stack: ItemStack::EMPTY,
+            fuel_stack: ItemStack::EMPTY,
+            output_stack: ItemStack::EMPTY,
+            burn_max_ticks: 0,
+            burn_remaining_ticks: 0,
+            smelt_ticks: 0,
+            last_input_stack: ItemStack::EMPTY,
+            last_output_stack: ItemStack::EMPTY,
+            active_output_stack: None,
+        }
+    }
 
     /// Internal function to compute the new recipe depending on the current input item.
     /// None is returned if the input stack is empty, if no recipe can be found, or if
@@ -59,8 +91,30 @@ impl FurnaceBlockEntity {
 
     }
 
-    /// Tick the furnace block entity.
-    pub fn tick(&mut self, world: &mut StdbWorld, pos: IVec3) {
+    /// Tick the furnace block entity. The storage and progress changes are not sent from
+    /// here, the proxy diffs the furnace row.
+    pub fn tick(&mut self, world: &mut StdbWorld, cache: &mut ChunkCache) {
+
+        let initial_burning = self.burn_remaining_ticks != 0;
+        if !self.tick_progress() {
+            return;
+        }
+
+        let pos = IVec3::from(self.pos);
+        let (_id, metadata) = world.get_block(pos, cache).expect("should not be ticking if not loaded");
+        if initial_burning {
+            // No longer burning.
+            world.set_block_notify(pos, block::FURNACE, metadata, cache);
+        } else {
+            // Now burning.
+            world.set_block_notify(pos, block::FURNACE_LIT, metadata, cache);
+        }
+
+    }
+
+    /// Internal function to consume fuel and progress the smelting of the input stack,
+    /// returning true if the furnace started or stopped burning.
+    fn tick_progress(&mut self) -> bool {
 
         // If the input stack have changed since last update, get the new recipe.
         // TODO: Also update of output stack have changed.
@@ -70,13 +124,9 @@ impl FurnaceBlockEntity {
             self.last_output_stack = self.output_stack;
         }
 
-        let mut smelt_modified = false;
-        let mut fuel_modified = false;
-
         let initial_burning = self.burn_remaining_ticks != 0;
         if initial_burning {
             self.burn_remaining_ticks -= 1;
-            fuel_modified = true;
         }
 
         if let Some(active_output_stack) = &self.active_output_stack {
@@ -87,37 +137,13 @@ impl FurnaceBlockEntity {
                 self.burn_remaining_ticks = self.burn_max_ticks;
                 
                 if self.burn_max_ticks != 0 {
-
                     self.fuel_stack.size -= 1;
-                    fuel_modified = true;
-
-                    // TODO: Another event that we don't care about in the SpacetimeDB module
-                    // world.push_event(Event::BlockEntity {
-                    //     pos,
-                    //     inner: BlockEntityEvent::Storage {
-                    //         storage: BlockEntityStorage::FurnaceFuel,
-                    //         stack: self.fuel_stack,
-                    //     },
-                    // });
-
-                    // TODO: Another event that we don't care about in the SpacetimeDB module
-                    // world.push_event(Event::BlockEntity {
-                    //     pos,
-                    //     inner: BlockEntityEvent::Progress {
-                    //         progress: BlockEntityProgress::FurnaceBurnMaxTime,
-                    //         value: self.burn_max_ticks,
-                    //     },
-                    // });
-
                 }
 
             }
 
             if self.burn_remaining_ticks == 0 {
-                if self.smelt_ticks != 0 {
-                    self.smelt_ticks = 0;
-                    smelt_modified = true;
-                }
+                self.smelt_ticks = 0;
             } else {
 
                 self.smelt_ticks += 1;
@@ -131,68 +157,101 @@ impl FurnaceBlockEntity {
                     self.input_stack.size -= 1;
                     self.output_stack = *active_output_stack;
 
-                    // TODO: Another event that we don't care about in the SpacetimeDB module
-                    // world.push_event(Event::BlockEntity {
-                    //     pos,
-                    //     inner: BlockEntityEvent::Storage {
-                    //         storage: BlockEntityStorage::FurnaceInput,
-                    //         stack: self.input_stack,
-                    //     },
-                    // });
-
-                    // TODO: Another event that we don't care about in the SpacetimeDB module
-                    // world.push_event(Event::BlockEntity {
-                    //     pos,
-                    //     inner: BlockEntityEvent::Storage {
-                    //         storage: BlockEntityStorage::FurnaceOutput,
-                    //         stack: self.output_stack,
-                    //     },
-                    // });
-
                 }
 
-                smelt_modified = true;
-
             }
             
-        } else if self.smelt_ticks != 0 {
+        } else {
             self.smelt_ticks = 0;
-            smelt_modified = true;
         }
 
-        // TODO: Another event that we don't care about in the SpacetimeDB module
-        // if smelt_modified {
-        //     world.push_event(Event::BlockEntity {
-        //         pos,
-        //         inner: BlockEntityEvent::Progress {
-        //             progress: BlockEntityProgress::FurnaceSmeltTime,
-        //             value: self.smelt_ticks,
-        //         },
-        //     });
-        // }
-
-        // TODO: Another event that we don't care about in the SpacetimeDB module
-        // if fuel_modified {
-        //     world.push_event(Event::BlockEntity {
-        //         pos,
-        //         inner: BlockEntityEvent::Progress {
-        //             progress: BlockEntityProgress::FurnaceBurnRemainingTime,
-        //             value: self.burn_remaining_ticks,
-        //         },
-        //     });
-        // }
-
-        // TODO: Another event that we don't care about in the SpacetimeDB module
-        // if initial_burning != (self.burn_remaining_ticks != 0) {
-        //     let (_id, metadata) = world.get_block(pos).expect("should not be ticking if not loaded");
-        //     if initial_burning {
-        //         // No longer burning.
-        //         world.set_block_notify(pos, block::FURNACE, metadata);
-        //     } else {
-        //         // Now burning.
-        //         world.set_block_notify(pos, block::FURNACE_LIT, metadata);
-        //     }
-        // }
+        initial_burning != (self.burn_remaining_ticks != 0)
+
+    }
+
+}
+
+super::impl_block_entity_table!(StdbFurnaceBlockEntity);
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn smelt_progress() {
+
+        let mut furnace = StdbFurnaceBlockEntity::new(0, IVec3::ZERO);
+        furnace.input_stack = ItemStack::new_block_sized(block::IRON_ORE, 0, 2);
+        furnace.fuel_stack = ItemStack::new_single(item::COAL, 0);
+
+        // The first tick consumes the fuel and starts burning.
+        assert!(furnace.tick_progress());
+        assert_eq!(furnace.fuel_stack.size, 0);
+        assert_eq!(furnace.burn_max_ticks, 1600);
+        assert_eq!(furnace.burn_remaining_ticks, 1600);
+        assert_eq!(furnace.smelt_ticks, 1);
+
+        for _ in 1..199 {
+            assert!(!furnace.tick_progress());
+        }
+        assert_eq!(furnace.smelt_ticks, 199);
+        assert!(furnace.output_stack.is_empty());
+
+        // An item is smelted every 200 ticks.
+        assert!(!furnace.tick_progress());
+        assert_eq!(furnace.smelt_ticks, 0);
+        assert_eq!(furnace.input_stack.size, 1);
+        assert_eq!(furnace.output_stack, ItemStack::new_single(item::IRON_INGOT, 0));
+
+        for _ in 0..200 {
+            assert!(!furnace.tick_progress());
+        }
+        assert_eq!(furnace.input_stack.size, 0);
+        assert_eq!(furnace.output_stack, ItemStack::new_sized(item::IRON_INGOT, 0, 2));
+
+        // Nothing left to smelt, the fuel still burns until it stops.
+        for _ in 0..1200 {
+            assert!(!furnace.tick_progress());
+        }
+        assert_eq!(furnace.smelt_ticks, 0);
+        assert!(furnace.tick_progress());
+        assert_eq!(furnace.burn_remaining_ticks, 0);
+
+    }
+
+    #[test]
+    fn smelt_without_fuel() {
+
+        let mut furnace = StdbFurnaceBlockEntity::new(0, IVec3::ZERO);
+        furnace.input_stack = ItemStack::new_block(block::IRON_ORE, 0);
+
+        for _ in 0..400 {
+            assert!(!furnace.tick_progress());
+        }
+
+        assert_eq!(furnace.smelt_ticks, 0);
+        assert_eq!(furnace.input_stack.size, 1);
+        assert!(furnace.output_stack.is_empty());
+
+    }
+
+    #[test]
+    fn smelt_output_full() {
+
+        let mut furnace = StdbFurnaceBlockEntity::new(0, IVec3::ZERO);
+        furnace.input_stack = ItemStack::new_block(block::IRON_ORE, 0);
+        furnace.fuel_stack = ItemStack::new_single(item::COAL, 0);
+        furnace.output_stack = ItemStack::new_sized(item::IRON_INGOT, 0, 64);
+
+        // No recipe can be found because the output is full, the fuel is kept.
+        for _ in 0..400 {
+            assert!(!furnace.tick_progress());
+        }
+
+        assert_eq!(furnace.fuel_stack.size, 1);
+        assert_eq!(furnace.input_stack.size, 1);
 
     }
 
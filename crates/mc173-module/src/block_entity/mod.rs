@@ -4,6 +4,35 @@ use glam::IVec3;
 
 use crate::world::StdbWorld;
 
+
+/// Implement the functions common to all block entity tables, the table must have a
+/// `block_entity_id` primary key computed with [`calc_block_entity_id`].
+macro_rules! impl_block_entity_table {
+    ( $name:ident ) => {
+
+        impl $name {
+
+            /// Find the block entity of this type at the given position.
+            pub fn find(pos: glam::IVec3) -> Option<Self> {
+                Self::filter_by_block_entity_id(&$crate::block_entity::calc_block_entity_id(pos))
+            }
+
+            /// Insert this block entity, or replace the one already at its position.
+            pub fn set(self) {
+                if Self::filter_by_block_entity_id(&self.block_entity_id).is_some() {
+                    Self::update_by_block_entity_id(&self.block_entity_id.clone(), self);
+                } else {
+                    Self::insert(self).unwrap();
+                }
+            }
+
+        }
+
+    };
+}
+
+use impl_block_entity_table;
+
 pub mod chest;
 pub mod furnace;
 pub mod dispenser;
@@ -14,6 +43,53 @@ pub mod sign;
 pub mod jukebox;
 
 
+/// Compute the unique id of a block entity from its position in the world, this id is
+/// used as primary key in every block entity table. The X and Z coordinates are stored
+/// on 28 bits each and the Y coordinate on 8 bits.
+pub fn calc_block_entity_id(pos: IVec3) -> u64 {
+    // Bounds check x, z and y, x and z must fit in 28 bits.
+    debug_assert!(pos.x >= -(1 << 27) && pos.x < (1 << 27));
+    debug_assert!(pos.z >= -(1 << 27) && pos.z < (1 << 27));
+    debug_assert!(pos.y >= 0 && pos.y < 128);
+    ((pos.x as u64 & 0xFFFFFFF) << 36) | ((pos.z as u64 & 0xFFFFFFF) << 8) | (pos.y as u64 & 0xFF)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn block_entity_id() {
+
+        assert_eq!(calc_block_entity_id(IVec3::new(0, 0, 0)), 0);
+        assert_eq!(calc_block_entity_id(IVec3::new(0, 127, 0)), 127);
+        assert_eq!(calc_block_entity_id(IVec3::new(0, 0, 1)), 1 << 8);
+        assert_eq!(calc_block_entity_id(IVec3::new(1, 0, 0)), 1 << 36);
+
+        // Negative coordinates are truncated to 28 bits.
+        assert_eq!(calc_block_entity_id(IVec3::new(-1, 0, 0)), 0xFFFFFFF << 36);
+        assert_eq!(calc_block_entity_id(IVec3::new(0, 0, -1)), 0xFFFFFFF << 8);
+
+        // Every position of a region must give a different id.
+        let mut ids = std::collections::HashSet::new();
+        for x in -2..2 {
+            for z in -2..2 {
+                for y in [0, 1, 64, 127] {
+                    assert!(ids.insert(calc_block_entity_id(IVec3::new(x, y, z))));
+                }
+            }
+        }
+
+        // Extreme coordinates do not overlap.
+        let min = calc_block_entity_id(IVec3::new(-(1 << 27), 0, -(1 << 27)));
+        let max = calc_block_entity_id(IVec3::new((1 << 27) - 1, 127, (1 << 27) - 1));
+        assert_ne!(min, max);
+
+    }
+
+}
+
 // /// All kinds of block entities.
 // #[derive(Debug, Clone)]
 // pub enum BlockEntity {
@@ -1,8 +1,43 @@
 //! Note block block entity.
 
+use glam::IVec3;
+use spacetimedb::spacetimedb;
 
-#[derive(Debug, Clone, Default)]
-pub struct NoteBlockBlockEntity {
+use crate::i32vec3::StdbI32Vec3;
+
+use super::calc_block_entity_id;
+
+
+/// Number of different notes a note block can be tuned to.
+pub const NOTES_COUNT: u8 = 25;
+
+#[spacetimedb(table(public))]
+#[derive(Debug, Clone)]
+pub struct StdbNoteBlockBlockEntity {
+    /// The block entity id, computed from its position.
+    #[primarykey]
+    pub block_entity_id: u64,
+    /// The position of the note block in the world.
+    pub pos: StdbI32Vec3,
     /// The note to play.
     pub note: u8,
+    /// True when the note block was powered on the last redstone notification, the note
+    /// is only played when the note block becomes powered.
+    pub powered: bool,
 }
+
+impl StdbNoteBlockBlockEntity {
+
+    /// Create a new unpowered note block playing the lowest note at the given position.
+    pub fn new(pos: IVec3) -> Self {
+        Self {
+            block_entity_id: calc_block_entity_id(pos),
+            pos: pos.into(),
+            note: 0,
+            powered: false,
+        }
+    }
+
+}
+
+super::impl_block_entity_table!(StdbNoteBlockBlockEntity);
@@ -1,42 +1,84 @@
 //! Moving piston block entity.
 
 use glam::IVec3;
+use spacetimedb::spacetimedb;
 
+use crate::chunk_cache::ChunkCache;
+use crate::i32vec3::StdbI32Vec3;
 use crate::world::StdbWorld;
 use crate::geom::Face;
+use crate::block;
 
+use super::calc_block_entity_id;
 
+
+#[spacetimedb(table(public))]
+#[spacetimedb(index(btree, dimension_id))]
 #[derive(Debug, Clone)]
-pub struct PistonBlockEntity {
+pub struct StdbPistonBlockEntity {
+    /// The block entity id, computed from its position.
+    #[primarykey]
+    pub block_entity_id: u64,
+    /// The dimension of the world ticking this moving block.
+    pub dimension_id: i32,
+    /// The position of the moving piston block in the world.
+    pub pos: StdbI32Vec3,
     /// The block id of the moving piston block.
     pub block: u8,
     /// The block metadata of the moving piston block.
     pub metadata: u8,
-    /// Face toward the block is moving.
-    pub face: Face,
+    /// Face toward the block is moving, encoded like the piston metadata.
+    pub face: u8,
     /// Progress of the move animation.
     pub progress: f32,
     /// True when the piston is extending, false when retracting.
     pub extending: bool,
 }
 
-impl Default for PistonBlockEntity {
-    fn default() -> Self {
-        Self { 
-            block: 0, 
-            metadata: 0, 
-            face: Face::PosY,
+impl StdbPistonBlockEntity {
+
+    /// Create a new moving block at the given position, the block will be placed at this
+    /// position when the move is finished.
+    pub fn new(dimension_id: i32, pos: IVec3, block: u8, metadata: u8, face: Face, extending: bool) -> Self {
+        Self {
+            block_entity_id: calc_block_entity_id(pos),
+            dimension_id,
+            pos: pos.into(),
+            block,
+            metadata,
+            face: face as u8,
             progress: 0.0,
-            extending: false,
+            extending,
         }
     }
-}
 
-impl PistonBlockEntity {
+    /// Get the face toward the block is moving.
+    pub fn get_face(&self) -> Face {
+        block::piston::get_face(self.face).unwrap_or(Face::PosY)
+    }
 
-    pub fn tick(&mut self, world: &mut StdbWorld, pos: IVec3) {
-        let _ = (world, pos);
-        // TODO:
+    /// Tick the moving block, the move lasts two ticks and the block is placed on the
+    /// following tick.
+    pub fn tick(mut self, world: &mut StdbWorld, cache: &mut ChunkCache) {
+        if self.progress >= 1.0 {
+            self.finish(world, cache);
+        } else {
+            // TODO: Push entities in the way when entities are stored in the module.
+            self.progress = (self.progress + 0.5).min(1.0);
+            self.set();
+        }
+    }
+
+    /// Immediately finish the move, the block entity is removed and the moving block is
+    /// replaced by the moved block.
+    pub fn finish(self, world: &mut StdbWorld, cache: &mut ChunkCache) {
+        let pos = IVec3::from(self.pos);
+        Self::delete_by_block_entity_id(&self.block_entity_id);
+        if world.is_block(pos, block::PISTON_MOVING, cache) {
+            world.set_block_notify(pos, self.block, self.metadata, cache);
+        }
     }
 
 }
+
+super::impl_block_entity_table!(StdbPistonBlockEntity);
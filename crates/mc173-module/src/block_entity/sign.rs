@@ -1,8 +1,119 @@
 //! Sign block entity.
 
+use glam::IVec3;
+use spacetimedb::spacetimedb;
+
+use crate::chunk::calc_chunk_pos_unchecked;
+use crate::i32vec3::StdbI32Vec3;
+use crate::stdb::chunk::StdbChunk;
+
+use super::calc_block_entity_id;
+
+
+/// Number of text lines on a sign.
+pub const SIGN_LINES_COUNT: usize = 4;
+/// Maximum number of characters on a single sign line.
+pub const SIGN_LINE_MAX_LEN: usize = 15;
+
+/// The characters that the client is able to render, any other character is refused in
+/// sign lines.
+const ALLOWED_CHARS: &str = " !\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_'abcdefghijklmnopqrstuvwxyz{|}~⌂ÇüéâäàåçêëèïîìÄÅÉæÆôöòûùÿÖÜø£Ø×ƒáíóúñÑªº¿®¬½¼¡«»";
+
+#[spacetimedb(table(public))]
+#[derive(Debug, Clone)]
+pub struct StdbSignBlockEntity {
+    /// The block entity id, computed from its position.
+    #[primarykey]
+    pub block_entity_id: u64,
+    /// The position of the sign in the world.
+    pub pos: StdbI32Vec3,
+    /// Id of the chunk containing the sign, used by the proxy to send the signs of a
+    /// chunk when it is sent to a player.
+    pub chunk_id: u32,
+    /// Text lines of this sign block.
+    pub lines: Vec<String>,
+    /// Entity id of the player that placed the sign, only this player can edit it.
+    pub placer_id: Option<u32>,
+    /// True while the sign text can still be set by its placer, this is cleared once the
+    /// sign has been edited.
+    ///
+    /// REF: TileEntitySign::isEditable
+    pub editable: bool,
+}
+
+impl StdbSignBlockEntity {
+
+    /// Create a new sign with empty lines at the given position, it can be edited once
+    /// by the player with the given entity id.
+    pub fn new(pos: IVec3, placer_id: Option<u32>) -> Self {
+        let (cx, cz) = calc_chunk_pos_unchecked(pos);
+        Self {
+            block_entity_id: calc_block_entity_id(pos),
+            pos: pos.into(),
+            chunk_id: StdbChunk::xz_to_chunk_id(cx, cz),
+            lines: vec![String::new(); SIGN_LINES_COUNT],
+            placer_id,
+            editable: placer_id.is_some(),
+        }
+    }
+
+    /// Return true if the player with the given entity id is allowed to edit this sign.
+    pub fn can_edit(&self, entity_id: u32) -> bool {
+        self.editable && self.placer_id == Some(entity_id)
+    }
+
+}
+
+super::impl_block_entity_table!(StdbSignBlockEntity);
+
+/// Return true if the given line can be written on a sign, it must not be too long and
+/// all of its characters must be allowed.
+pub fn is_valid_line(line: &str) -> bool {
+    line.chars().count() <= SIGN_LINE_MAX_LEN && line.chars().all(|c| ALLOWED_CHARS.contains(c))
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn valid_line() {
+
+        assert!(is_valid_line(""));
+        assert!(is_valid_line("Hello World!"));
+        assert!(is_valid_line("123456789012345"));
+        assert!(is_valid_line("Ça va? ½ £"));
+
+        // Too long, the length is counted in characters, not bytes.
+        assert!(!is_valid_line("1234567890123456"));
+        assert!(is_valid_line("ééééééééééééééé"));
+        assert!(!is_valid_line("éééééééééééééééé"));
+
+        // Characters that the client cannot render.
+        assert!(!is_valid_line("line\nbreak"));
+        assert!(!is_valid_line("tab\t"));
+        assert!(!is_valid_line("§4red"));
+        assert!(!is_valid_line("€"));
+
+    }
+
+    #[test]
+    fn editable() {
+
+        let mut sign = StdbSignBlockEntity::new(IVec3::new(0, 64, 0), Some(12));
+        assert!(sign.can_edit(12));
+        assert!(!sign.can_edit(13));
+
+        // Once edited, even the placer cannot edit the sign.
+        sign.editable = false;
+        assert!(!sign.can_edit(12));
+
+        // Signs without placer cannot be edited.
+        let sign = StdbSignBlockEntity::new(IVec3::new(0, 64, 0), None);
+        assert!(!sign.can_edit(12));
+
+    }
 
-#[derive(Debug, Clone, Default)]
-pub struct SignBlockEntity {
-    /// Text line of this sign block.
-    pub lines: Box<[String; 4]>,
 }
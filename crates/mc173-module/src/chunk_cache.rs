@@ -1,14 +1,18 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use crate::stdb::chunk::StdbChunk;
 
 pub struct ChunkCache {
     pub chunks: HashMap<u32, Option<StdbChunk>>,
+    /// Chunks that have been set since this cache was created, only these chunks are
+    /// written back on apply, so that chunks that are only read are not sent again.
+    pub dirty: HashSet<u32>,
 }
 
 impl ChunkCache {
     pub fn new() -> Self {
         Self {
             chunks: HashMap::new(),
+            dirty: HashSet::new(),
         }
     }
 
@@ -25,26 +29,44 @@ impl ChunkCache {
         }
     }
 
+    /// Get a reference to a chunk, this avoids copying the whole chunk when only a few
+    /// values are read from it.
+    pub fn get_chunk_ref(&mut self, x: i32, z: i32) -> Option<&StdbChunk> {
+        let chunk_id = StdbChunk::xz_to_chunk_id(x, z);
+        self.chunks.entry(chunk_id)
+            .or_insert_with(|| StdbChunk::filter_by_chunk_id(&chunk_id))
+            .as_ref()
+    }
+
+    /// Get a mutable reference to a chunk, the chunk is considered modified and will be
+    /// written back on apply.
+    pub fn get_chunk_mut(&mut self, x: i32, z: i32) -> Option<&mut StdbChunk> {
+        let chunk_id = StdbChunk::xz_to_chunk_id(x, z);
+        let chunk = self.chunks.entry(chunk_id)
+            .or_insert_with(|| StdbChunk::filter_by_chunk_id(&chunk_id))
+            .as_mut()?;
+        self.dirty.insert(chunk_id);
+        Some(chunk)
+    }
+
     pub fn set_chunk(&mut self, mut chunk: StdbChunk) {
         let chunk_id = StdbChunk::xz_to_chunk_id(chunk.x, chunk.z);
         chunk.chunk_id = chunk_id;
         self.chunks.insert(chunk_id, Some(chunk));
+        self.dirty.insert(chunk_id);
     }
 
     pub fn apply(&self) {
-        for (chunk_id, chunk) in self.chunks.iter() {
-            match chunk {
-                Some(chunk) => {
-                    match StdbChunk::filter_by_chunk_id(&chunk_id) {
-                        None => {
-                            StdbChunk::insert(chunk.clone()).unwrap();
-                        }
-                        Some(_) => {
-                            StdbChunk::update_by_chunk_id(&chunk_id, chunk.clone());
-                        }
+        for chunk_id in self.dirty.iter() {
+            if let Some(chunk) = &self.chunks[chunk_id] {
+                match StdbChunk::filter_by_chunk_id(chunk_id) {
+                    None => {
+                        StdbChunk::insert(chunk.clone()).unwrap();
+                    }
+                    Some(_) => {
+                        StdbChunk::update_by_chunk_id(chunk_id, chunk.clone());
                     }
                 }
-                None => (),
             }
         }
     }
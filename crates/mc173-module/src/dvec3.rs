@@ -2,7 +2,7 @@ use std::ops::Add;
 use glam::{DVec3, IVec3};
 use spacetimedb::SpacetimeType;
 
-#[derive(SpacetimeType, Clone, Copy, Debug)]
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
 pub struct StdbDVec3 {
     pub x: f64,
     pub y: f64,
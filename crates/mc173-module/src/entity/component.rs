@@ -0,0 +1,541 @@
+//! Storage of entities in component tables.
+//!
+//! Each entity is stored as a base row in [`StdbEntity`], common to all entities, with
+//! an optional row for its family ([`StdbLivingEntity`] or [`StdbProjectileEntity`]) and
+//! an optional row for its kind-specific data ([`StdbItemEntity`], [`StdbHuman`] or
+//! [`StdbKindEntity`]). Kinds without any specific data have no kind row. The entity is
+//! loaded into an [`Entity`] to be ticked and then stored back, its size and bounding
+//! box are not stored but recomputed from the position when loading.
+
+use glam::{DVec3, IVec3};
+use spacetimedb::{spacetimedb, SpacetimeType};
+
+use crate::chunk::calc_entity_chunk_pos;
+use crate::dvec3::StdbDVec3;
+use crate::i32vec3::StdbI32Vec3;
+use crate::item::ItemStack;
+use crate::rand::JavaRandom;
+use crate::stdb::chunk::StdbChunk;
+use crate::vec2::StdbVec2;
+
+use super::{Entity, EntityKind, BaseKind, LivingKind, ProjectileKind,
+    Base, Living, Projectile, ProjectileHit, Hurt, LookTarget, Path,
+    Item, Painting, PaintingOrientation, PaintingArt, Minecart, Bobber, FallingBlock, Tnt,
+    Arrow, Human, StdbHuman, Ghast, Slime, Pig, Chicken, Sheep, Squid, Wolf,
+    Creeper, PigZombie};
+
+
+/// The base component of every entity, see [`Base`].
+#[spacetimedb(table(public))]
+#[spacetimedb(index(btree, chunk_id))]
+#[derive(Debug, Clone)]
+pub struct StdbEntity {
+    /// The unique entity id, allocated by the world.
+    #[primarykey]
+    pub entity_id: u32,
+    /// The kind of entity, giving the other components of this entity.
+    pub kind: EntityKind,
+    /// The dimension in which this entity lives.
+    pub dimension_id: i32,
+    /// Id of the chunk the entity position is in, used to find entities around a
+    /// position and the players observing the entity.
+    pub chunk_id: u32,
+    pub persistent: bool,
+    pub pos: StdbDVec3,
+    pub vel: StdbDVec3,
+    /// Yaw and pitch angles in radians.
+    pub look: StdbVec2,
+    pub lifetime: u32,
+    pub can_pickup: bool,
+    pub no_clip: bool,
+    pub on_ground: bool,
+    pub in_water: bool,
+    pub in_lava: bool,
+    pub fall_distance: f32,
+    pub fire_time: u32,
+    pub air_time: u32,
+    pub hurt: Vec<Hurt>,
+    pub rider_id: Option<u32>,
+    pub bobber_id: Option<u32>,
+    pub rand: JavaRandom,
+}
+
+/// The living component of animals, mobs and humans, see [`Living`].
+#[spacetimedb(table(public))]
+#[derive(Debug, Clone)]
+pub struct StdbLivingEntity {
+    #[primarykey]
+    pub entity_id: u32,
+    pub artificial: bool,
+    pub health: u16,
+    pub hurt_last_damage: u16,
+    pub hurt_time: u16,
+    pub attack_time: u16,
+    pub death_time: u16,
+    pub accel_strafing: f32,
+    pub accel_forward: f32,
+    pub yaw_velocity: f32,
+    pub jumping: bool,
+    pub look_target: Option<LookTarget>,
+    pub attack_target: Option<u32>,
+    /// Points of the path this creature needs to follow, if any.
+    pub path: Option<Vec<StdbI32Vec3>>,
+    /// Index of the current point in the path.
+    pub path_index: u32,
+    pub wander_time: u16,
+}
+
+/// The projectile component of arrows, eggs, fireballs, snowballs and bobbers, see
+/// [`Projectile`].
+#[spacetimedb(table(public))]
+#[derive(Debug, Clone)]
+pub struct StdbProjectileEntity {
+    #[primarykey]
+    pub entity_id: u32,
+    pub state: Option<StdbProjectileHit>,
+    pub state_time: u16,
+    pub owner_id: Option<u32>,
+    pub shake: u8,
+}
+
+/// Stored version of [`ProjectileHit`].
+#[derive(SpacetimeType, Debug, Clone)]
+pub struct StdbProjectileHit {
+    pub pos: StdbI32Vec3,
+    pub block: u8,
+    pub metadata: u8,
+}
+
+/// The kind component of item entities, see [`Item`].
+#[spacetimedb(table(public))]
+#[derive(Debug, Clone)]
+pub struct StdbItemEntity {
+    #[primarykey]
+    pub entity_id: u32,
+    pub stack: ItemStack,
+    pub health: u16,
+    pub frozen_time: u32,
+}
+
+/// The kind component of all other entities that have specific data.
+#[spacetimedb(table(public))]
+#[derive(Debug, Clone)]
+pub struct StdbKindEntity {
+    #[primarykey]
+    pub entity_id: u32,
+    pub data: StdbKindData,
+}
+
+/// Kind-specific data of an entity, stored in [`StdbKindEntity`].
+#[derive(SpacetimeType, Debug, Clone, PartialEq)]
+pub enum StdbKindData {
+    Painting(StdbPainting),
+    Minecart(StdbMinecart),
+    FallingBlock(FallingBlock),
+    Tnt(Tnt),
+    Arrow(Arrow),
+    Fireball(StdbFireball),
+    Bobber(Bobber),
+    Ghast(StdbGhast),
+    Slime(Slime),
+    Pig(Pig),
+    Chicken(Chicken),
+    Sheep(Sheep),
+    Squid(Squid),
+    Wolf(Wolf),
+    Creeper(Creeper),
+    PigZombie(PigZombie),
+}
+
+/// Stored version of [`Painting`].
+#[derive(SpacetimeType, Debug, Clone, PartialEq)]
+pub struct StdbPainting {
+    pub block_pos: StdbI32Vec3,
+    pub orientation: PaintingOrientation,
+    pub art: PaintingArt,
+    pub check_valid_time: u8,
+}
+
+/// Stored version of [`Minecart`].
+#[derive(SpacetimeType, Debug, Clone, PartialEq)]
+pub enum StdbMinecart {
+    Normal,
+    /// The 27 stacks of the chest inventory.
+    Chest(Vec<ItemStack>),
+    Furnace(StdbFurnaceMinecart),
+}
+
+/// Stored version of [`Minecart::Furnace`].
+#[derive(SpacetimeType, Debug, Clone, PartialEq)]
+pub struct StdbFurnaceMinecart {
+    pub push_x: f64,
+    pub push_z: f64,
+    pub fuel: u32,
+}
+
+/// Stored version of [`Fireball`].
+#[derive(SpacetimeType, Debug, Clone, PartialEq)]
+pub struct StdbFireball {
+    pub accel: StdbDVec3,
+}
+
+/// Stored version of [`Ghast`].
+#[derive(SpacetimeType, Debug, Clone, PartialEq)]
+pub struct StdbGhast {
+    pub waypoint: StdbDVec3,
+    pub waypoint_check_time: u8,
+    pub attack_target_time: u8,
+}
+
+
+impl StdbEntity {
+
+    /// Teleport this entity row to the given position, updating its chunk id. This is
+    /// used for entities moved by their client, such as players.
+    pub fn teleport(&mut self, pos: DVec3) {
+        self.pos = pos.into();
+        self.chunk_id = calc_chunk_id(pos);
+    }
+
+}
+
+
+/// Return true if an entity exists with the given id.
+pub fn contains_entity(id: u32) -> bool {
+    StdbEntity::filter_by_entity_id(&id).is_some()
+}
+
+/// Load an entity from all its component rows, none is returned if the entity doesn't
+/// exist. The returned entity is resized so its bounding box is coherent.
+pub fn load_entity(id: u32) -> Option<Entity> {
+
+    let row = StdbEntity::filter_by_entity_id(&id)?;
+
+    let base = Base {
+        persistent: row.persistent,
+        pos: row.pos.into(),
+        vel: row.vel.into(),
+        look: row.look.into(),
+        lifetime: row.lifetime,
+        can_pickup: row.can_pickup,
+        no_clip: row.no_clip,
+        on_ground: row.on_ground,
+        in_water: row.in_water,
+        in_lava: row.in_lava,
+        fall_distance: row.fall_distance,
+        fire_time: row.fire_time,
+        air_time: row.air_time,
+        hurt: row.hurt,
+        rider_id: row.rider_id,
+        bobber_id: row.bobber_id,
+        rand: row.rand,
+        ..Default::default()
+    };
+
+    let Entity(_, mut base_kind) = *row.kind.new_default(DVec3::ZERO);
+
+    match &mut base_kind {
+        BaseKind::Item(item) => {
+            if let Some(row) = StdbItemEntity::filter_by_entity_id(&id) {
+                *item = Item {
+                    stack: row.stack,
+                    health: row.health,
+                    frozen_time: row.frozen_time,
+                };
+            }
+        }
+        BaseKind::Living(living, living_kind) => {
+            if let Some(row) = StdbLivingEntity::filter_by_entity_id(&id) {
+                *living = Living {
+                    artificial: row.artificial,
+                    health: row.health,
+                    hurt_last_damage: row.hurt_last_damage,
+                    hurt_time: row.hurt_time,
+                    attack_time: row.attack_time,
+                    death_time: row.death_time,
+                    accel_strafing: row.accel_strafing,
+                    accel_forward: row.accel_forward,
+                    yaw_velocity: row.yaw_velocity,
+                    jumping: row.jumping,
+                    look_target: row.look_target,
+                    attack_target: row.attack_target,
+                    path: row.path.map(|points| Path {
+                        points: points.into_iter().map(IVec3::from).collect(),
+                        index: row.path_index as usize,
+                    }),
+                    wander_time: row.wander_time,
+                };
+            }
+            if let LivingKind::Human(human) = living_kind {
+                if let Some(row) = StdbHuman::filter_by_entity_id(&id) {
+                    *human = Human {
+                        username: row.username,
+                        sleeping: row.sleeping,
+                        sleep_time: row.sleep_time,
+                        bed_pos: row.bed_pos.map(IVec3::from),
+                        sneaking: row.sneaking,
+                    };
+                }
+            }
+        }
+        BaseKind::Projectile(projectile, _) => {
+            if let Some(row) = StdbProjectileEntity::filter_by_entity_id(&id) {
+                *projectile = Projectile {
+                    state: row.state.map(|hit| ProjectileHit {
+                        pos: hit.pos.into(),
+                        block: hit.block,
+                        metadata: hit.metadata,
+                    }),
+                    state_time: row.state_time,
+                    owner_id: row.owner_id,
+                    shake: row.shake,
+                };
+            }
+        }
+        _ => {}
+    }
+
+    if let Some(row) = StdbKindEntity::filter_by_entity_id(&id) {
+        load_kind_data(&mut base_kind, row.data);
+    }
+
+    let mut entity = Entity(base, base_kind);
+    entity.resize();
+    Some(entity)
+
+}
+
+/// Store an entity in all its component rows, rows are inserted if the entity doesn't
+/// exist yet, or updated otherwise. The entity kind must not change between stores.
+/// When the previous state of the entity is given, only the rows of the components
+/// that changed are updated.
+pub fn store_entity(id: u32, dimension_id: i32, entity: &Entity, prev: Option<&Entity>) {
+
+    let Entity(base, base_kind) = entity;
+    let (prev_base, prev_kind) = match prev {
+        Some(Entity(prev_base, prev_kind)) => (Some(prev_base), Some(prev_kind)),
+        None => (None, None),
+    };
+
+    let exists = contains_entity(id);
+
+    if prev_base != Some(base) {
+        set_row(exists, StdbEntity {
+            entity_id: id,
+            kind: entity.kind(),
+            dimension_id,
+            chunk_id: calc_chunk_id(base.pos),
+            persistent: base.persistent,
+            pos: base.pos.into(),
+            vel: base.vel.into(),
+            look: base.look.into(),
+            lifetime: base.lifetime,
+            can_pickup: base.can_pickup,
+            no_clip: base.no_clip,
+            on_ground: base.on_ground,
+            in_water: base.in_water,
+            in_lava: base.in_lava,
+            fall_distance: base.fall_distance,
+            fire_time: base.fire_time,
+            air_time: base.air_time,
+            hurt: base.hurt.clone(),
+            rider_id: base.rider_id,
+            bobber_id: base.bobber_id,
+            rand: base.rand.clone(),
+        }, |row| StdbEntity::insert(row).is_ok(), |row| StdbEntity::update_by_entity_id(&id, row));
+    }
+
+    match base_kind {
+        BaseKind::Item(item) if !matches!(prev_kind, Some(BaseKind::Item(prev_item)) if prev_item == item) => {
+            set_row(exists, StdbItemEntity {
+                entity_id: id,
+                stack: item.stack,
+                health: item.health,
+                frozen_time: item.frozen_time,
+            }, |row| StdbItemEntity::insert(row).is_ok(), |row| StdbItemEntity::update_by_entity_id(&id, row));
+        }
+        BaseKind::Living(living, living_kind) => {
+            if !matches!(prev_kind, Some(BaseKind::Living(prev_living, _)) if prev_living == living) {
+                set_row(exists, StdbLivingEntity {
+                    entity_id: id,
+                    artificial: living.artificial,
+                    health: living.health,
+                    hurt_last_damage: living.hurt_last_damage,
+                    hurt_time: living.hurt_time,
+                    attack_time: living.attack_time,
+                    death_time: living.death_time,
+                    accel_strafing: living.accel_strafing,
+                    accel_forward: living.accel_forward,
+                    yaw_velocity: living.yaw_velocity,
+                    jumping: living.jumping,
+                    look_target: living.look_target.clone(),
+                    attack_target: living.attack_target,
+                    path: living.path.as_ref().map(|path| path.points.iter().copied().map(StdbI32Vec3::from).collect()),
+                    path_index: living.path.as_ref().map_or(0, |path| path.index as u32),
+                    wander_time: living.wander_time,
+                }, |row| StdbLivingEntity::insert(row).is_ok(), |row| StdbLivingEntity::update_by_entity_id(&id, row));
+            }
+            if let LivingKind::Human(human) = living_kind {
+                if !matches!(prev_kind, Some(BaseKind::Living(_, LivingKind::Human(prev_human))) if prev_human == human) {
+                    set_row(exists, StdbHuman {
+                        entity_id: id,
+                        username: human.username.clone(),
+                        sleeping: human.sleeping,
+                        sleep_time: human.sleep_time,
+                        bed_pos: human.bed_pos.map(StdbI32Vec3::from),
+                        sneaking: human.sneaking,
+                    }, |row| StdbHuman::insert(row).is_ok(), |row| StdbHuman::update_by_entity_id(&id, row));
+                }
+            }
+        }
+        BaseKind::Projectile(projectile, _) if !matches!(prev_kind, Some(BaseKind::Projectile(prev_projectile, _)) if prev_projectile == projectile) => {
+            set_row(exists, StdbProjectileEntity {
+                entity_id: id,
+                state: projectile.state.map(|hit| StdbProjectileHit {
+                    pos: hit.pos.into(),
+                    block: hit.block,
+                    metadata: hit.metadata,
+                }),
+                state_time: projectile.state_time,
+                owner_id: projectile.owner_id,
+                shake: projectile.shake,
+            }, |row| StdbProjectileEntity::insert(row).is_ok(), |row| StdbProjectileEntity::update_by_entity_id(&id, row));
+        }
+        _ => {}
+    }
+
+    if let Some(data) = store_kind_data(base_kind) {
+        if prev_kind.and_then(store_kind_data).as_ref() != Some(&data) {
+            set_row(exists, StdbKindEntity {
+                entity_id: id,
+                data,
+            }, |row| StdbKindEntity::insert(row).is_ok(), |row| StdbKindEntity::update_by_entity_id(&id, row));
+        }
+    }
+
+}
+
+/// Delete all component rows of an entity, returning true if the entity existed.
+pub fn delete_entity(id: u32) -> bool {
+    if !StdbEntity::delete_by_entity_id(&id) {
+        return false;
+    }
+    StdbLivingEntity::delete_by_entity_id(&id);
+    StdbProjectileEntity::delete_by_entity_id(&id);
+    StdbItemEntity::delete_by_entity_id(&id);
+    StdbHuman::delete_by_entity_id(&id);
+    StdbKindEntity::delete_by_entity_id(&id);
+    true
+}
+
+/// Calculate the id of the chunk an entity at the given position is in.
+#[inline]
+pub fn calc_chunk_id(pos: DVec3) -> u32 {
+    let (cx, cz) = calc_entity_chunk_pos(pos);
+    StdbChunk::xz_to_chunk_id(cx, cz)
+}
+
+/// Internal function to insert or update a component row.
+#[inline]
+fn set_row<T>(exists: bool, row: T, insert: impl FnOnce(T) -> bool, update: impl FnOnce(T) -> bool) {
+    let success = if exists { update(row) } else { insert(row) };
+    debug_assert!(success, "incoherent entity component row");
+}
+
+/// Internal function to load kind-specific data into the entity kind, the data is
+/// ignored if it doesn't match the kind.
+fn load_kind_data(base_kind: &mut BaseKind, data: StdbKindData) {
+    match (base_kind, data) {
+        (BaseKind::Painting(painting), StdbKindData::Painting(data)) => {
+            *painting = Painting {
+                block_pos: data.block_pos.into(),
+                orientation: data.orientation,
+                art: data.art,
+                check_valid_time: data.check_valid_time,
+            };
+        }
+        (BaseKind::Minecart(minecart), StdbKindData::Minecart(data)) => {
+            *minecart = match data {
+                StdbMinecart::Normal => Minecart::Normal,
+                StdbMinecart::Chest(stacks) => {
+                    let mut inv = Box::new([ItemStack::EMPTY; 27]);
+                    for (stack, data_stack) in inv.iter_mut().zip(stacks) {
+                        *stack = data_stack;
+                    }
+                    Minecart::Chest { inv }
+                }
+                StdbMinecart::Furnace(data) => Minecart::Furnace {
+                    push_x: data.push_x,
+                    push_z: data.push_z,
+                    fuel: data.fuel,
+                },
+            };
+        }
+        (BaseKind::FallingBlock(falling_block), StdbKindData::FallingBlock(data)) => *falling_block = data,
+        (BaseKind::Tnt(tnt), StdbKindData::Tnt(data)) => *tnt = data,
+        (BaseKind::Projectile(_, ProjectileKind::Arrow(arrow)), StdbKindData::Arrow(data)) => *arrow = data,
+        (BaseKind::Projectile(_, ProjectileKind::Fireball(fireball)), StdbKindData::Fireball(data)) => {
+            fireball.accel = data.accel.into();
+        }
+        (BaseKind::Projectile(_, ProjectileKind::Bobber(bobber)), StdbKindData::Bobber(data)) => *bobber = data,
+        (BaseKind::Living(_, LivingKind::Ghast(ghast)), StdbKindData::Ghast(data)) => {
+            *ghast = Ghast {
+                waypoint: data.waypoint.into(),
+                waypoint_check_time: data.waypoint_check_time,
+                attack_target_time: data.attack_target_time,
+            };
+        }
+        (BaseKind::Living(_, LivingKind::Slime(slime)), StdbKindData::Slime(data)) => *slime = data,
+        (BaseKind::Living(_, LivingKind::Pig(pig)), StdbKindData::Pig(data)) => *pig = data,
+        (BaseKind::Living(_, LivingKind::Chicken(chicken)), StdbKindData::Chicken(data)) => *chicken = data,
+        (BaseKind::Living(_, LivingKind::Sheep(sheep)), StdbKindData::Sheep(data)) => *sheep = data,
+        (BaseKind::Living(_, LivingKind::Squid(squid)), StdbKindData::Squid(data)) => *squid = data,
+        (BaseKind::Living(_, LivingKind::Wolf(wolf)), StdbKindData::Wolf(data)) => *wolf = data,
+        (BaseKind::Living(_, LivingKind::Creeper(creeper)), StdbKindData::Creeper(data)) => *creeper = data,
+        (BaseKind::Living(_, LivingKind::PigZombie(pig_zombie)), StdbKindData::PigZombie(data)) => *pig_zombie = data,
+        _ => {}
+    }
+}
+
+/// Internal function to get the kind-specific data to store for an entity kind, none if
+/// the kind has no specific data or if it's stored in its own table.
+fn store_kind_data(base_kind: &BaseKind) -> Option<StdbKindData> {
+    Some(match base_kind {
+        BaseKind::Painting(painting) => StdbKindData::Painting(StdbPainting {
+            block_pos: painting.block_pos.into(),
+            orientation: painting.orientation,
+            art: painting.art,
+            check_valid_time: painting.check_valid_time,
+        }),
+        BaseKind::Minecart(minecart) => StdbKindData::Minecart(match minecart {
+            Minecart::Normal => StdbMinecart::Normal,
+            Minecart::Chest { inv } => StdbMinecart::Chest(inv.to_vec()),
+            &Minecart::Furnace { push_x, push_z, fuel } => StdbMinecart::Furnace(StdbFurnaceMinecart {
+                push_x,
+                push_z,
+                fuel,
+            }),
+        }),
+        BaseKind::FallingBlock(falling_block) => StdbKindData::FallingBlock(falling_block.clone()),
+        BaseKind::Tnt(tnt) => StdbKindData::Tnt(tnt.clone()),
+        BaseKind::Projectile(_, ProjectileKind::Arrow(arrow)) => StdbKindData::Arrow(arrow.clone()),
+        BaseKind::Projectile(_, ProjectileKind::Fireball(fireball)) => StdbKindData::Fireball(StdbFireball {
+            accel: fireball.accel.into(),
+        }),
+        BaseKind::Projectile(_, ProjectileKind::Bobber(bobber)) => StdbKindData::Bobber(bobber.clone()),
+        BaseKind::Living(_, LivingKind::Ghast(ghast)) => StdbKindData::Ghast(StdbGhast {
+            waypoint: ghast.waypoint.into(),
+            waypoint_check_time: ghast.waypoint_check_time,
+            attack_target_time: ghast.attack_target_time,
+        }),
+        BaseKind::Living(_, LivingKind::Slime(slime)) => StdbKindData::Slime(slime.clone()),
+        BaseKind::Living(_, LivingKind::Pig(pig)) => StdbKindData::Pig(pig.clone()),
+        BaseKind::Living(_, LivingKind::Chicken(chicken)) => StdbKindData::Chicken(chicken.clone()),
+        BaseKind::Living(_, LivingKind::Sheep(sheep)) => StdbKindData::Sheep(sheep.clone()),
+        BaseKind::Living(_, LivingKind::Squid(squid)) => StdbKindData::Squid(squid.clone()),
+        BaseKind::Living(_, LivingKind::Wolf(wolf)) => StdbKindData::Wolf(wolf.clone()),
+        BaseKind::Living(_, LivingKind::Creeper(creeper)) => StdbKindData::Creeper(creeper.clone()),
+        BaseKind::Living(_, LivingKind::PigZombie(pig_zombie)) => StdbKindData::PigZombie(pig_zombie.clone()),
+        _ => return None,
+    })
+}
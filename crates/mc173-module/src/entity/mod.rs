@@ -1,16 +1,19 @@
 //! Entities structures and logic implementation.
 
 use glam::{DVec3, Vec2, IVec3};
-use spacetimedb::spacetimedb;
+use spacetimedb::{spacetimedb, SpacetimeType};
 use crate::block::material::Material;
 use crate::util::default as def;
 use crate::geom::BoundingBox;
 use crate::rand::JavaRandom;
 use crate::item::ItemStack;
+use crate::i32vec3::StdbI32Vec3;
 use crate::world::StdbWorld;
+use crate::chunk_cache::ChunkCache;
 use crate::block;
 
 pub mod common;
+pub mod component;
 
 mod tick;
 mod tick_state;
@@ -18,13 +21,15 @@ mod tick_ai;
 mod tick_attack;
 
 // use tick_state::tick_state;
-// use tick_ai::tick_ai;
-// use tick_attack::tick_attack;
+use tick_ai::tick_ai;
+use tick_attack::tick_attack;
+
+pub(crate) use tick_ai::reset_path_finder_budget;
 
 
 /// Kind of entity, without actual data. This enumeration can be used to construct a
 /// real entity instance with default values, to be modified later.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, SpacetimeType)]
 pub enum EntityKind {
     Item,
     Painting,
@@ -73,11 +78,11 @@ pub enum EntityCategory {
 /// which is common to all entities, and the base kind that is the first sub division in
 /// entities. Each subdivision in the entity family tree is composed of the family's
 /// common data as the first tuple element, and the kind of entity as the second element.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Entity(pub Base, pub BaseKind);
 
 /// Kind of base entity.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum BaseKind {
     Item(Item),
     Painting(Painting),
@@ -91,7 +96,7 @@ pub enum BaseKind {
 }
 
 /// Kind of projectile entity.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ProjectileKind {
     Arrow(Arrow),
     Egg(Egg),
@@ -101,7 +106,7 @@ pub enum ProjectileKind {
 }
 
 /// Kind of living entity, this include animals and mobs.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum LivingKind {
     // Not categorized
     Human(Human),
@@ -124,7 +129,7 @@ pub enum LivingKind {
 }
 
 /// The base data common to all entities.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Base {
     /// Tell if this entity is persistent or not. A persistent entity is saved with its
     /// chunk, but non-persistent entities are no saved. For example, all player entities
@@ -188,7 +193,7 @@ pub struct Base {
 }
 
 /// Hurt data to apply on the next tick to the entity.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, SpacetimeType)]
 pub struct Hurt {
     /// The damage to deal.
     pub damage: u16,
@@ -198,7 +203,7 @@ pub struct Hurt {
 }
 
 /// The data common to all living entities.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Living {
     /// Set to true if an entity is artificial, as opposed to natural. If not artificial,
     /// an entity is despawned when too far from the closest player (maximum distance of 
@@ -238,7 +243,7 @@ pub struct Living {
 }
 
 /// The data common to all projectile entities.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Projectile {
     /// The state of the projectile, none when in air, set to block/metadata when in.
     pub state: Option<ProjectileHit>,
@@ -251,7 +256,7 @@ pub struct Projectile {
     pub shake: u8,
 }
 
-#[derive(Debug, Copy, Clone, Default)]
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
 pub struct ProjectileHit {
     /// The block position the projectile is in.
     pub pos: IVec3,
@@ -261,7 +266,7 @@ pub struct ProjectileHit {
     pub metadata: u8,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Item {
     /// The item stack represented by this entity.
     pub stack: ItemStack,
@@ -271,7 +276,7 @@ pub struct Item {
     pub frozen_time: u32,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Painting {
     /// Block position of this painting.
     pub block_pos: IVec3,
@@ -283,7 +288,7 @@ pub struct Painting {
     pub check_valid_time: u8,
 }
 
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, SpacetimeType)]
 pub enum PaintingOrientation {
     #[default]
     NegX,
@@ -292,7 +297,7 @@ pub enum PaintingOrientation {
     PosZ,
 }
 
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, SpacetimeType)]
 pub enum PaintingArt {
     #[default]
     Kebab,
@@ -322,10 +327,10 @@ pub enum PaintingArt {
     DonkeyKong,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Boat { }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub enum Minecart { 
     /// A normal minecart for living entity transportation.
     #[default]
@@ -344,7 +349,7 @@ pub enum Minecart {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, SpacetimeType)]
 pub struct Bobber { 
     /// Some entity id if this bobber is attached to an entity instead of floating in 
     /// water.
@@ -354,10 +359,10 @@ pub struct Bobber {
     pub catch_time: u16,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct LightningBolt { }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, SpacetimeType)]
 pub struct FallingBlock {
     /// Number of ticks since this block is falling.
     pub fall_time: u32,
@@ -365,41 +370,46 @@ pub struct FallingBlock {
     pub block_id: u8,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, SpacetimeType)]
 pub struct Tnt {
     pub fuse_time: u32,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, SpacetimeType)]
 pub struct Arrow {
     /// Set to true for arrows that are sent by players and therefore can be picked up.
     pub from_player: bool,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Egg { }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Fireball {
     /// Acceleration to that fireball.
     pub accel: DVec3,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Snowball { }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Human {
     /// The player username.
     pub username: String,
     /// True when the player is sleeping.
     pub sleeping: bool,
+    /// Number of ticks the player has been sleeping, up to 100 when fully asleep.
+    pub sleep_time: u8,
+    /// Position of the head of the last bed the player slept in, this is where the
+    /// player respawns.
+    pub bed_pos: Option<IVec3>,
     /// True when the player is sneaking.
     pub sneaking: bool,
 }
 
 #[spacetimedb(table(public))]
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct StdbHuman {
     #[primarykey]
     pub entity_id: u32,
@@ -407,11 +417,15 @@ pub struct StdbHuman {
     pub username: String,
     /// True when the player is sleeping.
     pub sleeping: bool,
+    /// Number of ticks the player has been sleeping.
+    pub sleep_time: u8,
+    /// Position of the last bed the player slept in.
+    pub bed_pos: Option<StdbI32Vec3>,
     /// True when the player is sneaking.
     pub sneaking: bool,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Ghast {
     /// The ghast waypoint defaults to zero.
     pub waypoint: DVec3,
@@ -421,7 +435,7 @@ pub struct Ghast {
     pub attack_target_time: u8,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, SpacetimeType)]
 pub struct Slime {
     /// Size of the slime, this is a bit different because here the size is initially 
     /// at 0 and this is equivalent to 1 in Notchian implementation.
@@ -430,28 +444,28 @@ pub struct Slime {
     pub jump_remaining_time: u32,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, SpacetimeType)]
 pub struct Pig {
     /// True when the pig has a saddle.
     pub saddle: bool,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, SpacetimeType)]
 pub struct Chicken {
     /// Ticks remaining until this chicken lays an egg.
     pub next_egg_ticks: u32,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Cow { }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, SpacetimeType)]
 pub struct Sheep {
     pub sheared: bool,
     pub color: u8, // TODO: Color enumeration.
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, SpacetimeType)]
 pub struct Squid {
     /// Animation progress for the squid.
     pub animation: f32,
@@ -459,14 +473,14 @@ pub struct Squid {
     pub animation_speed: f32,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, SpacetimeType)]
 pub struct Wolf {
     pub angry: bool,
     pub sitting: bool,
     pub owner: Option<String>,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, SpacetimeType)]
 pub struct Creeper { 
     /// True when the creeper is powered.
     pub powered: bool,
@@ -474,21 +488,21 @@ pub struct Creeper {
     pub ignited_time: Option<u16>
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Giant { }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, SpacetimeType)]
 pub struct PigZombie { 
     pub anger: bool,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Skeleton { }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Spider { }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Zombie { }
 
 
@@ -519,7 +533,7 @@ impl Size {
 }
 
 /// Define a target for an entity to look at.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, SpacetimeType)]
 pub struct LookTarget {
     /// The entity id to look at.
     pub entity_id: u32,
@@ -528,7 +542,7 @@ pub struct LookTarget {
 }
 
 /// A result of the path finder.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Path {
     pub points: Vec<IVec3>,
     pub index: usize,
@@ -574,13 +588,20 @@ impl Entity {
         self.kind().category()
     }
 
-    // /// This this entity from its id in a world.
-    // ///
-    // /// **This is really important to no change the entity kind when ticking the
-    // /// function.**
-    // pub fn tick(&mut self, world: &mut World, id: u32, nano_time: u128) {
-    //     tick::tick(world, id, self, nano_time);
-    // }
+    /// This this entity from its id in a world.
+    ///
+    /// **This is really important to no change the entity kind when ticking the
+    /// function.**
+    pub fn tick(&mut self, world: &mut StdbWorld, id: u32, cache: &mut ChunkCache) {
+        tick::tick(world, id, self, cache);
+    }
+
+    /// Tick this player entity from its id in a world, players are not ticked like
+    /// other entities because they are moved by their client, so only their state is
+    /// ticked (hurts, drowning, burning, pickups).
+    pub fn tick_player(&mut self, world: &mut StdbWorld, id: u32, cache: &mut ChunkCache) {
+        tick_state::tick_player(world, id, self, cache);
+    }
 
     /// Recompute this entity's size and recompute the bounding box from its position.
     pub fn resize(&mut self) {
@@ -648,82 +669,82 @@ impl Entity {
         common::update_bounding_box_from_pos(base);
     }
 
-    // /// Return true if the entity can naturally spawn at its current position (with
-    // /// synchronized bounding box) in the given world. The entity is mutated because its
-    // /// RNG may be used.
-    // pub fn can_natural_spawn(&mut self, world: &World) -> bool {
-    //
-    //     let Entity(base, BaseKind::Living(_, living_kind)) = self else {
-    //         // Non-living entities cannot naturally spawn.
-    //         return false;
-    //     };
-    //
-    //     let kind = living_kind.entity_kind();
-    //     let block_pos = IVec3 {
-    //         x: base.bb.center_x().floor() as i32,
-    //         y: base.bb.min.y.floor() as i32,
-    //         z: base.bb.center_z().floor() as i32,
-    //     };
-    //
-    //     let category = kind.category();
-    //
-    //     if category == EntityCategory::Animal {
-    //
-    //         // Animals can only spawn on grass blocks.
-    //         if !world.is_block(block_pos - IVec3::Y, block::GRASS) {
-    //             return false;
-    //         }
-    //
-    //         // Animals requires a light level of at least 9.
-    //         if world.get_light(block_pos).max() <= 8 {
-    //             return false;
-    //         }
-    //
-    //     } else if category == EntityCategory::Mob {
-    //
-    //         let light = world.get_light(block_pos);
-    //
-    //         // Lower chance of spawn if there is sky light.
-    //         if light.sky as i32 > base.rand.next_int_bounded(32) {
-    //             return false;
-    //         }
-    //
-    //         // Random spawning chance when light is under 8.
-    //         if light.max_real() as i32 > base.rand.next_int_bounded(8) {
-    //             return false;
-    //         }
-    //
-    //     }
-    //
-    //     if category != EntityCategory::Other {
-    //         let weight_func = common::path_weight_func(living_kind);
-    //         if weight_func(world, block_pos) < 0.0 {
-    //             return false;
-    //         }
-    //     }
-    //
-    //     // Any hard entity colliding prevent spawning.
-    //     if world.has_entity_colliding(base.bb, true) {
-    //         return false;
-    //     }
-    //
-    //     if category != EntityCategory::WaterAnimal {
-    //
-    //         // Any block colliding prevent spawning.
-    //         if world.iter_blocks_boxes_colliding(base.bb).next().is_some() {
-    //             return false;
-    //         }
-    //
-    //         // Any colliding fluid block prevent spawning.
-    //         if world.iter_blocks_in_box(base.bb).any(|(_pos, block, _)| block::material::is_fluid(block)) {
-    //             return false;
-    //         }
-    //
-    //     }
-    //
-    //     true
-    //
-    // }
+    /// Return true if the entity can naturally spawn at its current position (with
+    /// synchronized bounding box) in the given world. The entity is mutated because its
+    /// RNG may be used.
+    pub fn can_natural_spawn(&mut self, world: &StdbWorld, cache: &mut ChunkCache) -> bool {
+
+        let Entity(base, BaseKind::Living(_, living_kind)) = self else {
+            // Non-living entities cannot naturally spawn.
+            return false;
+        };
+
+        let kind = living_kind.entity_kind();
+        let block_pos = IVec3 {
+            x: base.bb.center_x().floor() as i32,
+            y: base.bb.min.y.floor() as i32,
+            z: base.bb.center_z().floor() as i32,
+        };
+
+        let category = kind.category();
+
+        if category == EntityCategory::Animal {
+
+            // Animals can only spawn on grass blocks.
+            if !world.is_block(block_pos - IVec3::Y, block::GRASS, cache) {
+                return false;
+            }
+
+            // Animals requires a light level of at least 9.
+            if world.get_light(block_pos, cache).max() <= 8 {
+                return false;
+            }
+
+        } else if category == EntityCategory::Mob {
+
+            let light = world.get_light(block_pos, cache);
+
+            // Lower chance of spawn if there is sky light.
+            if light.sky as i32 > base.rand.next_int_bounded(32) {
+                return false;
+            }
+
+            // Random spawning chance when light is under 8.
+            if light.max_real() as i32 > base.rand.next_int_bounded(8) {
+                return false;
+            }
+
+        }
+
+        if category != EntityCategory::Other {
+            let weight_func = common::path_weight_func(living_kind);
+            if weight_func(world, block_pos, cache) < 0.0 {
+                return false;
+            }
+        }
+
+        // Any hard entity colliding prevent spawning.
+        if world.has_entity_colliding(base.bb, true) {
+            return false;
+        }
+
+        if category != EntityCategory::WaterAnimal {
+
+            // Any block colliding prevent spawning.
+            if world.iter_blocks_boxes_colliding(base.bb, cache).next().is_some() {
+                return false;
+            }
+
+            // Any colliding fluid block prevent spawning.
+            if world.iter_blocks_in_box(base.bb, cache).any(|(_pos, block, _)| block::material::is_fluid(block)) {
+                return false;
+            }
+
+        }
+
+        true
+
+    }
 
     /// Initialize this entity for natural spawn, for example this randomize the slime
     /// size or sheep color or make a spider with jokey.
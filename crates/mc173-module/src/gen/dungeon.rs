@@ -2,9 +2,8 @@
 
 use glam::IVec3;
 
-// use crate::block_entity::spawner::SpawnerBlockEntity;
-use crate::block_entity::chest::ChestBlockEntity;
-// use crate::block_entity::BlockEntity;
+use crate::block_entity::spawner::StdbSpawnerBlockEntity;
+use crate::block_entity::chest::StdbChestBlockEntity;
 use crate::item::{ItemStack, self};
 use crate::entity::EntityKind;
 use crate::rand::JavaRandom;
@@ -148,7 +147,7 @@ impl FeatureGenerator for DungeonGenerator {
                         continue 'chest_try;
                     }
 
-                    let mut chest = ChestBlockEntity::default();
+                    let mut chest = StdbChestBlockEntity::new(chest_pos);
 
                     // Pick 8 random items.
                     for _ in 0..8 {
@@ -160,8 +159,8 @@ impl FeatureGenerator for DungeonGenerator {
 
                     }
 
-                    // world.set_block(chest_pos, block::CHEST, 0);
-                    // world.set_block_entity(chest_pos, BlockEntity::Chest(chest));
+                    world.set_block(chest_pos, block::CHEST, 0, cache);
+                    chest.set();
                     break;
 
                 }
@@ -170,10 +169,10 @@ impl FeatureGenerator for DungeonGenerator {
 
         }
 
-        // let mut spawner = SpawnerBlockEntity::default();
-        // spawner.entity_kind = self.gen_spawner_entity(rand);
-        // world.set_block(pos, block::SPAWNER, 0);
-        // world.set_block_entity(pos, BlockEntity::Spawner(spawner));
+        let mut spawner = StdbSpawnerBlockEntity::new(world.dimension_id, pos);
+        spawner.entity_kind = self.gen_spawner_entity(rand);
+        world.set_block(pos, block::SPAWNER, 0, cache);
+        spawner.set();
 
         true
 
@@ -192,6 +192,8 @@ impl FeatureGenerator for LiquidGenerator {
 
         if stone_count == 3 && air_count == 1 {
             world.set_block(pos, self.fluid_id, 0, cache);
+            // The spring starts flowing on the next world tick.
+            world.schedule_block_tick(pos, self.fluid_id, 0);
         }
 
         true
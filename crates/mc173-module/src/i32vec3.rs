@@ -2,7 +2,7 @@ use std::ops::Add;
 use glam::IVec3;
 use spacetimedb::SpacetimeType;
 
-#[derive(SpacetimeType, Clone, Copy, Debug)]
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
 pub struct StdbI32Vec3 {
     pub x: i32,
     pub y: i32,
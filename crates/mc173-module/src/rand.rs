@@ -43,7 +43,7 @@ fn gen_seed(nano_time: u128) -> i64 {
 
 /// A pseudo-random number generator ported from the Java standard *RNG* with additional
 /// utility methods better suited for rust.
-#[derive(Debug, Clone, SpacetimeType)]
+#[derive(Debug, Clone, PartialEq, SpacetimeType)]
 pub struct JavaRandom {
     seed: i64,
     next_gaussian: Option<f64>,
@@ -44,10 +44,12 @@ pub struct StdbBlockSetUpdate {
 }
 
 #[spacetimedb(table(public))]
+#[spacetimedb(index(btree, dimension_id))]
 pub struct StdbChunkView {
     #[primarykey]
     #[autoinc]
     pub view_id: u32,
+    pub dimension_id: i32,
     pub chunk_id: u32,
     pub observer_id: u32,
 }
@@ -60,6 +62,11 @@ impl StdbChunk {
         ((x as u32) << 16) | (z as u32 & 0xFFFF)
     }
 
+    /// Inverse of [`Self::xz_to_chunk_id`], get the chunk coordinates from its id.
+    pub fn chunk_id_to_xz(chunk_id: u32) -> (i32, i32) {
+        ((chunk_id >> 16) as u16 as i16 as i32, chunk_id as u16 as i16 as i32)
+    }
+
     pub fn id_to_x_y(id: u32) -> (i32, i32) {
         let x = (id >> 16) as i32;
         let z = (id & 0xFFFF) as i32;
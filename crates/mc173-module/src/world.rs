@@ -17,7 +17,7 @@ use spacetimedb::{spacetimedb, SpacetimeType};
 
 use tracing::trace;
 
-use crate::entity::{Entity, EntityCategory, EntityKind};
+use crate::entity::{self, Entity, EntityCategory, EntityKind};
 // use crate::block_entity::BlockEntity;
 use crate::biome::Biome;
 use crate::chunk::{Chunk,
@@ -28,9 +28,19 @@ use crate::geom::{BoundingBox, Face};
 use crate::rand::JavaRandom;
 use crate::item::ItemStack;
 use crate::block;
+use crate::block_entity::calc_block_entity_id;
+use crate::block_entity::chest::StdbChestBlockEntity;
+use crate::block_entity::dispenser::StdbDispenserBlockEntity;
+use crate::block_entity::furnace::StdbFurnaceBlockEntity;
+use crate::block_entity::sign::StdbSignBlockEntity;
+use crate::block_entity::note_block::StdbNoteBlockBlockEntity;
+use crate::block_entity::spawner::StdbSpawnerBlockEntity;
+use crate::block_entity::piston::StdbPistonBlockEntity;
+use crate::entity::component::{self, StdbEntity};
 use crate::chunk_cache::ChunkCache;
 use crate::i32vec3::StdbI32Vec3;
-use crate::stdb::chunk::{ChunkUpdateType, StdbBlockSetUpdate, StdbChunk, StdbChunkUpdate};
+use crate::dvec3::StdbDVec3;
+use crate::stdb::chunk::{ChunkUpdateType, StdbBlockSetUpdate, StdbChunk, StdbChunkUpdate, StdbChunkView};
 use crate::stdb::weather::StdbWeather;
 
 
@@ -45,9 +55,14 @@ pub mod r#break;
 pub mod r#use;
 pub mod tick;
 pub mod notify;
+pub mod piston;
 pub mod explode;
+pub mod sleep;
 
 
+/// Maximum number of scheduled block ticks processed in a single world tick.
+const MAX_BLOCK_TICKS: usize = 1000;
+
 // Various thread local vectors that are used to avoid frequent reallocation of 
 // temporary vector used in the logic code.
 thread_local! {
@@ -156,16 +171,12 @@ pub struct StdbWorld {
     // block_entities: TickVec<BlockEntityComponent>,
     //// Mapping of block entities to they block position.
     // block_entities_pos_map: HashMap<IVec3, usize>,
-    /// Total scheduled ticks count since the world is running.
-    // block_ticks_count: u64,
-    /// Mapping of scheduled ticks in the future.
-    // block_ticks: BTreeSet<BlockTick>,
-    /// A set of all scheduled tick states, used to avoid ticking twice the same position
-    /// and block id. 
-    // block_ticks_states: HashSet<BlockTickState>,
-    /// Queue of pending light updates to be processed.
-    // light_updates: VecDeque<LightUpdate>,
-    pub light_updates: Vec<LightUpdate>,
+    /// Scheduled block ticks are stored in the StdbBlockTick table, this is the time of
+    /// the next scheduled ticks to process, all ticks scheduled before this time have
+    /// already been processed.
+    pub block_ticks_time: u64,
+    // NOTE: Pending light updates are stored in the StdbLightUpdate table, and queued
+    //  in the StdbLightQueue table.
     /// This is the wrapping seed used by random ticks to compute random block positions.
     pub random_ticks_seed: i32,
     /// The current weather in that world, note that the Notchian server do not work like
@@ -202,16 +213,12 @@ impl StdbWorld {
             rand: JavaRandom::new_seeded(nano_time),
             // chunks: HashMap::new(),
             entities_count: 0,
+            block_ticks_time: 0,
             // entities: TickVec::new(),
             // entities_id_map: HashMap::new(),
             // entities_player_map: IndexMap::new(),
             // block_entities: TickVec::new(),
             // block_entities_pos_map: HashMap::new(),
-            // block_ticks_count: 0,
-            // block_ticks: BTreeSet::new(),
-            // block_ticks_states: HashSet::new(),
-            // light_updates: VecDeque::new(),
-            light_updates: Vec::new(),
             random_ticks_seed: JavaRandom::new_seeded(nano_time).next_int(),
             // weather: Weather::Clear,
             // weather_next_time: 0,
@@ -219,27 +226,6 @@ impl StdbWorld {
         }
     }
 
-    /// Pops a light update and updates the StdbWorld row with the new value.
-    pub fn pop_light_update() -> Option<LightUpdate> {
-        let mut world = StdbWorld::filter_by_dimension_id(&DIMENSION_OVERWORLD).unwrap();
-        let result = world.pop_light_update_inner();
-        StdbWorld::update_by_dimension_id(&DIMENSION_OVERWORLD, world);
-        result
-    }
-
-    /// Pops a light update from a world without updating the StdbWorld row
-    pub fn pop_light_update_inner(&mut self) -> Option<LightUpdate> {
-        if self.light_updates.len() == 0 {
-            None
-        } else {
-            Some(self.light_updates.remove(0))
-        }
-    }
-
-    pub fn push_light_update_inner(&mut self, update: LightUpdate) {
-        self.light_updates.push(update);
-    }
-
     //// This function can be used to swap in a new events queue and return the previous
     //// one if relevant. Giving *None* events queue disable events registration using
     //// the [`push_event`] method. Swapping out the events is the only way of reading
@@ -550,7 +536,7 @@ impl StdbWorld {
     /// [`set_block`]: Self::set_block
     pub fn set_block_self_notify(&mut self, pos: IVec3, id: u8, metadata: u8, cache: &mut ChunkCache) -> Option<(u8, u8)> {
         let (prev_id, prev_metadata) = self.set_block(pos, id, metadata, cache)?;
-        // self.notify_change_unchecked(pos, prev_id, prev_metadata, id, metadata);
+        self.notify_change_unchecked(pos, prev_id, prev_metadata, id, metadata, cache);
         Some((prev_id, prev_metadata))
     }
 
@@ -615,17 +601,48 @@ impl StdbWorld {
     ///  
     /// See [`tick_light`](Self::tick_light).
     pub fn schedule_light_update(&mut self, pos: IVec3, kind: LightKind) {
-        self.light_updates.push(LightUpdate {
+        self.push_light_update(pos, kind, 15);
+    }
+
+    /// Push a light update with the given credit. If an update of the same kind is 
+    /// already pending at this position, it's reused and given the highest credit of
+    /// both, instead of processing the same position twice.
+    fn push_light_update(&mut self, pos: IVec3, kind: LightKind, credit: u8) {
+
+        let pos_id = calc_block_entity_id(pos);
+        if let Some(mut update) = StdbLightUpdate::filter_by_pos_id(&pos_id)
+            .find(|update| update.dimension_id == self.dimension_id && update.kind == kind) {
+            if update.credit < credit {
+                update.credit = credit;
+                StdbLightUpdate::update_by_uid(&update.uid.clone(), update);
+            }
+            return;
+        }
+
+        let mut queue = StdbLightQueue::filter_by_dimension_id(&self.dimension_id)
+            .unwrap_or(StdbLightQueue { dimension_id: self.dimension_id, head: 0, tail: 0 });
+
+        StdbLightUpdate::insert(StdbLightUpdate {
+            uid: 0,
+            dimension_id: self.dimension_id,
+            seq: queue.tail,
             kind,
+            pos_id,
             pos: pos.into(),
-            credit: 15,
-        });
+            credit,
+        }).unwrap();
+
+        queue.tail += 1;
+        queue.set();
+
     }
 
     /// Get the number of light updates remaining to process.
     #[inline]
     pub fn get_light_update_count(&self) -> usize {
-        self.light_updates.len()
+        StdbLightQueue::filter_by_dimension_id(&self.dimension_id)
+            .map(|queue| (queue.tail - queue.head) as usize)
+            .unwrap_or(0)
     }
 
     // =================== //
@@ -643,180 +660,94 @@ impl StdbWorld {
     //       ENTITIES      //
     // =================== //
 
-    //// Internal function to ensure monomorphization and reduce bloat of the
-    //// generic [`spawn_entity`].
-    // #[inline(never)]
-    // fn spawn_entity_inner(&mut self, entity: Box<Entity>) -> u32 {
-    //
-    //     // Get the next unique entity id.
-    //     let id = self.entities_count;
-    //     self.entities_count = self.entities_count.checked_add(1)
-    //         .expect("entity count overflow");
-    //
-    //     let kind = entity.kind();
-    //     trace!("spawn entity #{id} ({:?})", kind);
-    //
-    //     let (cx, cz) = calc_entity_chunk_pos(entity.0.pos);
-    //     let chunk = StdbChunk::filter_by_chunk_id(StdbChunk::chunk_id(cx, cz)).unwrap();
-    //     let entity_index = self.entities.push(EntityComponent {
-    //         inner: Some(entity),
-    //         id,
-    //         cx,
-    //         cz,
-    //         // loaded: chunk_comp.data.is_some(),
-    //         kind,
-    //     });
-    //
-    //     // TODO(jdetter): We're getting rid of the concept of all of these lookup maps
-    //     // chunk.chunk.entities.insert(id, entity_index);
-    //     // self.entities_id_map.insert(id, entity_index);
-    //
-    //     // This makes no sense in SpacetimeDB, we'll have to remember to do this in the client
-    //     // self.push_event(Event::Entity { id, inner: EntityEvent::Spawn });
-    //     // self.push_event(Event::Chunk { cx, cz, inner: ChunkEvent::Dirty });
-    //
-    //     id
-    //
-    // }
+    /// Allocate a new unique entity id in this world, ids start at 1. The world row
+    /// must be updated afterward for the counter to be saved.
+    pub fn alloc_entity_id(&mut self) -> u32 {
+        self.entities_count = self.entities_count.checked_add(1)
+            .expect("entity count overflow");
+        self.entities_count
+    }
 
-    //// Spawn an entity in this world, this function gives it a unique id and ensure
-    //// coherency with chunks cache.
-    ////
-    //// **This function is legal to call from ticking entities, but such entities will be
-    //// ticked once in the same cycle as the currently ticking entity.**
-    // #[inline(always)]
-    // pub fn spawn_entity(&mut self, entity: impl Into<Box<Entity>>) -> u32 {
-    //     // NOTE: This method is just a wrapper to erase generics.
-    //     self.spawn_entity_inner(entity.into())
-    // }
+    /// Spawn an entity in this world, this function gives it a unique id and stores it
+    /// in the entity component tables. The world row must be updated afterward for the
+    /// entity counter to be saved.
+    ///
+    /// **This function is legal to call from ticking entities, but such entities will
+    /// only be ticked on the next world tick.**
+    pub fn spawn_entity(&mut self, entity: impl Into<Box<Entity>>) -> u32 {
+        let entity = entity.into();
+        let id = self.alloc_entity_id();
+        trace!("spawn entity #{id} ({:?})", entity.kind());
+        component::store_entity(id, self.dimension_id, &entity, None);
+        id
+    }
 
-    // /// Return true if an entity is present from its id.
-    // pub fn contains_entity(&self, id: u32) -> bool {
-    //     self.entities_id_map.contains_key(&id)
-    // }
+    /// Return true if an entity is present from its id.
+    pub fn contains_entity(&self, id: u32) -> bool {
+        component::contains_entity(id)
+    }
 
-    // /// Return the number of entities in the world, loaded or not.
-    // #[inline]
-    // pub fn get_entity_count(&self) -> usize {
-    //     self.entities.len()
-    // }
+    /// Return the number of entities in the world.
+    pub fn get_entity_count(&self) -> usize {
+        StdbEntity::filter_by_dimension_id(&self.dimension_id).count()
+    }
 
-    // /// Get a generic entity from its unique id. This generic entity can later be checked
-    // /// for being of a particular type. None can be returned if no entity is existing for
-    // /// this id or if the entity is the current entity being updated.
-    // pub fn get_entity(&self, id: u32) -> Option<&Entity> {
-    //     let index = *self.entities_id_map.get(&id)?;
-    //     self.entities.get(index).unwrap().inner.as_deref()
-    // }
+    /// Get a generic entity from its unique id. This generic entity can later be checked
+    /// for being of a particular type. The entity is loaded from its component tables,
+    /// so it needs to be set back with [`set_entity`](Self::set_entity) to save any
+    /// modification. **The entity currently being ticked is returned as it was before
+    /// its tick.**
+    pub fn get_entity(&self, id: u32) -> Option<Entity> {
+        component::load_entity(id)
+    }
 
-    // /// Get a generic entity from its unique id. This generic entity can later be checked
-    // /// for being of a particular type. None can be returned if no entity is existing for
-    // /// this id or if the entity is the current entity being updated.
-    // pub fn get_entity_mut(&mut self, id: u32) -> Option<&mut Entity> {
-    //     let index = *self.entities_id_map.get(&id)?;
-    //     self.entities.get_mut(index).unwrap().inner.as_deref_mut()
-    // }
+    /// Save the given entity to its component tables, the entity must already exist
+    /// in the world and its kind must not have changed. This returns false if the entity
+    /// doesn't exist.
+    pub fn set_entity(&mut self, id: u32, entity: &Entity) -> bool {
+        if !component::contains_entity(id) {
+            return false;
+        }
+        component::store_entity(id, self.dimension_id, entity, None);
+        true
+    }
 
-    // /// Remove an entity with given id, returning some boxed entity is successful. This
-    // /// returns true if the entity has been successfully removed removal, the entity's
-    // /// storage is guaranteed to be freed after return, but the entity footprint in the
-    // /// world will be cleaned only after ticking.
-    // pub fn remove_entity(&mut self, id: u32, reason: &str) -> bool {
-    //     self.remove_entity_inner(id, true, reason).is_some()
-    // }
+    /// Same as [`set_entity`](Self::set_entity), but only the component rows that have
+    /// changed since the given previous state of the entity are updated.
+    pub fn set_entity_changes(&mut self, id: u32, prev_entity: &Entity, entity: &Entity) -> bool {
+        if !component::contains_entity(id) {
+            return false;
+        }
+        component::store_entity(id, self.dimension_id, entity, Some(prev_entity));
+        true
+    }
 
-    // /// Internal version of [`remove_entity`] that returns the removed component.
-    // ///
-    // /// The caller can specify if the entity is known to be in an existing chunk
-    // /// component, if the caller know that the chunk component is no longer existing,
-    // /// it avoids panicking.
-    // ///
-    // /// The given reason is only used for log tracing.
-    // fn remove_entity_inner(&mut self, id: u32, has_chunk: bool, reason: &str) -> Option<EntityComponent> {
-    //
-    //     let index = self.entities_id_map.remove(&id)?;
-    //
-    //     // Also remove the entity from the player map, if it was.
-    //     self.entities_player_map.remove(&id);
-    //
-    //     let comp = self.entities.remove(index);
-    //     let swapped_index = self.entities.len();
-    //     debug_assert_eq!(comp.id, id, "entity incoherent id");
-    //
-    //     trace!("remove entity #{id} ({:?}): {reason}", comp.kind);
-    //
-    //     // Directly remove the entity from its chunk if needed.
-    //     let (cx, cz) = (comp.cx, comp.cz);
-    //     if has_chunk {
-    //         let removed_index = self.chunks.get_mut(&(cx, cz))
-    //             .expect("entity chunk is missing")
-    //             .entities
-    //             .remove(&id);
-    //         debug_assert_eq!(removed_index, Some(index), "entity is incoherent in its chunk");
-    //     }
-    //
-    //     // The entity that has been swapped has a new index, so we need to update its
-    //     // index into the chunk cache...
-    //     if let Some(swapped_comp) = self.entities.get(index) {
-    //
-    //         let prev_index = self.entities_id_map.insert(swapped_comp.id, index);
-    //         debug_assert_eq!(prev_index, Some(swapped_index), "swapped entity is incoherent");
-    //
-    //         // Update the index of the entity within the player map, if it is a player.
-    //         self.entities_player_map.entry(swapped_comp.id).and_modify(|i| *i = index);
-    //
-    //         let (swapped_cx, swapped_cz) = (swapped_comp.cx, swapped_comp.cz);
-    //         if has_chunk || (swapped_cx, swapped_cz) != (cx, cz) {
-    //
-    //             let removed_index = self.chunks.get_mut(&(swapped_cx, swapped_cz))
-    //                 .expect("swapped entity chunk is missing")
-    //                 .entities
-    //                 .insert(swapped_comp.id, index);
-    //             debug_assert_eq!(removed_index, Some(swapped_index), "swapped entity is incoherent in its chunk");
-    //
-    //         }
-    //
-    //     }
-    //
-    //     self.push_event(Event::Entity { id, inner: EntityEvent::Remove });
-    //     if has_chunk {
-    //         self.push_event(Event::Chunk { cx, cz, inner: ChunkEvent::Dirty });
-    //     }
-    //
-    //     Some(comp)
-    //
-    // }
+    /// Remove an entity with given id, returning true if the entity has been removed,
+    /// in this case all of its component rows have been deleted.
+    ///
+    /// The given reason is only used for log tracing.
+    pub fn remove_entity(&mut self, id: u32, reason: &str) -> bool {
+        let removed = component::delete_entity(id);
+        if removed {
+            trace!("remove entity #{id}: {reason}");
+        }
+        removed
+    }
     
     // =================== //
     //   PLAYER ENTITIES   //
     // =================== //
 
-    // /// Set an entity that is already existing to be a player entity. Player entities are
-    // /// used as dynamic anchors in the world that are used for things like natural entity
-    // /// despawning when players are too far away, or for looking at players.
-    // ///
-    // /// This methods returns true if the property has been successfully set.
-    // pub fn set_entity_player(&mut self, id: u32, player: bool) -> bool {
-    //     let Some(&index) = self.entities_id_map.get(&id) else { return false };
-    //     if player {
-    //         self.entities_player_map.insert(id, index);
-    //     } else {
-    //         self.entities_player_map.remove(&id);
-    //     }
-    //     true
-    // }
+    // Player entities are the human entities of connected players, they are used as
+    // dynamic anchors in the world for things like natural entity despawning, or for
+    // looking at players.
 
-    // /// Returns true if the given entity by its id is a player entity. This also returns
-    // /// false if the entity isn't existing.
-    // pub fn is_entity_player(&mut self, id: u32) -> bool {
-    //     self.entities_player_map.contains_key(&id)
-    // }
-
-    // /// Returns the number of player entities in the world, loaded or not.
-    // #[inline]
-    // pub fn get_entity_player_count(&self) -> usize {
-    //     self.entities_player_map.len()
-    // }
+    /// Returns the number of player entities in the world.
+    pub fn get_entity_player_count(&self) -> usize {
+        StdbEntity::filter_by_dimension_id(&self.dimension_id)
+            .filter(|row| row.kind == EntityKind::Human)
+            .count()
+    }
 
     // =================== //
     //   BLOCK ENTITIES    //
@@ -893,12 +824,18 @@ impl StdbWorld {
     //     self.block_entities.get_mut(index).unwrap().inner.as_deref_mut()
     // }
 
-    // /// Remove a block entity from a position. Returning true if successful, in this case
-    // /// the block entity storage is guaranteed to be freed, but the block entity footprint
-    // /// in this world will be definitely cleaned after ticking.
-    // pub fn remove_block_entity(&mut self, pos: IVec3) -> bool {
-    //     self.remove_block_entity_inner(pos, true).is_some()
-    // }
+    /// Remove a block entity from a position. Returning true if successful, in this case
+    /// the block entity row has been deleted from its table.
+    pub fn remove_block_entity(&mut self, pos: IVec3) -> bool {
+        let id = calc_block_entity_id(pos);
+        StdbChestBlockEntity::delete_by_block_entity_id(&id)
+            || StdbFurnaceBlockEntity::delete_by_block_entity_id(&id)
+            || StdbDispenserBlockEntity::delete_by_block_entity_id(&id)
+            || StdbSignBlockEntity::delete_by_block_entity_id(&id)
+            || StdbNoteBlockBlockEntity::delete_by_block_entity_id(&id)
+            || StdbSpawnerBlockEntity::delete_by_block_entity_id(&id)
+            || StdbPistonBlockEntity::delete_by_block_entity_id(&id)
+    }
 
     // /// Internal version of `remove_block_entity` that returns the removed component.
     // ///
@@ -957,27 +894,99 @@ impl StdbWorld {
     //   SCHEDULED TICKS   //
     // =================== //
 
-    // /// Schedule a tick update to happen at the given position, for the given block id
-    // /// and with a given delay in ticks. The block tick is not scheduled if a tick was
-    // /// already scheduled for that exact block id and position.
-    // pub fn schedule_block_tick(&mut self, pos: IVec3, id: u8, delay: u64) {
-    //
-    //     let uid = self.block_ticks_count;
-    //     self.block_ticks_count = self.block_ticks_count.checked_add(1)
-    //         .expect("scheduled ticks count overflow");
-    //
-    //     let state = BlockTickState { pos, id };
-    //     if self.block_ticks_states.insert(state) {
-    //         self.block_ticks.insert(BlockTick { time: self.time + delay, state, uid });
-    //     }
-    //
-    // }
+    /// Schedule a tick update to happen at the given position, for the given block id
+    /// and with a given delay in ticks. The block tick is not scheduled if a tick was
+    /// already scheduled for that exact block id and position.
+    pub fn schedule_block_tick(&mut self, pos: IVec3, id: u8, delay: u64) {
 
-    // /// Return the current number of scheduled block ticks waiting.
-    // #[inline]
-    // pub fn get_block_tick_count(&self) -> usize {
-    //     self.block_ticks.len()
-    // }
+        let pos_id = calc_block_entity_id(pos);
+        if StdbBlockTick::filter_by_pos_id(&pos_id).any(|tick| tick.dimension_id == self.dimension_id && tick.id == id) {
+            return;
+        }
+
+        StdbBlockTick::insert(StdbBlockTick {
+            uid: 0,
+            dimension_id: self.dimension_id,
+            time: self.time + delay,
+            pos_id,
+            pos: pos.into(),
+            id,
+        }).unwrap();
+
+    }
+
+    /// Push a block action to clients observing the block's chunk.
+    pub fn push_block_action(&mut self, pos: IVec3, data0: i8, data1: i8) {
+        let (cx, cz) = calc_chunk_pos_unchecked(pos);
+        StdbBlockActionEvent::insert(StdbBlockActionEvent {
+            event_id: 0,
+            dimension_id: self.dimension_id,
+            pos: pos.into(),
+            chunk_id: StdbChunk::xz_to_chunk_id(cx, cz),
+            data0,
+            data1,
+        }).unwrap();
+    }
+
+    /// Push a sound or particle effect to clients observing the block's chunk, the
+    /// meaning of the effect data depends on the effect id.
+    pub fn push_effect(&mut self, pos: IVec3, effect_id: u32, effect_data: u32) {
+        let (cx, cz) = calc_chunk_pos_unchecked(pos);
+        StdbEffectEvent::insert(StdbEffectEvent {
+            event_id: 0,
+            dimension_id: self.dimension_id,
+            pos: pos.into(),
+            chunk_id: StdbChunk::xz_to_chunk_id(cx, cz),
+            effect_id,
+            effect_data,
+        }).unwrap();
+    }
+
+    /// Push the pickup of an entity by another one, to be applied and played by clients
+    /// observing the picked up entity's chunk.
+    pub fn push_entity_pickup(&mut self, entity_id: u32, target_id: u32) {
+        let Some(target) = StdbEntity::filter_by_entity_id(&target_id) else { return };
+        StdbEntityPickupEvent::insert(StdbEntityPickupEvent {
+            event_id: 0,
+            dimension_id: self.dimension_id,
+            entity_id,
+            target_id,
+            chunk_id: target.chunk_id,
+        }).unwrap();
+    }
+
+    /// Push a status of an entity, like its hurt or death animation, to be played by
+    /// clients observing the entity's chunk.
+    pub fn push_entity_status(&mut self, entity_id: u32, status: EntityStatus) {
+        let Some(entity) = StdbEntity::filter_by_entity_id(&entity_id) else { return };
+        StdbEntityStatusEvent::insert(StdbEntityStatusEvent {
+            event_id: 0,
+            dimension_id: self.dimension_id,
+            entity_id,
+            chunk_id: entity.chunk_id,
+            status,
+        }).unwrap();
+    }
+
+    /// Push a sudden velocity change of an entity, like a knock back, this is required
+    /// for clients to update the velocity of entities they are simulating, including
+    /// their own player.
+    pub fn push_entity_velocity(&mut self, entity_id: u32, vel: DVec3) {
+        let Some(entity) = StdbEntity::filter_by_entity_id(&entity_id) else { return };
+        StdbEntityVelocityEvent::insert(StdbEntityVelocityEvent {
+            event_id: 0,
+            dimension_id: self.dimension_id,
+            entity_id,
+            chunk_id: entity.chunk_id,
+            vel: vel.into(),
+        }).unwrap();
+    }
+
+    /// Return the current number of scheduled block ticks waiting.
+    #[inline]
+    pub fn get_block_tick_count(&self) -> usize {
+        StdbBlockTick::filter_by_dimension_id(&self.dimension_id).count()
+    }
 
     // =================== //
     //      ITERATORS      //
@@ -1009,15 +1018,14 @@ impl StdbWorld {
     //     EntitiesIterMut(self.entities.iter_mut())
     // }
 
-    // /// Iterate over all player entities in the world.
-    // /// *This function can't return the current updated entity.*
-    // #[inline]
-    // pub fn iter_player_entities(&self) -> PlayerEntitiesIter<'_> {
-    //     PlayerEntitiesIter {
-    //         indices: Some(self.entities_player_map.values()),
-    //         entities: &self.entities,
-    //     }
-    // }
+    /// Iterate over all player entities in the world, entities are loaded from their
+    /// component tables like with [`get_entity`](Self::get_entity).
+    /// *The current updated entity is returned as it was before its tick.*
+    pub fn iter_player_entities(&self) -> impl Iterator<Item = (u32, Entity)> {
+        StdbEntity::filter_by_dimension_id(&self.dimension_id)
+            .filter(|row| row.kind == EntityKind::Human)
+            .filter_map(|row| Some((row.entity_id, component::load_entity(row.entity_id)?)))
+    }
 
     // /// Iterate over all player entities in the world through mutable references.
     // /// *This function can't return the current updated entity.*
@@ -1053,53 +1061,31 @@ impl StdbWorld {
     //     }
     // }
 
-    // /// Iterate over all entities colliding with the given bounding box.
-    // /// *This function can't return the current updated entity.*
-    // #[inline]
-    // pub fn iter_entities_colliding(&self, bb: BoundingBox) -> EntitiesCollidingIter<'_> {
-    //
-    //     let (start_cx, start_cz) = calc_entity_chunk_pos(bb.min - 2.0);
-    //     let (end_cx, end_cz) = calc_entity_chunk_pos(bb.max + 2.0);
-    //
-    //     EntitiesCollidingIter {
-    //         chunks: ChunkComponentsIter {
-    //             chunks: &self.chunks,
-    //             range: ChunkRange::new(start_cx, start_cz, end_cx, end_cz) },
-    //         indices: None,
-    //         entities: &self.entities,
-    //         bb,
-    //     }
-    //
-    // }
+    /// Iterate over all entities colliding with the given bounding box, entities are
+    /// loaded from their component tables like with [`get_entity`](Self::get_entity).
+    /// *The current updated entity is returned as it was before its tick, so it should
+    /// be filtered out by its id.*
+    pub fn iter_entities_colliding(&self, bb: BoundingBox) -> impl Iterator<Item = (u32, Entity)> {
 
-    // /// Iterate over all entities colliding with the given bounding box through mut ref.
-    // /// *This function can't return the current updated entity.*
-    // #[inline]
-    // pub fn iter_entities_colliding_mut(&mut self, bb: BoundingBox) -> EntitiesCollidingIterMut<'_> {
-    //
-    //     let (start_cx, start_cz) = calc_entity_chunk_pos(bb.min - 2.0);
-    //     let (end_cx, end_cz) = calc_entity_chunk_pos(bb.max + 2.0);
-    //
-    //     EntitiesCollidingIterMut {
-    //         chunks: ChunkComponentsIter {
-    //             chunks: &self.chunks,
-    //             range: ChunkRange::new(start_cx, start_cz, end_cx, end_cz) },
-    //         indices: None,
-    //         entities: &mut self.entities,
-    //         bb,
-    //         #[cfg(debug_assertions)]
-    //         returned_pointers: HashSet::new(),
-    //     }
-    //
-    // }
+        let (start_cx, start_cz) = calc_entity_chunk_pos(bb.min - 2.0);
+        let (end_cx, end_cz) = calc_entity_chunk_pos(bb.max + 2.0);
+        let dimension_id = self.dimension_id;
 
-    // /// Return true if any entity is colliding the given bounding box. The hard argument
-    // /// can be set to true in order to only check for "hard" entities, hard entities can
-    // /// prevent block placements and entity spawning.
-    // pub fn has_entity_colliding(&self, bb: BoundingBox, hard: bool) -> bool {
-    //     self.iter_entities_colliding(bb)
-    //         .any(|(_, entity)| !hard || entity.kind().is_hard())
-    // }
+        ChunkRange::new(start_cx, start_cz, end_cx, end_cz)
+            .flat_map(|(cx, cz)| StdbEntity::filter_by_chunk_id(&StdbChunk::xz_to_chunk_id(cx, cz)))
+            .filter(move |row| row.dimension_id == dimension_id)
+            .filter_map(|row| Some((row.entity_id, component::load_entity(row.entity_id)?)))
+            .filter(move |(_, entity)| entity.0.bb.intersects(bb))
+
+    }
+
+    /// Return true if any entity is colliding the given bounding box. The hard argument
+    /// can be set to true in order to only check for "hard" entities, hard entities can
+    /// prevent block placements and entity spawning.
+    pub fn has_entity_colliding(&self, bb: BoundingBox, hard: bool) -> bool {
+        self.iter_entities_colliding(bb)
+            .any(|(_, entity)| !hard || entity.kind().is_hard())
+    }
 
     // =================== //
     //       TICKING       //
@@ -1116,21 +1102,40 @@ impl StdbWorld {
             // println!("sky_light_subtracted: {}", self.sky_light_subtracted);
         }
 
+        // Block actions of the previous tick have already been sent by the proxy.
+        for event in StdbBlockActionEvent::filter_by_dimension_id(&self.dimension_id).collect::<Vec<_>>() {
+            StdbBlockActionEvent::delete_by_event_id(&event.event_id);
+        }
+        for event in StdbEffectEvent::filter_by_dimension_id(&self.dimension_id).collect::<Vec<_>>() {
+            StdbEffectEvent::delete_by_event_id(&event.event_id);
+        }
+        for event in StdbEntityPickupEvent::filter_by_dimension_id(&self.dimension_id).collect::<Vec<_>>() {
+            StdbEntityPickupEvent::delete_by_event_id(&event.event_id);
+        }
+        for event in StdbEntityStatusEvent::filter_by_dimension_id(&self.dimension_id).collect::<Vec<_>>() {
+            StdbEntityStatusEvent::delete_by_event_id(&event.event_id);
+        }
+        for event in StdbEntityVelocityEvent::filter_by_dimension_id(&self.dimension_id).collect::<Vec<_>>() {
+            StdbEntityVelocityEvent::delete_by_event_id(&event.event_id);
+        }
+        for event in StdbExplodeEvent::filter_by_dimension_id(&self.dimension_id).collect::<Vec<_>>() {
+            StdbExplodeEvent::delete_by_event_id(&event.event_id);
+        }
+
+        self.tick_sleep(cache);
         self.tick_weather();
-        // TODO: Wake up all sleeping player if day time.
         
-        // self.tick_natural_spawn();
+        self.tick_natural_spawn(cache);
 
         self.tick_sky_light();
 
         self.time += 1;
 
-        // self.tick_blocks();
-        // self.tick_entities(nano_time);
-        // self.tick_block_entities();
+        self.tick_blocks(cache);
+        self.tick_entities(cache);
+        self.tick_block_entities(cache);
 
-        // TODO(jdetter): Re-enable this
-        // self.tick_light(1000, cache);
+        self.tick_light(1000, cache);
         
     }
 
@@ -1164,197 +1169,195 @@ impl StdbWorld {
 
     }
 
-    // /// Do natural animal and mob spawning in the world.
-    // fn tick_natural_spawn(&mut self) {
-    //
-    //     /// The maximum manhattan distance a chunk can be loaded.
-    //     const CHUNK_MAX_DIST: u32 = 8;
-    //     /// The minimum distance required from any player entity to spawn.
-    //     const SPAWN_MIN_DIST_SQUARED: f64 = 24.0 * 24.0;
-    //
-    //     // Categories of entities to spawn, also used to count how many are currently
-    //     // loaded in the world. We have 4 slots in this array because there are 4
-    //     // entity categories.
-    //     let mut categories_count = [0; EntityCategory::ALL.len()];
-    //
-    //     // Count every entity category.
-    //     for comp in self.entities.iter() {
-    //         if comp.loaded {
-    //             if let Some(entity) = comp.inner.as_deref() {
-    //                 categories_count[entity.category() as usize] += 1;
-    //             }
-    //         }
-    //     }
-    //
-    //     // Temporary list of chunks loaded by data and players in range.
-    //     let mut loaded_chunks = LOADED_CHUNKS.take();
-    //     loaded_chunks.clear();
-    //     loaded_chunks.extend(self.chunks.iter()
-    //         .filter_map(|(&pos, comp)| comp.data.is_some().then_some(pos)));
-    //     loaded_chunks.retain(|&(cx, cz)| {
-    //         self.entities_player_map.values()
-    //             .map(|&index| self.entities.get(index).unwrap())
-    //             .any(|comp| comp.cx.abs_diff(cx) <= CHUNK_MAX_DIST && comp.cz.abs_diff(cz) <= CHUNK_MAX_DIST)
-    //     });
-    //
-    //     for category in EntityCategory::ALL {
-    //
-    //         let max_world_count = category.natural_spawn_max_world_count();
-    //
-    //         // Skip the category if it cannot spawn.
-    //         if max_world_count == 0 {
-    //             continue;
-    //         }
-    //         // Skip the category if it already has enough loaded entities.
-    //         if categories_count[category as usize] > max_world_count * self.chunks.len() / 256 {
-    //             continue;
-    //         }
-    //
-    //         for &(cx, cz) in &loaded_chunks {
-    //
-    //             // Temporary borrowing of chunk data to query biome and block.
-    //             let chunk = self.chunks.get(&(cx, cz)).unwrap();
-    //             let chunk_data = chunk.data.as_deref().unwrap();
-    //
-    //             let biome = chunk_data.get_biome(IVec3::ZERO);
-    //             let kinds = biome.natural_entity_kinds(category);
-    //
-    //             // Ignore this chunk is its biome cannot spawn any entity.
-    //             if kinds.is_empty() {
-    //                 continue;
-    //             }
-    //
-    //             // Next we pick a random spawn position within the chunk and check it.
-    //             let center_pos = IVec3 {
-    //                 x: cx * 16 + self.rand.next_int_bounded(16),
-    //                 y: self.rand.next_int_bounded(128),
-    //                 z: cz * 16 + self.rand.next_int_bounded(16),
-    //             };
-    //
-    //             // If the block is not valid to spawn the category in, skip chunk.
-    //             let (block, _) = chunk_data.get_block(center_pos);
-    //             if block::material::get_material(block) != category.natural_spawn_material() {
-    //                 continue;
-    //             }
-    //
-    //             let chance_sum = kinds.iter().map(|kind| kind.chance).sum::<u16>();
-    //             let index = self.rand.next_int_bounded(chance_sum as i32) as u16;
-    //             let mut chance_acc = 0;
-    //             let mut kind = kinds[0].kind;
-    //
-    //             for test_kind in kinds {
-    //                 chance_acc += test_kind.chance;
-    //                 if index < chance_acc {
-    //                     kind = test_kind.kind;
-    //                     break;
-    //                 }
-    //             }
-    //
-    //             // Keep the maximum chunk count to compare with spawn count.
-    //             let max_chunk_count = kind.natural_spawn_max_chunk_count();
-    //
-    //             // Keep track of the total number of entity spawned in that chunk.
-    //             let mut spawn_count = 0usize;
-    //
-    //             'pack: for _ in 0..3 {
-    //
-    //                 let mut spawn_pos = center_pos;
-    //
-    //                 'chain: for _ in 0..4 {
-    //
-    //                     spawn_pos += IVec3 {
-    //                         x: self.rand.next_int_bounded(6) - self.rand.next_int_bounded(6),
-    //                         y: self.rand.next_int_bounded(1) - self.rand.next_int_bounded(1),
-    //                         z: self.rand.next_int_bounded(6) - self.rand.next_int_bounded(6),
-    //                     };
-    //
-    //                     // Preliminary check if the block position is valid.
-    //                     if category == EntityCategory::WaterAnimal {
-    //
-    //                         // Water animals can only spawn in liquid.
-    //                         if !self.get_block_material(spawn_pos).is_fluid() {
-    //                             continue;
-    //                         }
-    //
-    //                         // Water animals cannot spawn if above block is opaque.
-    //                         if self.is_block_opaque_cube(spawn_pos + IVec3::Y) {
-    //                             continue;
-    //                         }
-    //
-    //                     } else {
-    //
-    //                         // The 2 block column should not be opaque cube.
-    //                         if self.is_block_opaque_cube(spawn_pos) || self.is_block_opaque_cube(spawn_pos + IVec3::Y) {
-    //                             continue;
-    //                         }
-    //
-    //                         // Block below should be opaque.
-    //                         if !self.is_block_opaque_cube(spawn_pos - IVec3::Y) {
-    //                             continue;
-    //                         }
-    //
-    //                         // PARITY: We don't do the fluid block check because it would
-    //                         // be redundant with the check in 'can_natural_spawn'.
-    //
-    //                     }
-    //
-    //                     let spawn_pos = spawn_pos.as_dvec3() + DVec3::new(0.5, 0.0, 0.5);
-    //
-    //                     // PARITY: We check that this entity would be in the 128.0 block
-    //                     // no-despawn range of at least one player. This avoid entities
-    //                     // to be instantly removed after spawning.
-    //                     let mut close_player = false;
-    //                     for (_, Entity(player_base, _)) in self.iter_player_entities() {
-    //                         // If there is a player too close to that spawn point, abort.
-    //                         let player_dist_sq = player_base.pos.distance_squared(spawn_pos);
-    //                         if player_dist_sq < SPAWN_MIN_DIST_SQUARED {
-    //                             continue 'chain;
-    //                         } else if player_dist_sq <= 128.0 * 128.0 {
-    //                             close_player = true;
-    //                         }
-    //                     }
-    //
-    //                     // Skip if no player is in range to keep this natural entity.
-    //                     if !close_player {
-    //                         continue;
-    //                     }
-    //
-    //                     // TODO: Do not spawn inside spawn chunks
-    //
-    //                     let mut entity = kind.new_default(spawn_pos);
-    //                     entity.0.persistent = true;
-    //                     entity.0.look.x = self.rand.next_float() * std::f32::consts::TAU;
-    //
-    //                     // Important to init natural spawn before checking if it can spawn
-    //                     // because slime may be resized, so this can change the bb.
-    //                     entity.init_natural_spawn(self);
-    //
-    //                     // Skip if the entity cannot be spawned.
-    //                     if !entity.can_natural_spawn(self) {
-    //                         continue;
-    //                     }
-    //
-    //                     // TODO: Spawning spider has 1% chance of being spider jockey.
-    //                     // TODO: Random sheep wool color.
-    //
-    //                     self.spawn_entity(entity);
-    //                     spawn_count += 1;
-    //                     if spawn_count >= max_chunk_count {
-    //                         break 'pack;
-    //                     }
-    //
-    //                 }
-    //
-    //             }
-    //
-    //         }
-    //
-    //     }
-    //
-    //     // To avoid too short allocation...
-    //     LOADED_CHUNKS.set(loaded_chunks);
-    //
-    // }
+    /// Do natural animal and mob spawning in the world.
+    fn tick_natural_spawn(&mut self, cache: &mut ChunkCache) {
+
+        /// The maximum manhattan distance a chunk can be loaded.
+        const CHUNK_MAX_DIST: i32 = 8;
+        /// The minimum distance required from any player entity to spawn.
+        const SPAWN_MIN_DIST_SQUARED: f64 = 24.0 * 24.0;
+
+        // Categories of entities to spawn, also used to count how many are currently
+        // loaded in the world. We have 4 slots in this array because there are 4
+        // entity categories.
+        let mut categories_count = [0; EntityCategory::ALL.len()];
+
+        // Count every entity category, and keep the position of every player.
+        let mut players_pos = Vec::new();
+        for row in StdbEntity::filter_by_dimension_id(&self.dimension_id) {
+            categories_count[row.kind.category() as usize] += 1;
+            if row.kind == EntityKind::Human {
+                players_pos.push(DVec3::from(row.pos));
+            }
+        }
+
+        // Temporary list of chunks loaded by data and players in range.
+        let mut loaded_chunks = LOADED_CHUNKS.take();
+        loaded_chunks.clear();
+        for &player_pos in &players_pos {
+            let (player_cx, player_cz) = calc_entity_chunk_pos(player_pos);
+            for cx in player_cx - CHUNK_MAX_DIST..=player_cx + CHUNK_MAX_DIST {
+                for cz in player_cz - CHUNK_MAX_DIST..=player_cz + CHUNK_MAX_DIST {
+                    if !loaded_chunks.contains(&(cx, cz)) && self.contains_chunk(cx, cz, cache) {
+                        loaded_chunks.push((cx, cz));
+                    }
+                }
+            }
+        }
+
+        for category in EntityCategory::ALL {
+
+            let max_world_count = category.natural_spawn_max_world_count();
+
+            // Skip the category if it cannot spawn.
+            if max_world_count == 0 {
+                continue;
+            }
+            // Skip the category if it already has enough loaded entities.
+            if categories_count[category as usize] > max_world_count * loaded_chunks.len() / 256 {
+                continue;
+            }
+
+            for &(cx, cz) in &loaded_chunks {
+
+                let Some(biome) = self.get_biome(IVec3::new(cx * 16, 0, cz * 16), cache) else { continue };
+                let kinds = biome.natural_entity_kinds(category);
+
+                // Ignore this chunk is its biome cannot spawn any entity.
+                if kinds.is_empty() {
+                    continue;
+                }
+
+                // Next we pick a random spawn position within the chunk and check it.
+                let center_pos = IVec3 {
+                    x: cx * 16 + self.rand.next_int_bounded(16),
+                    y: self.rand.next_int_bounded(128),
+                    z: cz * 16 + self.rand.next_int_bounded(16),
+                };
+
+                // If the block is not valid to spawn the category in, skip chunk.
+                let Some((block, _)) = self.get_block(center_pos, cache) else { continue };
+                if block::material::get_material(block) != category.natural_spawn_material() {
+                    continue;
+                }
+
+                let chance_sum = kinds.iter().map(|kind| kind.chance).sum::<u16>();
+                let index = self.rand.next_int_bounded(chance_sum as i32) as u16;
+                let mut chance_acc = 0;
+                let mut kind = kinds[0].kind;
+
+                for test_kind in kinds {
+                    chance_acc += test_kind.chance;
+                    if index < chance_acc {
+                        kind = test_kind.kind;
+                        break;
+                    }
+                }
+
+                // Keep the maximum chunk count to compare with spawn count.
+                let max_chunk_count = kind.natural_spawn_max_chunk_count();
+
+                // Keep track of the total number of entity spawned in that chunk.
+                let mut spawn_count = 0usize;
+
+                'pack: for _ in 0..3 {
+
+                    let mut spawn_pos = center_pos;
+
+                    'chain: for _ in 0..4 {
+
+                        spawn_pos += IVec3 {
+                            x: self.rand.next_int_bounded(6) - self.rand.next_int_bounded(6),
+                            y: self.rand.next_int_bounded(1) - self.rand.next_int_bounded(1),
+                            z: self.rand.next_int_bounded(6) - self.rand.next_int_bounded(6),
+                        };
+
+                        // Preliminary check if the block position is valid.
+                        if category == EntityCategory::WaterAnimal {
+
+                            // Water animals can only spawn in liquid.
+                            if !self.get_block_material(spawn_pos, cache).is_fluid() {
+                                continue;
+                            }
+
+                            // Water animals cannot spawn if above block is opaque.
+                            if self.is_block_opaque_cube(spawn_pos + IVec3::Y, cache) {
+                                continue;
+                            }
+
+                        } else {
+
+                            // The 2 block column should not be opaque cube.
+                            if self.is_block_opaque_cube(spawn_pos, cache) || self.is_block_opaque_cube(spawn_pos + IVec3::Y, cache) {
+                                continue;
+                            }
+
+                            // Block below should be opaque.
+                            if !self.is_block_opaque_cube(spawn_pos - IVec3::Y, cache) {
+                                continue;
+                            }
+
+                            // PARITY: We don't do the fluid block check because it would
+                            // be redundant with the check in 'can_natural_spawn'.
+
+                        }
+
+                        let spawn_pos = spawn_pos.as_dvec3() + DVec3::new(0.5, 0.0, 0.5);
+
+                        // PARITY: We check that this entity would be in the 128.0 block
+                        // no-despawn range of at least one player. This avoid entities
+                        // to be instantly removed after spawning.
+                        let mut close_player = false;
+                        for &player_pos in &players_pos {
+                            // If there is a player too close to that spawn point, abort.
+                            let player_dist_sq = player_pos.distance_squared(spawn_pos);
+                            if player_dist_sq < SPAWN_MIN_DIST_SQUARED {
+                                continue 'chain;
+                            } else if player_dist_sq <= 128.0 * 128.0 {
+                                close_player = true;
+                            }
+                        }
+
+                        // Skip if no player is in range to keep this natural entity.
+                        if !close_player {
+                            continue;
+                        }
+
+                        // TODO: Do not spawn inside spawn chunks
+
+                        let mut entity = kind.new_default(spawn_pos);
+                        entity.0.persistent = true;
+                        entity.0.look.x = self.rand.next_float() * std::f32::consts::TAU;
+
+                        // Important to init natural spawn before checking if it can spawn
+                        // because slime may be resized, so this can change the bb.
+                        entity.init_natural_spawn(self);
+
+                        // Skip if the entity cannot be spawned.
+                        if !entity.can_natural_spawn(self, cache) {
+                            continue;
+                        }
+
+                        // TODO: Spawning spider has 1% chance of being spider jockey.
+
+                        self.spawn_entity(entity);
+                        spawn_count += 1;
+                        if spawn_count >= max_chunk_count {
+                            break 'pack;
+                        }
+
+                    }
+
+                }
+
+            }
+
+        }
+
+        // To avoid too short allocation...
+        LOADED_CHUNKS.set(loaded_chunks);
+
+    }
 
     /// Update the sky light value depending on the current time, it is then used to get
     /// the real light value of blocks.
@@ -1387,261 +1390,258 @@ impl StdbWorld {
 
     }
 
-    // /// Internal function to tick the internal scheduler.
-    // fn tick_blocks(&mut self) {
-    //
-    //     debug_assert_eq!(self.block_ticks.len(), self.block_ticks_states.len());
-    //
-    //     // Schedule ticks...
-    //     while let Some(tick) = self.block_ticks.first() {
-    //         if self.time > tick.time {
-    //             // This tick should be activated.
-    //             let tick = self.block_ticks.pop_first().unwrap();
-    //             assert!(self.block_ticks_states.remove(&tick.state));
-    //             // Check coherency of the scheduled tick and current block.
-    //             if let Some((id, metadata)) = self.get_block(tick.state.pos) {
-    //                 if id == tick.state.id {
-    //                     self.tick_block_unchecked(tick.state.pos, id, metadata, false);
-    //                 }
-    //             }
-    //         } else {
-    //             // Our set is ordered by time first, so we break when past current time.
-    //             break;
-    //         }
-    //     }
-    //
-    //     // Random ticking...
-    //     let mut pending_random_ticks = RANDOM_TICKS_PENDING.take();
-    //     debug_assert!(pending_random_ticks.is_empty());
-    //
-    //     // Random tick only on loaded chunks.
-    //     for (&(cx, cz), chunk) in &mut self.chunks {
-    //         if let Some(chunk_data) = &chunk.data {
-    //
-    //             // TODO: Lightning strikes.
-    //             // TODO: Random snowing.
-    //
-    //             let chunk_pos = IVec3::new(cx * CHUNK_WIDTH as i32, 0, cz * CHUNK_WIDTH as i32);
-    //
-    //             // Minecraft run 80 random ticks per tick per chunk.
-    //             for _ in 0..80 {
-    //
-    //                 self.random_ticks_seed = self.random_ticks_seed
-    //                     .wrapping_mul(3)
-    //                     .wrapping_add(1013904223);
-    //
-    //                 let rand = self.random_ticks_seed >> 2;
-    //                 let pos = IVec3::new((rand >> 0) & 15, (rand >> 16) & 127, (rand >> 8) & 15);
-    //
-    //                 let (id, metadata) = chunk_data.get_block(pos);
-    //                 pending_random_ticks.push((chunk_pos + pos, id, metadata));
-    //
-    //             }
-    //
-    //         }
-    //     }
-    //
-    //     for (pos, id, metadata) in pending_random_ticks.drain(..) {
-    //         self.tick_block_unchecked(pos, id, metadata, true);
-    //     }
-    //
-    //     RANDOM_TICKS_PENDING.set(pending_random_ticks);
-    //
-    // }
+    /// Internal function to tick the internal scheduler.
+    fn tick_blocks(&mut self, cache: &mut ChunkCache) {
 
-    // /// Internal function to tick all entities.
-    // fn tick_entities(&mut self, nano_time: u128) {
-    //
-    //     self.entities.reset();
-    //
-    //     while let Some((_, comp)) = self.entities.current_mut() {
-    //
-    //         if !comp.loaded {
-    //             self.entities.advance();
-    //             continue;
-    //         }
-    //
-    //         let mut entity = comp.inner.take()
-    //             .expect("entity was already being updated");
-    //
-    //         let id = comp.id;
-    //         let (prev_cx, prev_cz) = (comp.cx, comp.cz);
-    //         entity.tick(&mut *self, id, nano_time);
-    //
-    //         // Get the component again, the entity may have been removed.
-    //         if let Some((index, comp)) = self.entities.current_mut() {
-    //
-    //             debug_assert_eq!(comp.id, id, "entity id incoherent");
-    //
-    //             // Check if the entity moved to another chunk...
-    //             let (new_cx, new_cz) = calc_entity_chunk_pos(entity.0.pos);
-    //             comp.inner = Some(entity);
-    //
-    //             if (prev_cx, prev_cz) != (new_cx, new_cz) {
-    //
-    //                 // NOTE: This part is really critical as this ensures Memory Safety
-    //                 // in iterators and therefore avoids Undefined Behaviors. Each entity
-    //                 // really needs to be in a single chunk at a time.
-    //
-    //                 let removed_index = self.chunks.get_mut(&(prev_cx, prev_cz))
-    //                     .expect("entity previous chunk is missing")
-    //                     .entities.remove(&id);
-    //                 debug_assert_eq!(removed_index, Some(index), "entity is incoherent in its previous chunk");
-    //
-    //                 // Update the world entity to its new chunk and orphan state.
-    //                 comp.cx = new_cx;
-    //                 comp.cz = new_cz;
-    //
-    //                 // Insert the entity in its new chunk.
-    //                 let new_chunk_comp = self.chunks.entry((new_cx, new_cz)).or_default();
-    //                 let insert_success = new_chunk_comp.entities.insert(id, index).is_none();
-    //                 debug_assert!(insert_success, "entity was already present in its new chunk");
-    //                 // Update the loaded flag of the entity depending on the new chunk
-    //                 // being loaded or not.
-    //                 comp.loaded = new_chunk_comp.data.is_some();
-    //
-    //                 self.push_event(Event::Chunk { cx: prev_cx, cz: prev_cz, inner: ChunkEvent::Dirty });
-    //                 self.push_event(Event::Chunk { cx: new_cx, cz: new_cz, inner: ChunkEvent::Dirty });
-    //
-    //             }
-    //
-    //         }
-    //
-    //         self.entities.advance();
-    //
-    //     }
-    //
-    // }
+        // Schedule ticks... Every tick due before the current time is processed, even if
+        // its time has been skipped (when sleeping for example), so due ticks are queried
+        // for each time since the last processed one. Ticks are processed in time order
+        // and then in scheduling order, and their count is limited, the remaining ones
+        // are processed on next ticks. Ticks scheduled while processing have a time of at
+        // least the current time, and will be processed on next tick.
+        let mut ticks = Vec::new();
+        while self.block_ticks_time < self.time {
 
-    // fn tick_block_entities(&mut self) {
-    //
-    //     self.block_entities.reset();
-    //
-    //     while let Some((_, comp)) = self.block_entities.current_mut() {
-    //
-    //         if !comp.loaded {
-    //             self.block_entities.advance();
-    //             continue;
-    //         }
-    //
-    //         let mut block_entity = comp.inner.take()
-    //             .expect("block entity was already being updated");
-    //
-    //         let pos = comp.pos;
-    //         block_entity.tick(self, pos);
-    //
-    //         // Get the component again and re-insert the block entity.
-    //         if let Some((_, comp)) = self.block_entities.current_mut() {
-    //             comp.inner = Some(block_entity);
-    //         }
-    //
-    //         self.block_entities.advance();
-    //
-    //     }
-    //
-    // }
+            let mut due_ticks = StdbBlockTick::filter_by_time(&self.block_ticks_time)
+                .filter(|tick| tick.dimension_id == self.dimension_id)
+                .collect::<Vec<_>>();
+            due_ticks.sort_by_key(|tick| tick.uid);
 
-    /// Tick pending light updates for a maximum number of light updates. This function
-    /// returns true only if all light updates have been processed.
-    /// TODO(jdetter): Look through this because this impl is super suspicious and I think *very*
-    ///  likely there are some bugs here in the stdb impl.
-    pub fn tick_light(&mut self, limit: usize, cache: &mut ChunkCache) {
+            let remaining = MAX_BLOCK_TICKS - ticks.len();
+            if due_ticks.len() > remaining {
+                // Some ticks at this time are left, continue from this time next tick.
+                ticks.extend(due_ticks.into_iter().take(remaining));
+                break;
+            }
 
-        // IMPORTANT NOTE: This algorithm is terrible but works, I've been trying to come
-        // with a better one but it has been too complicated so far.
-        for _ in 0..limit {
+            ticks.extend(due_ticks);
+            self.block_ticks_time += 1;
 
-            let Some(update) = self.pop_light_update_inner() else { break };
+        }
 
-            let mut max_face_emission = 0;
-            for face in Face::ALL {
+        for tick in ticks {
+            StdbBlockTick::delete_by_uid(&tick.uid);
+            // Check coherency of the scheduled tick and current block.
+            let pos = IVec3::from(tick.pos);
+            if let Some((id, metadata)) = self.get_block(pos, cache) {
+                if id == tick.id {
+                    self.tick_block_unchecked(pos, id, metadata, false, cache);
+                }
+            }
+        }
 
-                let face_pos = <StdbI32Vec3 as Into<IVec3>>::into(update.pos) + face.delta();
+        // Random ticking...
+        let mut pending_random_ticks = RANDOM_TICKS_PENDING.take();
+        debug_assert!(pending_random_ticks.is_empty());
 
-                let Some((cx, cz)) = calc_chunk_pos(face_pos) else { continue };
-                let Some(mut chunk) = self.get_chunk(cx, cz, cache) else { continue };
+        // Random tick only on chunks around players, these are the chunks viewed by them.
+        // The set is ordered so that the random ticks seed is used in a stable order.
+        let chunk_ids = StdbChunkView::filter_by_dimension_id(&self.dimension_id)
+            .map(|view| view.chunk_id)
+            .collect::<BTreeSet<_>>();
 
-                let face_emission = match update.kind {
-                    LightKind::Block => chunk.chunk.get_block_light(face_pos),
-                    LightKind::Sky => chunk.chunk.get_sky_light(face_pos),
-                };
+        for chunk_id in chunk_ids {
+
+            let (cx, cz) = StdbChunk::chunk_id_to_xz(chunk_id);
+            let Some(chunk) = cache.get_chunk(cx, cz) else { continue };
+
+            // TODO: Lightning strikes.
+            // TODO: Random snowing.
+
+            let chunk_pos = IVec3::new(cx * CHUNK_WIDTH as i32, 0, cz * CHUNK_WIDTH as i32);
+
+            // Minecraft run 80 random ticks per tick per chunk.
+            for _ in 0..80 {
+
+                self.random_ticks_seed = self.random_ticks_seed
+                    .wrapping_mul(3)
+                    .wrapping_add(1013904223);
+
+                let rand = self.random_ticks_seed >> 2;
+                let pos = IVec3::new(rand & 15, (rand >> 16) & 127, (rand >> 8) & 15);
+
+                let (id, metadata) = chunk.chunk.get_block(pos);
+                pending_random_ticks.push((chunk_pos + pos, id, metadata));
+
+            }
+
+        }
+
+        for (pos, id, metadata) in pending_random_ticks.drain(..) {
+            self.tick_block_unchecked(pos, id, metadata, true, cache);
+        }
 
-                max_face_emission = max_face_emission.max(face_emission);
-                if max_face_emission == 15 {
+        RANDOM_TICKS_PENDING.set(pending_random_ticks);
+
+    }
+
+    /// Internal function to tick all entities, player entities are moved by their client
+    /// and are therefore only checked for hurts and pickups. Each entity is loaded before
+    /// its tick and stored back after, unless it has been removed in the meantime.
+    fn tick_entities(&mut self, cache: &mut ChunkCache) {
+
+        let mut ids = StdbEntity::filter_by_dimension_id(&self.dimension_id)
+            .map(|row| (row.entity_id, row.kind))
+            .collect::<Vec<_>>();
+
+        ids.sort_unstable_by_key(|&(id, _)| id);
+        entity::reset_path_finder_budget();
+
+        for (id, kind) in ids {
+            // The entity may have been removed by a previously ticked entity.
+            let Some(mut entity) = self.get_entity(id) else { continue };
+            let prev_entity = entity.clone();
+            if kind == EntityKind::Human {
+                entity.tick_player(self, id, cache);
+            } else {
+                entity.tick(self, id, cache);
+            }
+            // Only store the entity on changes, rows of idle entities are left untouched.
+            if entity != prev_entity {
+                self.set_entity_changes(id, &prev_entity, &entity);
+            }
+        }
+
+    }
+
+    /// Tick all block entities that need it, even if no player is around.
+    fn tick_block_entities(&mut self, cache: &mut ChunkCache) {
+        for mut furnace in StdbFurnaceBlockEntity::filter_by_dimension_id(&self.dimension_id).collect::<Vec<_>>() {
+            let prev_furnace = furnace.clone();
+            furnace.tick(self, cache);
+            // Only update the row on changes, an idle furnace is left untouched.
+            if furnace != prev_furnace {
+                StdbFurnaceBlockEntity::update_by_block_entity_id(&furnace.block_entity_id.clone(), furnace);
+            }
+        }
+        for mut spawner in StdbSpawnerBlockEntity::filter_by_dimension_id(&self.dimension_id).collect::<Vec<_>>() {
+            let prev_spawner = spawner.clone();
+            spawner.tick(self, cache);
+            if spawner != prev_spawner {
+                StdbSpawnerBlockEntity::update_by_block_entity_id(&spawner.block_entity_id.clone(), spawner);
+            }
+        }
+        // Moving pistons may finish other moving pistons, so each one is checked again.
+        for piston in StdbPistonBlockEntity::filter_by_dimension_id(&self.dimension_id).collect::<Vec<_>>() {
+            if let Some(piston) = StdbPistonBlockEntity::filter_by_block_entity_id(&piston.block_entity_id) {
+                piston.tick(self, cache);
+            }
+        }
+    }
+
+    /// Tick pending light updates for a maximum number of light updates. Updates are
+    /// processed in scheduling order, each one being queried from its sequence number in
+    /// the world's light queue. Updates scheduled while propagating light are processed
+    /// in the same tick if the limit is not reached. Chunks with modified lights are
+    /// written back with the cache, and therefore sent again to clients.
+    pub fn tick_light(&mut self, limit: usize, cache: &mut ChunkCache) {
+
+        let Some(queue) = StdbLightQueue::filter_by_dimension_id(&self.dimension_id) else { return };
+        let mut head = queue.head;
+        let mut tail = queue.tail;
+
+        for _ in 0..limit {
+
+            if head >= tail {
+                // Updates may have been pushed to the queue while propagating.
+                tail = StdbLightQueue::filter_by_dimension_id(&self.dimension_id).unwrap().tail;
+                if head >= tail {
                     break;
                 }
+            }
 
+            let update = StdbLightUpdate::filter_by_seq(&head)
+                .find(|update| update.dimension_id == self.dimension_id);
+
+            head += 1;
+
+            if let Some(update) = update {
+                StdbLightUpdate::delete_by_uid(&update.uid);
+                self.tick_light_update(update, cache);
             }
 
-            let Some((cx, cz)) = calc_chunk_pos(update.pos.into()) else { continue };
-            let Some(mut chunk) = self.get_chunk(cx, cz, cache) else { continue };
+        }
 
-            let (id, _) = chunk.chunk.get_block(update.pos.into());
-            let opacity = block::material::get_light_opacity(id).max(1);
+        let mut queue = StdbLightQueue::filter_by_dimension_id(&self.dimension_id).unwrap();
+        queue.head = head;
+        queue.set();
 
-            let emission = match update.kind {
-                LightKind::Block => block::material::get_light_emission(id),
-                LightKind::Sky => {
-                    // If the block is above ground, then it has
-                    let column_height = chunk.chunk.get_height(update.pos.into()) as i32;
-                    if update.pos.y >= column_height { 15 } else { 0 }
-                }
-            };
+    }
 
-            let new_light = emission.max(max_face_emission.saturating_sub(opacity));
-            let mut changed = false;
-            let mut sky_exposed = false;
+    /// Process a single light update, propagating to surrounding blocks if the light 
+    /// level has changed.
+    fn tick_light_update(&mut self, update: StdbLightUpdate, cache: &mut ChunkCache) {
 
-            match update.kind {
-                LightKind::Block => {
-                    if chunk.chunk.get_block_light(update.pos.into()) != new_light {
-                        chunk.chunk.set_block_light(update.pos.into(), new_light);
-                        changed = true;
-                    }
-                }
-                LightKind::Sky => {
-                    if chunk.chunk.get_sky_light(update.pos.into()) != new_light {
-                        chunk.chunk.set_sky_light(update.pos.into(), new_light);
-                        changed = true;
-                        sky_exposed = emission == 15;
-                    }
-                }
+        // IMPORTANT NOTE: This algorithm is terrible but works, I've been trying to come
+        // with a better one but it has been too complicated so far.
+        let pos = IVec3::from(update.pos);
+
+        let mut max_face_emission = 0;
+        for face in Face::ALL {
+
+            let face_pos = pos + face.delta();
+
+            let Some((cx, cz)) = calc_chunk_pos(face_pos) else { continue };
+            let Some(chunk) = cache.get_chunk_ref(cx, cz) else { continue };
+
+            let face_emission = match update.kind {
+                LightKind::Block => chunk.chunk.get_block_light(face_pos),
+                LightKind::Sky => chunk.chunk.get_sky_light(face_pos),
+            };
+
+            max_face_emission = max_face_emission.max(face_emission);
+            if max_face_emission == 15 {
+                break;
             }
 
+        }
 
-            if changed {
-                // TODO: There is probably a bug here
-                // StdbChunkEvent::insert(StdbChunkEvent {
-                //     x: cx,
-                //     z: cz,
-                //     inner: ChunkEvent::Dirty,
-                // });
+        let Some((cx, cz)) = calc_chunk_pos(pos) else { return };
+        let Some(chunk) = cache.get_chunk_ref(cx, cz) else { return };
 
-                // self.push_event(Event::Chunk { cx, cz, inner: ChunkEvent::Dirty });
-                cache.set_chunk(chunk);
+        let (id, _) = chunk.chunk.get_block(pos);
+        let opacity = block::material::get_light_opacity(id).max(1);
+
+        let emission = match update.kind {
+            LightKind::Block => block::material::get_light_emission(id),
+            LightKind::Sky => {
+                // If the block is above ground, then it has the maximum sky light.
+                let column_height = chunk.chunk.get_height(pos) as i32;
+                if pos.y >= column_height { 15 } else { 0 }
             }
+        };
 
-            if changed && update.credit >= 1 {
-                for face in Face::ALL {
-                    // Do not propagate light upward when the updated block is above 
-                    // ground, so all blocks above are also exposed and should already
-                    // be at max level.
-                    if face == Face::PosY && sky_exposed {
-                        continue;
-                    }
-                    self.push_light_update_inner(LightUpdate {
-                        kind: update.kind,
-                        // pos: update.pos.into() + face.delta(),
-                        pos: (<StdbI32Vec3 as Into<IVec3>>::into(update.pos) + face.delta()).into(),
-                        credit: update.credit - 1,
-                    });
+        let new_light = emission.max(max_face_emission.saturating_sub(opacity));
+        let prev_light = match update.kind {
+            LightKind::Block => chunk.chunk.get_block_light(pos),
+            LightKind::Sky => chunk.chunk.get_sky_light(pos),
+        };
+
+        if prev_light == new_light {
+            return;
+        }
+
+        // Only get the chunk mutably on change, so it's only written back if modified.
+        let chunk = cache.get_chunk_mut(cx, cz).unwrap();
+        match update.kind {
+            LightKind::Block => chunk.chunk.set_block_light(pos, new_light),
+            LightKind::Sky => chunk.chunk.set_sky_light(pos, new_light),
+        }
+
+        if update.credit >= 1 {
+            let sky_exposed = update.kind == LightKind::Sky && emission == 15;
+            for face in Face::ALL {
+                // Do not propagate light upward when the updated block is above 
+                // ground, so all blocks above are also exposed and should already
+                // be at max level.
+                if face == Face::PosY && sky_exposed {
+                    continue;
                 }
+                self.push_light_update(pos + face.delta(), update.kind, update.credit - 1);
             }
-            
         }
 
     }
-
 }
 
 
@@ -1984,63 +1984,230 @@ struct EntityComponent {
 //     pos: IVec3,
 // }
 
-// /// A block tick position, this is always linked to a [`ScheduledTick`] being added to
-// /// the tree map, this structure is also stored appart in order to check that two ticks
-// /// are not scheduled for the same position and block id.
-// #[derive(Clone, Copy, Hash, PartialEq, Eq)]
-// struct BlockTickState {
-//     /// Position of the block to tick.
-//     pos: IVec3,
-//     /// The expected id of the block, if the block has no longer this id, this tick is
-//     /// ignored.
-//     id: u8,
-// }
+/// A block tick scheduled in the future, it's indexed by the world time when it should
+/// be processed, so that only due ticks are queried. Ticks are also indexed by position
+/// in order to check that two ticks are not scheduled for the same position and block id.
+/// This table is only used internally by the world, so it's not public.
+#[spacetimedb(table(private))]
+#[spacetimedb(index(btree, dimension_id))]
+#[spacetimedb(index(btree, time))]
+#[spacetimedb(index(btree, pos_id))]
+#[derive(Debug, Clone)]
+pub struct StdbBlockTick {
+    /// This tick unique id within the world, also giving the scheduling order.
+    #[primarykey]
+    #[autoinc]
+    pub uid: u64,
+    /// The dimension of the world where the block is ticked.
+    pub dimension_id: i32,
+    /// The time to tick the block, it is processed on the tick after this time.
+    pub time: u64,
+    /// Position of the block to tick, packed like block entity ids.
+    pub pos_id: u64,
+    /// Position of the block to tick.
+    pub pos: StdbI32Vec3,
+    /// The expected id of the block, if the block has no longer this id, this tick is
+    /// ignored.
+    pub id: u8,
+}
 
-// /// A block tick scheduled in the future, it's associated to a world time in a tree map.
-// /// This structure is ordered by time and then by position, this allows to have multiple
-// /// block update at the same time but for different positions.
-// #[derive(Clone, Eq)]
-// struct BlockTick {
-//     /// This tick unique id within the world.
-//     uid: u64,
-//     /// The time to tick the block.
-//     time: u64,
-//     /// State of that scheduled tick.
-//     state: BlockTickState,
-// }
+/// A toggle of a redstone torch, used to burn out torches that are toggled too often.
+/// Toggles are forgotten after some time, when any torch is checked.
+#[spacetimedb(table(public))]
+#[spacetimedb(index(btree, pos_id))]
+#[derive(Debug, Clone)]
+pub struct StdbRedstoneTorchToggle {
+    #[primarykey]
+    #[autoinc]
+    pub uid: u64,
+    /// The world time of this toggle.
+    pub time: u64,
+    /// Position of the toggled torch, packed like block entity ids.
+    pub pos_id: u64,
+}
 
-// impl PartialEq for BlockTick {
-//     fn eq(&self, other: &Self) -> bool {
-//         self.uid == other.uid && self.time == other.time
-//     }
-// }
-//
-// impl PartialOrd for BlockTick {
-//     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-//         Some(Ord::cmp(self, other))
-//     }
-// }
-//
-// impl Ord for BlockTick {
-//     fn cmp(&self, other: &Self) -> Ordering {
-//         self.time.cmp(&other.time)
-//             .then(self.uid.cmp(&other.uid))
-//     }
-// }
+/// A block action to be played by clients observing the chunk, such as a note block
+/// playing its note. These events are cleared on each world tick, after being received
+/// by the proxy.
+#[spacetimedb(table(public))]
+#[spacetimedb(index(btree, dimension_id))]
+#[derive(Debug, Clone)]
+pub struct StdbBlockActionEvent {
+    #[primarykey]
+    #[autoinc]
+    pub event_id: u64,
+    /// The dimension of the world where this block action happened.
+    pub dimension_id: i32,
+    /// Position of the block playing the action.
+    pub pos: StdbI32Vec3,
+    /// Id of the chunk containing the block, used to find players observing it.
+    pub chunk_id: u32,
+    /// First action data, its meaning depends on the block.
+    pub data0: i8,
+    /// Second action data, its meaning depends on the block.
+    pub data1: i8,
+}
 
-/// A light update to apply to the world.
-#[derive(Clone, SpacetimeType)]
-pub struct LightUpdate {
+/// A sound or particle effect played by clients observing the chunk, such as the click
+/// of a dispenser. These events are cleared on each world tick.
+#[spacetimedb(table(public))]
+#[spacetimedb(index(btree, dimension_id))]
+#[derive(Debug, Clone)]
+pub struct StdbEffectEvent {
+    #[primarykey]
+    #[autoinc]
+    pub event_id: u64,
+    /// The dimension of the world where this effect happened.
+    pub dimension_id: i32,
+    /// Position of the block playing the effect.
+    pub pos: StdbI32Vec3,
+    /// Id of the chunk containing the block, used to find players observing it.
+    pub chunk_id: u32,
+    /// The effect id, see the effect packet of the proxy for known effects.
+    pub effect_id: u32,
+    /// Additional data of the effect, its meaning depends on the effect.
+    pub effect_data: u32,
+}
+
+/// An entity, typically an item or an arrow, picked up by another entity. Pickups are
+/// raised while ticking and applied to the picker's inventory at the end of the tick,
+/// the events that remain are played by clients and cleared on the next world tick.
+#[spacetimedb(table(public))]
+#[spacetimedb(index(btree, dimension_id))]
+#[derive(Debug, Clone)]
+pub struct StdbEntityPickupEvent {
+    #[primarykey]
+    #[autoinc]
+    pub event_id: u64,
+    /// The dimension of the world where this pickup happened.
+    pub dimension_id: i32,
+    /// The entity that picked up the other one.
+    pub entity_id: u32,
+    /// The entity that has been picked up.
+    pub target_id: u32,
+    /// Id of the chunk containing the picked up entity, used to find players observing it.
+    pub chunk_id: u32,
+}
+
+/// Status of an entity played by clients, statuses are pushed while ticking and
+/// cleared on the next world tick.
+#[spacetimedb(table(public))]
+#[spacetimedb(index(btree, dimension_id))]
+#[derive(Debug, Clone)]
+pub struct StdbEntityStatusEvent {
+    #[primarykey]
+    #[autoinc]
+    pub event_id: u64,
+    /// The dimension of the world where this status happened.
+    pub dimension_id: i32,
+    /// The entity playing the status.
+    pub entity_id: u32,
+    /// Id of the chunk containing the entity, used to find players observing it.
+    pub chunk_id: u32,
+    pub status: EntityStatus,
+}
+
+/// Kind of status played by clients on an entity.
+#[derive(SpacetimeType, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntityStatus {
+    /// The living entity has been hurt.
+    Hurt,
+    /// The living entity is dead, it is removed after its death animation.
+    Dead,
+}
+
+/// A sudden change of an entity velocity, cleared on the next world tick.
+#[spacetimedb(table(public))]
+#[spacetimedb(index(btree, dimension_id))]
+#[derive(Debug, Clone)]
+pub struct StdbEntityVelocityEvent {
+    #[primarykey]
+    #[autoinc]
+    pub event_id: u64,
+    /// The dimension of the world where this velocity change happened.
+    pub dimension_id: i32,
+    /// The entity with the new velocity.
+    pub entity_id: u32,
+    /// Id of the chunk containing the entity, used to find players observing it.
+    pub chunk_id: u32,
+    pub vel: StdbDVec3,
+}
+
+/// An explosion to be played by clients observing the chunk, blocks destroyed by the
+/// explosion are sent apart, cleared on the next world tick.
+#[spacetimedb(table(public))]
+#[spacetimedb(index(btree, dimension_id))]
+#[derive(Debug, Clone)]
+pub struct StdbExplodeEvent {
+    #[primarykey]
+    #[autoinc]
+    pub event_id: u64,
+    /// The dimension of the world where this explosion happened.
+    pub dimension_id: i32,
+    /// Id of the chunk containing the explosion center, used to find players observing it.
+    pub chunk_id: u32,
+    pub center: StdbDVec3,
+    pub radius: f32,
+}
+
+/// A light update to apply to the world, pending light updates are processed in their
+/// scheduling order, see [`StdbWorld::tick_light`]. Updates are indexed by their sequence
+/// number in the world's light queue, and also by position in order to merge updates of
+/// the same kind scheduled for the same position. This table is only used internally by
+/// the world, so it's not public.
+#[spacetimedb(table(private))]
+#[spacetimedb(index(btree, seq))]
+#[spacetimedb(index(btree, pos_id))]
+#[derive(Clone)]
+pub struct StdbLightUpdate {
+    /// This update unique id.
+    #[primarykey]
+    #[autoinc]
+    pub uid: u64,
+    /// The dimension of the world to update.
+    pub dimension_id: i32,
+    /// Sequence number of this update in the world's light queue, giving the scheduling
+    /// order.
+    pub seq: u64,
     /// Light kind targeted by this update, the update only applies to one of the kind.
-    kind: LightKind,
+    pub kind: LightKind,
+    /// Position of the light update, packed like block entity ids.
+    pub pos_id: u64,
     /// The position of the light update.
-    pos: StdbI32Vec3,
-    // pos: Vec<i32>,
+    pub pos: StdbI32Vec3,
     /// Credit remaining to update light, this is used to limit the number of updates
     /// produced by a block chance initial update. Initial value is something like 15
     /// and decrease for each propagation, when it reaches 0 the light update stops 
     /// propagating.
-    credit: u8,
+    pub credit: u8,
+}
+
+/// The queue of pending light updates of a world, each update is given the next sequence
+/// number in the queue when scheduled, so that updates can be queried one after another
+/// in their scheduling order.
+#[spacetimedb(table(private))]
+#[derive(Clone)]
+pub struct StdbLightQueue {
+    /// The dimension of the world of this queue.
+    #[primarykey]
+    pub dimension_id: i32,
+    /// Sequence number of the next light update to process.
+    pub head: u64,
+    /// Sequence number to give to the next scheduled light update.
+    pub tail: u64,
+}
+
+impl StdbLightQueue {
+
+    /// Insert this queue, or replace the one of its world.
+    fn set(self) {
+        if Self::filter_by_dimension_id(&self.dimension_id).is_some() {
+            Self::update_by_dimension_id(&self.dimension_id.clone(), self);
+        } else {
+            Self::insert(self).unwrap();
+        }
+    }
+
 }
 
 /// A tick vector is an internal structure used for both entities and block entities,
@@ -5,161 +5,202 @@ use glam::{DVec3, IVec3};
 use tracing::trace;
 
 use crate::geom::BoundingBox;
-use crate::rand::JavaRandom;
 
 use crate::world::bound::RayTraceKind;
-use crate::entity::{Entity, Hurt};
+use crate::entity::{Entity, Hurt, Tnt};
 use crate::block;
+use crate::chunk_cache::ChunkCache;
+use crate::entity::component::calc_chunk_id;
 
-use super::StdbWorld;
+use super::{StdbWorld, StdbExplodeEvent};
 
 
 /// Methods related to explosions.
 impl StdbWorld {
 
-    // /// Make an explosion in the world at the given position and size. The explosion can
-    // /// optionally propagate flames around.
-    // pub fn explode(&mut self, center: DVec3, radius: f32, set_fire: bool, origin_id: Option<u32>, nano_time: u128) {
-    //
-    //     /// This is the step to advance each explosion ray.
-    //     const STEP: f32 = 0.3;
-    //
-    //     trace!("explode, center: {center}, radius: {radius}, set fire: {set_fire}, origin id: {origin_id:?}");
-    //
-    //     let mut rand = JavaRandom::new_seeded(nano_time);
-    //     let mut affected_pos = Vec::new();
-    //
-    //     // Start by computing each destroyed block.
-    //     for dx in 0..16 {
-    //         for dy in 0..16 {
-    //             for dz in 0..16 {
-    //                 if dx == 0 || dx == 15 || dy == 0 || dy == 15 || dz == 0 || dz == 15 {
-    //
-    //                     // Calculate the normalized of the explosion ray.
-    //                     let dir = (IVec3::new(dx, dy, dz).as_vec3() / 15.0) * 2.0 - 1.0;
-    //                     let dir = dir.normalize() * STEP;
-    //                     let dir = dir.as_dvec3();
-    //
-    //                     // The initial intensity of this ray of explosion.
-    //                     let mut intensity = radius * (0.7 + self.rand.next_float() * 0.6);
-    //                     let mut check_pos = center;
-    //
-    //                     while intensity > 0.0 {
-    //
-    //                         let block_pos = check_pos.floor().as_ivec3();
-    //                         let Some((block, _)) = self.get_block(block_pos) else {
-    //                             break // Just abort this ray if we enter unloaded chunk.
-    //                         };
-    //
-    //                         // NOTE: This should properly handle the infinite resistance
-    //                         // returned by some blocks, this will just set intensity to
-    //                         // negative infinity and stop the loop.
-    //                         intensity -= (block::material::get_explosion_resistance(block) + 0.3) * STEP;
-    //                         if intensity > 0.0 {
-    //
-    //                             if set_fire
-    //                             && block == block::AIR
-    //                             && self.is_block_opaque_cube(block_pos - IVec3::Y)
-    //                             && rand.next_int_bounded(3) == 0 {
-    //                                 self.set_block_notify(block_pos, block::FIRE, 0);
-    //                             }
-    //
-    //                             affected_pos.push((block_pos, block != block::AIR));
-    //
-    //                         }
-    //
-    //                         check_pos += dir;
-    //                         intensity -= (12.0 / 16.0) * STEP;
-    //
-    //                     }
-    //
-    //                 }
-    //             }
-    //         }
-    //     }
-    //
-    //     // Calculate the explosion bounding box.
-    //     let diameter = (radius * 2.0) as f64;
-    //     let bb = BoundingBox {
-    //         min: (center - diameter - 1.0).floor(),
-    //         max: (center + diameter + 1.0).floor(),
-    //     };
-    //
-    //     let mut damaged_entities = Vec::new();
-    //
-    //     // Calculate the amount of damage to apply to each entity in the bounding box.
-    //     for (collided_id, Entity(collided_base, _)) in self.iter_entities_colliding(bb) {
-    //
-    //         let delta = collided_base.pos - center;
-    //         let dist = delta.length();
-    //         let dist_norm = dist as f32 / radius;
-    //
-    //         if dist_norm <= 1.0 {
-    //
-    //             let dir = delta / dist;
-    //
-    //             // The goal here is to compute how many rays starting from every point in
-    //             // the entity bounding box we reach the explosion center. The more
-    //             let ray = collided_base.bb.min - center;
-    //             let step = 1.0 / (collided_base.bb.size() * 2.0 + 1.0);
-    //
-    //             // This is the offset to apply to the ray to go to different point into
-    //             // the bounding box, step by step.
-    //             let mut ray_offset = DVec3::ZERO;
-    //             let mut ray_pass = 0usize;
-    //             let mut ray_count = 0usize;
-    //
-    //             while ray_offset.x <= 1.0 {
-    //                 ray_offset.y = 0.0;
-    //                 while ray_offset.y <= 1.0 {
-    //                     ray_offset.z = 0.0;
-    //                     while ray_offset.z <= 1.0 {
-    //                         ray_pass += self.ray_trace_blocks(center, ray + ray_offset, RayTraceKind::Overlay).is_none() as usize;
-    //                         ray_count += 1;
-    //                         ray_offset.z += step.z;
-    //                     }
-    //                     ray_offset.y += step.y;
-    //                 }
-    //                 ray_offset.x += step.x;
-    //             }
-    //
-    //             // The final damage depends on the distance and the number of rays.
-    //             let damage_factor = (1.0 - dist_norm) * (ray_pass as f32 / ray_count as f32);
-    //             let damage = (damage_factor * damage_factor + damage_factor) / 2.0 * 8.0 * radius + 1.0;
-    //             let damage = damage as u16;
-    //
-    //             damaged_entities.push((collided_id, damage, dir * damage_factor as f64));
-    //
-    //         }
-    //
-    //     }
-    //
-    //     // Finally alter entities.
-    //     for (eid, damage, accel) in damaged_entities {
-    //
-    //         let Entity(base, _) = self.get_entity_mut(eid).unwrap();
-    //
-    //         base.hurt.push(Hurt {
-    //             damage,
-    //             origin_id,
-    //         });
-    //
-    //         base.vel += accel;
-    //
-    //     }
-    //
-    //     // Finally drain the destroyed pos and remove blocks.
-    //     for (pos, should_destroy) in affected_pos {
-    //         if should_destroy {
-    //             // We can unwrap because these position were previously checked.
-    //             let (prev_block, prev_metadata) = self.set_block_notify(pos, block::AIR, 0).unwrap();
-    //             self.spawn_block_loot(pos, prev_block, prev_metadata, 0.3);
-    //         }
-    //     }
-    //
-    //     // TODO: Another event that we don't care about in the SpacetimeDB module
-    //     // self.push_event(Event::Explode { center, radius });
-    //
-    // }
+    /// Ignite the TNT block at the given position, removing the block.
+    pub fn ignite_tnt(&mut self, pos: IVec3, cache: &mut ChunkCache) {
+
+        self.spawn_entity(Tnt::new_with(|new_base, new_tnt| {
+            new_base.pos = pos.as_dvec3() + 0.5;
+            new_tnt.fuse_time = 80;
+        }));
+        self.set_block_notify(pos, block::AIR, 0, cache);
+
+    }
+
+    /// Make an explosion in the world at the given position and size. The explosion can
+    /// optionally propagate flames around.
+    pub fn explode(&mut self, center: DVec3, radius: f32, set_fire: bool, origin_id: Option<u32>, cache: &mut ChunkCache) {
+
+        /// This is the step to advance each explosion ray.
+        const STEP: f32 = 0.3;
+
+        trace!("explode, center: {center}, radius: {radius}, set fire: {set_fire}, origin id: {origin_id:?}");
+
+        let mut affected_pos = Vec::new();
+
+        // Start by computing each destroyed block.
+        for dx in 0..16 {
+            for dy in 0..16 {
+                for dz in 0..16 {
+                    if dx == 0 || dx == 15 || dy == 0 || dy == 15 || dz == 0 || dz == 15 {
+
+                        // Calculate the normalized of the explosion ray.
+                        let dir = (IVec3::new(dx, dy, dz).as_vec3() / 15.0) * 2.0 - 1.0;
+                        let dir = dir.normalize() * STEP;
+                        let dir = dir.as_dvec3();
+
+                        // The initial intensity of this ray of explosion.
+                        let mut intensity = radius * (0.7 + self.rand.next_float() * 0.6);
+                        let mut check_pos = center;
+
+                        while intensity > 0.0 {
+
+                            let block_pos = check_pos.floor().as_ivec3();
+                            let Some((block, _)) = self.get_block(block_pos, cache) else {
+                                break // Just abort this ray if we enter unloaded chunk.
+                            };
+
+                            // NOTE: This should properly handle the infinite resistance
+                            // returned by some blocks, this will just set intensity to
+                            // negative infinity and stop the loop.
+                            intensity -= (block::material::get_explosion_resistance(block) + 0.3) * STEP;
+                            if intensity > 0.0 {
+                                affected_pos.push((block_pos, block != block::AIR));
+                            }
+
+                            check_pos += dir;
+                            intensity -= (12.0 / 16.0) * STEP;
+
+                        }
+
+                    }
+                }
+            }
+        }
+
+        // Many rays cross the same blocks, keep each position once so that each block is
+        // destroyed once and has a single chance to catch fire. Sorting is used instead of
+        // a hash set to keep a deterministic order for the random generator.
+        affected_pos.sort_unstable_by_key(|(pos, _)| pos.to_array());
+        affected_pos.dedup_by_key(|(pos, _)| *pos);
+
+        // Calculate the explosion bounding box.
+        let diameter = (radius * 2.0) as f64;
+        let bb = BoundingBox {
+            min: (center - diameter - 1.0).floor(),
+            max: (center + diameter + 1.0).floor(),
+        };
+
+        let mut damaged_entities = Vec::new();
+
+        // Calculate the amount of damage to apply to each entity in the bounding box.
+        let colliding = self.iter_entities_colliding(bb).collect::<Vec<_>>();
+        for (collided_id, Entity(collided_base, _)) in colliding {
+
+            let delta = collided_base.pos - center;
+            let dist = delta.length();
+            let dist_norm = dist as f32 / radius;
+
+            if dist_norm <= 1.0 {
+
+                let dir = delta / dist;
+
+                // The goal here is to compute how many rays starting from every point in
+                // the entity bounding box we reach the explosion center. The more
+                let ray = collided_base.bb.min - center;
+                let step = 1.0 / (collided_base.bb.size() * 2.0 + 1.0);
+
+                // This is the offset to apply to the ray to go to different point into
+                // the bounding box, step by step.
+                let mut ray_offset = DVec3::ZERO;
+                let mut ray_pass = 0usize;
+                let mut ray_count = 0usize;
+
+                while ray_offset.x <= 1.0 {
+                    ray_offset.y = 0.0;
+                    while ray_offset.y <= 1.0 {
+                        ray_offset.z = 0.0;
+                        while ray_offset.z <= 1.0 {
+                            ray_pass += self.ray_trace_blocks(center, ray + ray_offset, RayTraceKind::Overlay, cache).is_none() as usize;
+                            ray_count += 1;
+                            ray_offset.z += step.z;
+                        }
+                        ray_offset.y += step.y;
+                    }
+                    ray_offset.x += step.x;
+                }
+
+                // The final damage depends on the distance and the number of rays.
+                let damage_factor = (1.0 - dist_norm) * (ray_pass as f32 / ray_count as f32);
+                let damage = (damage_factor * damage_factor + damage_factor) / 2.0 * 8.0 * radius + 1.0;
+                let damage = damage as u16;
+
+                damaged_entities.push((collided_id, damage, dir * damage_factor as f64));
+
+            }
+
+        }
+
+        // Finally alter entities, the velocity is pushed for clients to apply it.
+        for (eid, damage, accel) in damaged_entities {
+
+            let Some(mut entity) = self.get_entity(eid) else { continue };
+            let Entity(base, _) = &mut entity;
+
+            base.hurt.push(Hurt {
+                damage,
+                origin_id,
+            });
+
+            base.vel += accel;
+            let vel = base.vel;
+
+            self.set_entity(eid, &entity);
+            self.push_entity_velocity(eid, vel);
+
+        }
+
+        // Finally drain the destroyed pos and remove blocks.
+        for &(pos, should_destroy) in &affected_pos {
+            if should_destroy {
+                let Some((prev_block, prev_metadata)) = self.set_block_notify(pos, block::AIR, 0, cache) else { continue };
+                if prev_block == block::TNT {
+                    // TNT destroyed by an explosion is ignited with a shorter fuse.
+                    // REF: BlockTNT::onBlockDestroyedByExplosion
+                    let fuse_time = self.rand.next_int_bounded(20) as u32 + 10;
+                    self.spawn_entity(Tnt::new_with(|new_base, new_tnt| {
+                        new_base.pos = pos.as_dvec3() + 0.5;
+                        new_tnt.fuse_time = fuse_time;
+                    }));
+                } else if prev_block != block::AIR {
+                    self.spawn_block_loot(pos, prev_block, prev_metadata, 0.3);
+                }
+            }
+        }
+
+        // Propagate flames on the air blocks above opaque cubes, after destroying blocks.
+        if set_fire {
+            for &(pos, _) in &affected_pos {
+                if self.is_block_air(pos, cache)
+                && self.is_block_opaque_cube(pos - IVec3::Y, cache)
+                && self.rand.next_int_bounded(3) == 0 {
+                    self.set_block_notify(pos, block::FIRE, 0, cache);
+                }
+            }
+        }
+
+        // Notify clients observing the chunk of the explosion, the destroyed blocks are
+        // already sent through block updates.
+        StdbExplodeEvent::insert(StdbExplodeEvent {
+            event_id: 0,
+            dimension_id: self.dimension_id,
+            chunk_id: calc_chunk_id(center),
+            center: center.into(),
+            radius,
+        }).unwrap();
+
+    }
 
 }
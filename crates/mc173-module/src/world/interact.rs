@@ -3,158 +3,207 @@
 use glam::IVec3;
 
 // use crate::block_entity::BlockEntity;
+use crate::block_entity::chest::StdbChestBlockEntity;
+use crate::block_entity::dispenser::StdbDispenserBlockEntity;
+use crate::block_entity::furnace::StdbFurnaceBlockEntity;
+use crate::block_entity::note_block::{StdbNoteBlockBlockEntity, NOTES_COUNT};
 use crate::geom::Face;
 use crate::block;
+use crate::chunk_cache::ChunkCache;
 
-use super::StdbWorld;
+use super::{StdbWorld, DIMENSION_NETHER};
 
 
 /// Methods related to block interactions when client clicks on a block.
 impl StdbWorld {
 
-    // /// Interact with a block at given position. This function returns true if an
-    // /// interaction has been handled and some action happened to the world, which should
-    // /// typically prevent usage of the player's hand item.
-    // pub fn interact_block(&mut self, pos: IVec3) -> Interaction {
-    //     if let Some((id, metadata)) = self.get_block(pos) {
-    //         self.interact_block_unchecked(pos, id, metadata)
-    //     } else {
-    //         Interaction::None
-    //     }
-    // }
+    /// Interact with a block at given position. This function returns true if an
+    /// interaction has been handled and some action happened to the world, which should
+    /// typically prevent usage of the player's hand item.
+    pub fn interact_block(&mut self, pos: IVec3, cache: &mut ChunkCache) -> Interaction {
+        if let Some((id, metadata)) = self.get_block(pos, cache) {
+            self.interact_block_unchecked(pos, id, metadata, cache)
+        } else {
+            Interaction::None
+        }
+    }
 
-    // /// Internal function to handle block interaction at given position and with known
-    // /// block and metadata. The function returns true if an interaction has been handled.
-    // pub(super) fn interact_block_unchecked(&mut self, pos: IVec3, id: u8, metadata: u8) -> Interaction {
-    //     match id {
-    //         block::BUTTON => self.interact_button(pos, metadata),
-    //         block::LEVER => self.interact_lever(pos, metadata),
-    //         block::TRAPDOOR => self.interact_trapdoor(pos, metadata),
-    //         block::IRON_DOOR => true,
-    //         block::WOOD_DOOR => self.interact_wood_door(pos, metadata),
-    //         block::REPEATER |
-    //         block::REPEATER_LIT => self.interact_repeater(pos, id, metadata),
-    //         block::REDSTONE_ORE => self.interact_redstone_ore(pos),
-    //         block::CRAFTING_TABLE => return Interaction::CraftingTable { pos },
-    //         block::CHEST => return self.interact_chest(pos),
-    //         block::FURNACE |
-    //         block::FURNACE_LIT => return self.interact_furnace(pos),
-    //         block::DISPENSER => return self.interact_dispenser(pos),
-    //         _ => return Interaction::None
-    //     }.into()
-    // }
+    /// Internal function to handle block interaction at given position and with known
+    /// block and metadata. The function returns true if an interaction has been handled.
+    pub(super) fn interact_block_unchecked(&mut self, pos: IVec3, id: u8, metadata: u8, cache: &mut ChunkCache) -> Interaction {
+        match id {
+            block::BUTTON => self.interact_button(pos, metadata, cache),
+            block::LEVER => self.interact_lever(pos, metadata, cache),
+            block::TRAPDOOR => self.interact_trapdoor(pos, metadata, cache),
+            block::IRON_DOOR => Interaction::Handled,
+            block::WOOD_DOOR => self.interact_wood_door(pos, metadata, cache),
+            block::REPEATER |
+            block::REPEATER_LIT => self.interact_repeater(pos, id, metadata, cache),
+            block::NOTE_BLOCK => self.interact_note_block(pos, cache),
+            // block::REDSTONE_ORE => self.interact_redstone_ore(pos),
+            block::CRAFTING_TABLE => Interaction::CraftingTable { pos },
+            block::CHEST => self.interact_chest(pos, cache),
+            block::FURNACE |
+            block::FURNACE_LIT => self.interact_furnace(pos),
+            block::DISPENSER => self.interact_dispenser(pos),
+            block::BED => self.interact_bed(pos, metadata, cache),
+            _ => Interaction::None
+        }
+    }
 
-    // /// Interact with a button block.
-    // fn interact_button(&mut self, pos: IVec3, mut metadata: u8) -> bool {
-    //     if !block::button::is_active(metadata) {
-    //         block::button::set_active(&mut metadata, true);
-    //         self.set_block_notify(pos, block::BUTTON, metadata);
-    //         self.schedule_block_tick(pos, block::BUTTON, 20);
-    //     }
-    //     true
-    // }
+    /// Interact with a button block.
+    fn interact_button(&mut self, pos: IVec3, mut metadata: u8, cache: &mut ChunkCache) -> Interaction {
+        if !block::button::is_active(metadata) {
+            block::button::set_active(&mut metadata, true);
+            self.set_block_notify(pos, block::BUTTON, metadata, cache);
+            self.schedule_block_tick(pos, block::BUTTON, 20);
+        }
+        Interaction::Handled
+    }
 
-    // fn interact_lever(&mut self, pos: IVec3, mut metadata: u8) -> bool {
-    //     let active = block::lever::is_active(metadata);
-    //     block::lever::set_active(&mut metadata, !active);
-    //     self.set_block_notify(pos, block::LEVER, metadata);
-    //     true
-    // }
+    /// Interact with a lever block, toggling it.
+    fn interact_lever(&mut self, pos: IVec3, mut metadata: u8, cache: &mut ChunkCache) -> Interaction {
+        let active = block::lever::is_active(metadata);
+        block::lever::set_active(&mut metadata, !active);
+        self.set_block_notify(pos, block::LEVER, metadata, cache);
+        Interaction::Handled
+    }
 
-    // fn interact_trapdoor(&mut self, pos: IVec3, mut metadata: u8) -> bool {
-    //     let active = block::trapdoor::is_open(metadata);
-    //     block::trapdoor::set_open(&mut metadata, !active);
-    //     self.set_block_notify(pos, block::TRAPDOOR, metadata);
-    //     true
-    // }
+    /// Interact with a trapdoor block, opening or closing it.
+    fn interact_trapdoor(&mut self, pos: IVec3, mut metadata: u8, cache: &mut ChunkCache) -> Interaction {
+        let active = block::trapdoor::is_open(metadata);
+        block::trapdoor::set_open(&mut metadata, !active);
+        self.set_block_notify(pos, block::TRAPDOOR, metadata, cache);
+        Interaction::Handled
+    }
 
-    // fn interact_wood_door(&mut self, pos: IVec3, mut metadata: u8) -> bool {
-    //
-    //     if block::door::is_upper(metadata) {
-    //         if let Some((block::WOOD_DOOR, metadata)) = self.get_block(pos - IVec3::Y) {
-    //             self.interact_wood_door(pos - IVec3::Y, metadata);
-    //         }
-    //     } else {
-    //
-    //         let open = block::door::is_open(metadata);
-    //         block::door::set_open(&mut metadata, !open);
-    //
-    //         self.set_block_notify(pos, block::WOOD_DOOR, metadata);
-    //
-    //         if let Some((block::WOOD_DOOR, _)) = self.get_block(pos + IVec3::Y) {
-    //             block::door::set_upper(&mut metadata, true);
-    //             self.set_block_notify(pos + IVec3::Y, block::WOOD_DOOR, metadata);
-    //         }
-    //
-    //     }
-    //
-    //     true
-    //
-    // }
+    /// Interact with a wood door block, opening or closing both halves of the door.
+    fn interact_wood_door(&mut self, pos: IVec3, mut metadata: u8, cache: &mut ChunkCache) -> Interaction {
 
-    // fn interact_repeater(&mut self, pos: IVec3, id: u8, mut metadata: u8) -> bool {
-    //     let delay = block::repeater::get_delay(metadata);
-    //     block::repeater::set_delay(&mut metadata, (delay + 1) % 4);
-    //     self.set_block_notify(pos, id, metadata);
-    //     true
-    // }
+        if block::door::is_upper(metadata) {
+            if let Some((block::WOOD_DOOR, metadata)) = self.get_block(pos - IVec3::Y, cache) {
+                self.interact_wood_door(pos - IVec3::Y, metadata, cache);
+            }
+        } else {
+
+            let open = block::door::is_open(metadata);
+            block::door::set_open(&mut metadata, !open);
+
+            self.set_block_notify(pos, block::WOOD_DOOR, metadata, cache);
+
+            if let Some((block::WOOD_DOOR, _)) = self.get_block(pos + IVec3::Y, cache) {
+                block::door::set_upper(&mut metadata, true);
+                self.set_block_notify(pos + IVec3::Y, block::WOOD_DOOR, metadata, cache);
+            }
+
+        }
+
+        Interaction::Handled
+
+    }
+
+    /// Interact with a repeater block, cycling its delay.
+    fn interact_repeater(&mut self, pos: IVec3, id: u8, mut metadata: u8, cache: &mut ChunkCache) -> Interaction {
+        let delay = block::repeater::get_delay(metadata);
+        block::repeater::set_delay(&mut metadata, (delay + 1) % 4);
+        self.set_block_notify(pos, id, metadata, cache);
+        Interaction::Handled
+    }
+
+    /// Interact with a note block, tuning it to the next note and playing it.
+    fn interact_note_block(&mut self, pos: IVec3, cache: &mut ChunkCache) -> Interaction {
+        if let Some(mut note_block) = StdbNoteBlockBlockEntity::find(pos) {
+            note_block.note = (note_block.note + 1) % NOTES_COUNT;
+            self.play_note_block(pos, note_block.note, cache);
+            note_block.set();
+        }
+        Interaction::Handled
+    }
 
     // fn interact_redstone_ore(&mut self, pos: IVec3) -> bool {
     //     self.set_block_notify(pos, block::REDSTONE_ORE_LIT, 0);
     //     false  // Notchian client lit the ore but do not mark the interaction.
     // }
 
-    // fn interact_chest(&mut self, pos: IVec3) -> Interaction {
-    //
-    //     let Some(BlockEntity::Chest(_)) = self.get_block_entity(pos) else {
-    //         return Interaction::Handled
-    //     };
-    //
-    //     if self.is_block_opaque_cube(pos + IVec3::Y) {
-    //         return Interaction::Handled;
-    //     }
-    //
-    //     for face in Face::HORIZONTAL {
-    //         let face_pos = pos + face.delta();
-    //         if self.is_block(face_pos, block::CHEST) && self.is_block_opaque_cube(face_pos + IVec3::Y) {
-    //             return Interaction::Handled;
-    //         }
-    //     }
-    //
-    //     let mut all_pos = vec![pos];
-    //
-    //     // NOTE: Same order as Notchian server for parity, we also insert first or last
-    //     // depending on the neighbor chest being on neg or pos face, like Notchian client.
-    //     for face in [Face::NegX, Face::PosX, Face::NegZ, Face::PosZ] {
-    //         let face_pos = pos + face.delta();
-    //         if let Some(BlockEntity::Chest(_)) = self.get_block_entity(face_pos) {
-    //             if face.is_neg() {
-    //                 all_pos.insert(0, face_pos);
-    //             } else {
-    //                 all_pos.push(face_pos);
-    //             }
-    //         }
-    //     }
-    //
-    //     Interaction::Chest { pos: all_pos }
-    //
-    // }
+    fn interact_chest(&mut self, pos: IVec3, cache: &mut ChunkCache) -> Interaction {
 
-    // fn interact_furnace(&mut self, pos: IVec3) -> Interaction {
-    //     if let Some(BlockEntity::Furnace(_)) = self.get_block_entity(pos) {
-    //         Interaction::Furnace { pos }
-    //     } else {
-    //         Interaction::None
-    //     }
-    // }
+        if StdbChestBlockEntity::find(pos).is_none() {
+            return Interaction::Handled
+        }
 
-    // fn interact_dispenser(&mut self, pos: IVec3) -> Interaction {
-    //     if let Some(BlockEntity::Dispenser(_)) = self.get_block_entity(pos) {
-    //         Interaction::Dispenser { pos }
-    //     } else {
-    //         Interaction::None
-    //     }
-    // }
+        if self.is_block_opaque_cube(pos + IVec3::Y, cache) {
+            return Interaction::Handled;
+        }
+
+        for face in Face::HORIZONTAL {
+            let face_pos = pos + face.delta();
+            if self.is_block(face_pos, block::CHEST, cache) && self.is_block_opaque_cube(face_pos + IVec3::Y, cache) {
+                return Interaction::Handled;
+            }
+        }
+
+        let mut all_pos = vec![pos];
+
+        // NOTE: Same order as Notchian server for parity, we also insert first or last
+        // depending on the neighbor chest being on neg or pos face, like Notchian client.
+        for face in [Face::NegX, Face::PosX, Face::NegZ, Face::PosZ] {
+            let face_pos = pos + face.delta();
+            if StdbChestBlockEntity::find(face_pos).is_some() {
+                if face.is_neg() {
+                    all_pos.insert(0, face_pos);
+                } else {
+                    all_pos.push(face_pos);
+                }
+            }
+        }
+
+        Interaction::Chest { pos: all_pos }
+
+    }
+
+    fn interact_furnace(&mut self, pos: IVec3) -> Interaction {
+        if StdbFurnaceBlockEntity::find(pos).is_some() {
+            Interaction::Furnace { pos }
+        } else {
+            Interaction::None
+        }
+    }
+
+    fn interact_dispenser(&mut self, pos: IVec3) -> Interaction {
+        if StdbDispenserBlockEntity::find(pos).is_some() {
+            Interaction::Dispenser { pos }
+        } else {
+            Interaction::None
+        }
+    }
+
+    /// Interact with a bed, the interaction is forwarded to the head piece of the bed.
+    /// Beds cannot be used in the nether, where they explode instead.
+    ///
+    /// REF: BlockBed::blockActivated
+    fn interact_bed(&mut self, mut pos: IVec3, metadata: u8, cache: &mut ChunkCache) -> Interaction {
+
+        let face = block::bed::get_face(metadata);
+
+        if !block::bed::is_head(metadata) {
+            pos += face.delta();
+            if !self.is_block(pos, block::BED, cache) {
+                return Interaction::Handled;
+            }
+        }
+
+        if self.dimension_id == DIMENSION_NETHER {
+            self.set_block_notify(pos, block::AIR, 0, cache);
+            if self.is_block(pos - face.delta(), block::BED, cache) {
+                self.set_block_notify(pos - face.delta(), block::AIR, 0, cache);
+            }
+            self.explode(pos.as_dvec3() + 0.5, 5.0, true, None, cache);
+            return Interaction::Handled;
+        }
+
+        Interaction::Bed { pos }
+
+    }
 
 }
 
@@ -193,6 +242,12 @@ pub enum Interaction {
         /// exists.
         pos: IVec3,
     },
+    /// A bed has been interacted, the front-end should interpret this and try to make
+    /// the player sleep in it.
+    Bed {
+        /// Position of the head piece of the bed.
+        pos: IVec3,
+    },
 }
 
 impl From<bool> for Interaction {
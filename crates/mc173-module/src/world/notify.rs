@@ -7,6 +7,9 @@ use glam::IVec3;
 
 use crate::geom::{Face, FaceSet};
 use crate::block;
+use crate::block::material::Material;
+use crate::block_entity::dispenser::StdbDispenserBlockEntity;
+use crate::block_entity::note_block::StdbNoteBlockBlockEntity;
 use crate::chunk_cache::ChunkCache;
 use super::{StdbSetBlockEvent, StdbWorld};
 
@@ -32,11 +35,11 @@ impl StdbWorld {
     pub(super) fn notify_block_unchecked(&mut self, pos: IVec3, id: u8, metadata: u8, origin_id: u8, cache: &mut ChunkCache) {
         match id {
             block::REDSTONE if origin_id != block::REDSTONE => self.notify_redstone(pos, cache),
-            // block::REPEATER |
-            // block::REPEATER_LIT => self.notify_repeater(pos, id, metadata),
-            // block::REDSTONE_TORCH |
-            // block::REDSTONE_TORCH_LIT => self.notify_redstone_torch(pos, id),
-            // block::DISPENSER => self.notify_dispenser(pos, origin_id),
+            block::REPEATER |
+            block::REPEATER_LIT => self.notify_repeater(pos, id, metadata, cache),
+            block::REDSTONE_TORCH |
+            block::REDSTONE_TORCH_LIT => self.notify_redstone_torch(pos, id),
+            block::DISPENSER if is_redstone_block(origin_id) => self.notify_dispenser(pos, cache),
             block::WATER_MOVING |
             block::LAVA_MOVING => self.notify_fluid(pos, id, metadata, cache),
             block::WATER_STILL |
@@ -44,6 +47,11 @@ impl StdbWorld {
             block::TRAPDOOR => self.notify_trapdoor(pos, metadata, origin_id, cache),
             block::WOOD_DOOR |
             block::IRON_DOOR => self.notify_door(pos, id, metadata, origin_id, cache),
+            block::NOTE_BLOCK if is_redstone_block(origin_id) => self.notify_note_block(pos, cache),
+            block::PISTON |
+            block::STICKY_PISTON => self.notify_piston(pos, id, metadata, cache),
+            block::PISTON_EXT => self.notify_piston_ext(pos, metadata, cache),
+            block::TNT if is_redstone_block(origin_id) => self.notify_tnt(pos, cache),
             block::DANDELION |
             block::POPPY |
             block::SAPLING |
@@ -54,67 +62,125 @@ impl StdbWorld {
             block::BROWN_MUSHROOM => self.notify_mushroom(pos, cache),
             block::CACTUS => self.notify_cactus(pos, cache),
             block::SAND |
-            // block::GRAVEL => self.schedule_block_tick(pos, id, 3),
+            block::GRAVEL => self.schedule_block_tick(pos, id, 3),
             _ => {}
         }
     }
 
-    // pub(super) fn notify_change_unchecked(&mut self, pos: IVec3,
-    //     from_id: u8, from_metadata: u8,
-    //     to_id: u8, to_metadata: u8
-    // ) {
-    //
-    //     match from_id {
-    //         block::BUTTON => {
-    //             if let Some(face) = block::button::get_face(to_metadata) {
-    //                 self.notify_blocks_around(pos + face.delta(), block::BUTTON);
-    //             }
-    //         }
-    //         block::LEVER => {
-    //             if let Some((face, _)) = block::lever::get_face(to_metadata) {
-    //                 self.notify_blocks_around(pos + face.delta(), block::LEVER);
-    //             }
-    //         }
-    //         // Remove the chest/dispenser block entity.
-    //         block::CHEST if to_id != block::CHEST => {
-    //             self.remove_block_entity(pos);
-    //         }
-    //         block::DISPENSER if to_id != block::DISPENSER => {
-    //             self.remove_block_entity(pos);
-    //         }
-    //         // Remove the furnace block entity.
-    //         block::FURNACE |
-    //         block::FURNACE_LIT if to_id != block::FURNACE_LIT && to_id != block::FURNACE => {
-    //             self.remove_block_entity(pos);
-    //         }
-    //         block::SPAWNER if to_id != block::SPAWNER => {
-    //             self.remove_block_entity(pos);
-    //         }
-    //         block::NOTE_BLOCK if to_id != block::NOTE_BLOCK => {
-    //             self.remove_block_entity(pos);
-    //         }
-    //         block::JUKEBOX if to_id != block::JUKEBOX => {
-    //             self.remove_block_entity(pos);
-    //         }
-    //         _ => {}
-    //     }
-    //
-    //     match to_id {
-    //         block::WATER_MOVING => self.schedule_block_tick(pos, to_id, 5),
-    //         block::LAVA_MOVING => self.schedule_block_tick(pos, to_id, 30),
-    //         block::REDSTONE => self.notify_redstone(pos),
-    //         block::REPEATER |
-    //         block::REPEATER_LIT => self.notify_repeater(pos, to_id, from_metadata),
-    //         block::REDSTONE_TORCH |
-    //         block::REDSTONE_TORCH_LIT => self.notify_redstone_torch(pos, to_id),
-    //         block::SAND |
-    //         block::GRAVEL => self.schedule_block_tick(pos, to_id, 3),
-    //         block::CACTUS => self.notify_cactus(pos),
-    //         block::FIRE => self.schedule_block_tick(pos, to_id, 40),
-    //         _ => {}
-    //     }
-    //
-    // }
+    /// Notify a block change at the given position, from the previous block to the new
+    /// one. This is where block entities are removed when their block is replaced.
+    pub(super) fn notify_change_unchecked(&mut self, pos: IVec3,
+        from_id: u8, from_metadata: u8,
+        to_id: u8, to_metadata: u8,
+        cache: &mut ChunkCache,
+    ) {
+
+        match from_id {
+            block::BUTTON => {
+                if let Some(face) = block::button::get_face(to_metadata) {
+                    self.notify_blocks_around(pos + face.delta(), block::BUTTON, cache);
+                }
+            }
+            block::LEVER => {
+                if let Some((face, _)) = block::lever::get_face(to_metadata) {
+                    self.notify_blocks_around(pos + face.delta(), block::LEVER, cache);
+                }
+            }
+            // Lit torches and repeaters power through the block they are pointing to, so
+            // the neighbors of that block are notified when they are no longer lit.
+            block::REDSTONE_TORCH_LIT if to_id != block::REDSTONE_TORCH_LIT => {
+                self.notify_blocks_around(pos + IVec3::Y, block::REDSTONE_TORCH, cache);
+            }
+            block::REPEATER_LIT if to_id != block::REPEATER_LIT => {
+                let face = block::repeater::get_face(from_metadata);
+                self.notify_blocks_around(pos + face.delta(), block::REPEATER, cache);
+            }
+            // Spill the chest content and remove the chest block entity.
+            block::CHEST if to_id != block::CHEST => {
+                self.remove_block_entity_with_loot(pos);
+            }
+            // Spill the dispenser content and remove the dispenser block entity.
+            block::DISPENSER if to_id != block::DISPENSER => {
+                self.remove_block_entity_with_loot(pos);
+            }
+            // Spill the furnace content and remove the furnace block entity.
+            block::FURNACE |
+            block::FURNACE_LIT if to_id != block::FURNACE_LIT && to_id != block::FURNACE => {
+                self.remove_block_entity_with_loot(pos);
+            }
+            block::SIGN |
+            block::WALL_SIGN if to_id != block::SIGN && to_id != block::WALL_SIGN => {
+                self.remove_block_entity(pos);
+            }
+            block::SPAWNER if to_id != block::SPAWNER => {
+                self.remove_block_entity(pos);
+            }
+            block::NOTE_BLOCK if to_id != block::NOTE_BLOCK => {
+                self.remove_block_entity(pos);
+            }
+            block::PISTON_MOVING if to_id != block::PISTON_MOVING => {
+                self.remove_block_entity(pos);
+            }
+            // Removing an extended piston base also removes its head.
+            block::PISTON |
+            block::STICKY_PISTON if to_id != from_id && block::piston::is_extended(from_metadata) => {
+                if let Some(face) = block::piston::get_face(from_metadata) {
+                    if self.is_block(pos + face.delta(), block::PISTON_EXT, cache) {
+                        self.set_block_notify(pos + face.delta(), block::AIR, 0, cache);
+                    }
+                }
+            }
+            // Removing a piston head breaks its extended base.
+            block::PISTON_EXT if to_id != block::PISTON_EXT => {
+                if let Some(face) = block::piston::get_face(from_metadata) {
+                    if let Some((block::PISTON | block::STICKY_PISTON, base_metadata)) = self.get_block(pos - face.delta(), cache) {
+                        if block::piston::is_extended(base_metadata) {
+                            self.break_block(pos - face.delta(), cache);
+                        }
+                    }
+                }
+            }
+            // block::JUKEBOX if to_id != block::JUKEBOX => {
+            //     self.remove_block_entity(pos);
+            // }
+            _ => {}
+        }
+
+        match to_id {
+            block::WATER_MOVING => self.schedule_block_tick(pos, to_id, 5),
+            block::LAVA_MOVING => {
+                // Lava may harden as soon as it's placed next to water.
+                self.notify_fluid(pos, to_id, to_metadata, cache);
+                self.schedule_block_tick(pos, to_id, 30);
+            }
+            block::REDSTONE => self.notify_redstone(pos, cache),
+            block::REPEATER => self.notify_repeater(pos, to_id, to_metadata, cache),
+            block::REPEATER_LIT => {
+                if from_id != block::REPEATER_LIT {
+                    let face = block::repeater::get_face(to_metadata);
+                    self.notify_blocks_around(pos + face.delta(), block::REPEATER_LIT, cache);
+                }
+                self.notify_repeater(pos, to_id, to_metadata, cache);
+            }
+            block::REDSTONE_TORCH => self.notify_redstone_torch(pos, to_id),
+            block::REDSTONE_TORCH_LIT => {
+                if from_id != block::REDSTONE_TORCH_LIT {
+                    self.notify_blocks_around(pos + IVec3::Y, block::REDSTONE_TORCH_LIT, cache);
+                }
+                self.notify_redstone_torch(pos, to_id);
+            }
+            block::PISTON |
+            block::STICKY_PISTON => self.notify_piston(pos, to_id, to_metadata, cache),
+            // TNT placed next to a power source is directly ignited.
+            block::TNT => self.notify_tnt(pos, cache),
+            block::SAND |
+            block::GRAVEL => self.schedule_block_tick(pos, to_id, 3),
+            // block::CACTUS => self.notify_cactus(pos),
+            // block::FIRE => self.schedule_block_tick(pos, to_id, 40),
+            _ => {}
+        }
+
+    }
 
     /// Notification of a moving fluid block.
     fn notify_fluid(&mut self, pos: IVec3, id: u8, metadata: u8, cache: &mut ChunkCache) {
@@ -141,7 +207,11 @@ impl StdbWorld {
         let moving_id = id - 1;
 
         self.notify_fluid(pos, moving_id, metadata, cache);
-        self.set_block_self_notify(pos, moving_id, metadata, cache);
+
+        // Lava may have hardened, in such case it's no longer a fluid.
+        if self.is_block(pos, id, cache) {
+            self.set_block_self_notify(pos, moving_id, metadata, cache);
+        }
 
     }
 
@@ -176,33 +246,94 @@ impl StdbWorld {
         }
     }
 
-    // /// Notification of a redstone repeater block.
-    // fn notify_repeater(&mut self, pos: IVec3, id: u8, metadata: u8) {
-    //
-    //     let lit = id == block::REPEATER_LIT;
-    //     let face = block::repeater::get_face(metadata);
-    //     let delay = block::repeater::get_delay_ticks(metadata);
-    //     let back_powered = self.has_passive_power_from(pos - face.delta(), face);
-    //
-    //     if lit != back_powered {
-    //         self.schedule_block_tick(pos, id, delay);
-    //     }
-    //
-    // }
-
-    // /// Notification of a redstone repeater block.
-    // fn notify_redstone_torch(&mut self, pos: IVec3, id: u8) {
-    //     self.schedule_block_tick(pos, id, 2);
-    // }
-
-    // fn notify_dispenser(&mut self, pos: IVec3, origin_id: u8) {
-    //     if is_redstone_block(origin_id) {
-    //         // TODO: Also check above? See associated tick function.
-    //         if self.has_passive_power(pos) {
-    //             self.schedule_block_tick(pos, block::DISPENSER, 4);
-    //         }
-    //     }
-    // }
+    /// Notification of a redstone repeater block, its state is changed after the delay
+    /// given by its metadata if its back power no longer match its state.
+    fn notify_repeater(&mut self, pos: IVec3, id: u8, metadata: u8, cache: &mut ChunkCache) {
+
+        let lit = id == block::REPEATER_LIT;
+        let face = block::repeater::get_face(metadata);
+        let delay = block::repeater::get_delay_ticks(metadata);
+        let back_powered = self.has_passive_power_from(pos - face.delta(), face, cache);
+
+        if lit != back_powered {
+            self.schedule_block_tick(pos, id, delay);
+        }
+
+    }
+
+    /// Notification of a redstone torch block, its power is checked on a later tick.
+    fn notify_redstone_torch(&mut self, pos: IVec3, id: u8) {
+        self.schedule_block_tick(pos, id, 2);
+    }
+
+    /// Notification of a note block, the note is played when the block becomes powered.
+    fn notify_note_block(&mut self, pos: IVec3, cache: &mut ChunkCache) {
+        let Some(mut note_block) = StdbNoteBlockBlockEntity::find(pos) else { return };
+        let powered = self.has_passive_power(pos, cache);
+        if note_block.powered != powered {
+            note_block.powered = powered;
+            if powered {
+                self.play_note_block(pos, note_block.note, cache);
+            }
+            note_block.set();
+        }
+    }
+
+    /// Play the note of a note block to clients, this only works if there is air above
+    /// it. The instrument depends on the material of the block below.
+    pub(super) fn play_note_block(&mut self, pos: IVec3, note: u8, cache: &mut ChunkCache) {
+
+        if !self.is_block_air(pos + IVec3::Y, cache) {
+            return;
+        }
+
+        let instrument = match self.get_block_material(pos - IVec3::Y, cache) {
+            Material::Rock => 1,
+            Material::Sand => 2,
+            Material::Glass => 3,
+            Material::Wood => 4,
+            _ => 0,
+        };
+
+        self.push_block_action(pos, instrument, note as i8);
+
+    }
+
+    /// Notification of a piston head, removing it if its base is no longer present.
+    fn notify_piston_ext(&mut self, pos: IVec3, metadata: u8, cache: &mut ChunkCache) {
+        let Some(face) = block::piston::get_face(metadata) else { return };
+        if !matches!(self.get_block(pos - face.delta(), cache), Some((block::PISTON | block::STICKY_PISTON, _))) {
+            self.set_block_notify(pos, block::AIR, 0, cache);
+        }
+    }
+
+    /// Notification of a TNT block, igniting it if powered.
+    fn notify_tnt(&mut self, pos: IVec3, cache: &mut ChunkCache) {
+        if self.has_passive_power(pos, cache) {
+            self.ignite_tnt(pos, cache);
+        }
+    }
+
+    /// Notification of a dispenser, an item is dispensed on a later tick when the
+    /// dispenser becomes powered.
+    fn notify_dispenser(&mut self, pos: IVec3, cache: &mut ChunkCache) {
+        let Some(mut dispenser) = StdbDispenserBlockEntity::find(pos) else { return };
+        let powered = self.has_dispenser_power(pos, cache);
+        if dispenser.powered != powered {
+            if dispenser.set_powered(powered) {
+                self.schedule_block_tick(pos, block::DISPENSER, 4);
+            }
+            dispenser.set();
+        }
+    }
+
+    /// Return true if a dispenser at the given position is powered, the block above the
+    /// dispenser is also checked like in the Notchian impl.
+    ///
+    /// REF: BlockDispenser::onNeighborBlockChange
+    pub(super) fn has_dispenser_power(&mut self, pos: IVec3, cache: &mut ChunkCache) -> bool {
+        self.has_passive_power(pos, cache) || self.has_passive_power(pos + IVec3::Y, cache)
+    }
 
     /// Notification of a trapdoor, breaking it if no longer on its wall, or updating its 
     /// state depending on redstone signal.
@@ -0,0 +1,258 @@
+//! Piston extension and retraction in the world.
+
+use glam::IVec3;
+
+use crate::block::material::{self, PistonPolicy};
+use crate::block_entity::piston::StdbPistonBlockEntity;
+use crate::geom::Face;
+use crate::block;
+use crate::chunk_cache::ChunkCache;
+
+use super::StdbWorld;
+
+
+/// Maximum number of blocks a piston can push.
+const MAX_PUSH: usize = 12;
+
+/// Methods related to pistons.
+impl StdbWorld {
+
+    /// Notification of a piston base, extending it if powered or retracting it if no
+    /// longer powered. The piston state is changed before moving any block, so that the
+    /// notifications of moved blocks do not trigger the piston again.
+    pub(super) fn notify_piston(&mut self, pos: IVec3, id: u8, mut metadata: u8, cache: &mut ChunkCache) {
+
+        let Some(face) = block::piston::get_face(metadata) else { return };
+        let extended = block::piston::is_extended(metadata);
+        let powered = self.has_piston_power(pos, face, cache);
+
+        if powered && !extended {
+            if let Some(end_pos) = self.find_piston_push_end(pos, face, cache) {
+                block::piston::set_extended(&mut metadata, true);
+                self.set_block(pos, id, metadata, cache);
+                self.extend_piston(pos, id, face, end_pos, cache);
+                self.push_block_action(pos, 0, face as i8);
+                self.notify_blocks_around(pos, id, cache);
+            }
+        } else if !powered && extended {
+            block::piston::set_extended(&mut metadata, false);
+            self.set_block(pos, id, metadata, cache);
+            self.push_block_action(pos, 1, face as i8);
+            self.retract_piston(pos, id, face, cache);
+        }
+
+    }
+
+    /// Return true if the piston at the given position and facing is powered. Power is
+    /// checked on all faces but the front one, and also around the block above the piston.
+    fn has_piston_power(&mut self, pos: IVec3, face: Face, cache: &mut ChunkCache) -> bool {
+
+        for test_face in Face::ALL {
+            if test_face != face && self.has_passive_power_from(pos + test_face.delta(), test_face.opposite(), cache) {
+                return true;
+            }
+        }
+
+        // PARITY: Notchian server also checks the power around the block above.
+        let above_pos = pos + IVec3::Y;
+        for test_face in Face::ALL {
+            if test_face != Face::NegY && self.has_passive_power_from(above_pos + test_face.delta(), test_face.opposite(), cache) {
+                return true;
+            }
+        }
+
+        false
+
+    }
+
+    /// Find the position where the last pushed block will be moved, this is the first
+    /// air block or block to break in front of the piston. None is returned if the piston
+    /// cannot extend, because a block cannot be moved or there are too many blocks.
+    fn find_piston_push_end(&mut self, pos: IVec3, face: Face, cache: &mut ChunkCache) -> Option<IVec3> {
+        find_push_end(pos, face, |pos| self.get_block(pos, cache))
+    }
+
+    /// Extend the piston, all blocks up to the end position are moved by one block, the
+    /// block at end position is broken if not air.
+    fn extend_piston(&mut self, pos: IVec3, id: u8, face: Face, end_pos: IVec3, cache: &mut ChunkCache) {
+
+        if !self.is_block_air(end_pos, cache) {
+            self.break_block(end_pos, cache);
+        }
+
+        let mut ext_metadata = 0;
+        block::piston::set_face(&mut ext_metadata, face);
+        if id == block::STICKY_PISTON {
+            ext_metadata |= 8;
+        }
+
+        let mut moved = Vec::new();
+        let mut move_pos = end_pos;
+
+        // Move blocks starting from the end, the last moved block is the piston head.
+        while move_pos != pos {
+
+            let from_pos = move_pos - face.delta();
+            let (from_id, from_metadata) = if from_pos == pos {
+                (block::PISTON_EXT, ext_metadata)
+            } else {
+                self.get_block(from_pos, cache).unwrap()
+            };
+
+            self.set_block(move_pos, block::PISTON_MOVING, from_metadata, cache);
+            StdbPistonBlockEntity::new(self.dimension_id, move_pos, from_id, from_metadata, face, true).set();
+
+            moved.push((move_pos, from_id));
+            move_pos = from_pos;
+
+        }
+
+        for (moved_pos, moved_id) in moved {
+            self.notify_blocks_around(moved_pos, moved_id, cache);
+        }
+
+    }
+
+    /// Retract the piston, the piston head is removed and sticky pistons pull the block
+    /// in front of the head.
+    fn retract_piston(&mut self, pos: IVec3, id: u8, face: Face, cache: &mut ChunkCache) {
+
+        let head_pos = pos + face.delta();
+
+        // If the head is still moving, finish its move before retracting.
+        if let Some(moving) = StdbPistonBlockEntity::find(head_pos) {
+            moving.finish(self, cache);
+        }
+
+        let mut base_metadata = 0;
+        block::piston::set_face(&mut base_metadata, face);
+        self.set_block(pos, block::PISTON_MOVING, base_metadata, cache);
+        StdbPistonBlockEntity::new(self.dimension_id, pos, id, base_metadata, face, false).set();
+
+        if id == block::STICKY_PISTON {
+
+            let pull_pos = head_pos + face.delta();
+            if let Some((pull_id, pull_metadata)) = self.get_block(pull_pos, cache) {
+
+                // PARITY: If the pulled block is still being pushed by this piston, its
+                // move is finished and it's not pulled, like the Notchian server.
+                let mut dropped = false;
+                if pull_id == block::PISTON_MOVING {
+                    if let Some(moving) = StdbPistonBlockEntity::find(pull_pos) {
+                        if moving.get_face() == face && moving.extending {
+                            moving.finish(self, cache);
+                            dropped = true;
+                        }
+                    }
+                }
+
+                if !dropped && pull_id != block::AIR && get_piston_policy(pull_id, pull_metadata, true) == PistonPolicy::Push {
+                    self.set_block(head_pos, block::PISTON_MOVING, pull_metadata, cache);
+                    StdbPistonBlockEntity::new(self.dimension_id, head_pos, pull_id, pull_metadata, face, false).set();
+                    self.set_block_notify(pull_pos, block::AIR, 0, cache);
+                    return;
+                }
+
+            }
+
+        }
+
+        self.set_block_notify(head_pos, block::AIR, 0, cache);
+
+    }
+
+}
+
+
+/// Internal function to find the end of a piston push from the piston position and its
+/// facing, blocks are queried with the given function, see
+/// [`StdbWorld::find_piston_push_end`].
+fn find_push_end(pos: IVec3, face: Face, mut get_block: impl FnMut(IVec3) -> Option<(u8, u8)>) -> Option<IVec3> {
+
+    let mut end_pos = pos + face.delta();
+
+    for count in 0..=MAX_PUSH {
+
+        if end_pos.y <= 0 || end_pos.y >= 127 {
+            return None;
+        }
+
+        let (id, metadata) = get_block(end_pos)?;
+        if id == block::AIR {
+            return Some(end_pos);
+        }
+
+        match get_piston_policy(id, metadata, false) {
+            PistonPolicy::Push if count < MAX_PUSH => end_pos += face.delta(),
+            PistonPolicy::Break => return Some(end_pos),
+            _ => return None,
+        }
+
+    }
+
+    None
+
+}
+
+/// Get the piston policy of a block, also checking that piston bases are not extended.
+/// When pulling a block, only blocks that can be pushed are pulled.
+fn get_piston_policy(id: u8, metadata: u8, pull: bool) -> PistonPolicy {
+    match id {
+        block::PISTON |
+        block::STICKY_PISTON if block::piston::is_extended(metadata) => PistonPolicy::Stop,
+        _ => match material::get_piston_policy(id) {
+            PistonPolicy::Break if pull => PistonPolicy::Stop,
+            policy => policy,
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    /// Find the push end of a piston at the origin pushing toward positive X, with the
+    /// given blocks in front of it and air after them.
+    fn push_end(blocks: &[u8]) -> Option<IVec3> {
+        let pos = IVec3::new(0, 64, 0);
+        find_push_end(pos, Face::PosX, |block_pos| {
+            let index = (block_pos.x - 1) as usize;
+            Some((blocks.get(index).copied().unwrap_or(block::AIR), 0))
+        })
+    }
+
+    #[test]
+    fn push_limit() {
+
+        assert_eq!(push_end(&[]), Some(IVec3::new(1, 64, 0)));
+        assert_eq!(push_end(&[block::STONE]), Some(IVec3::new(2, 64, 0)));
+
+        // A piston can push up to 12 blocks.
+        assert_eq!(push_end(&[block::STONE; 12]), Some(IVec3::new(13, 64, 0)));
+        assert_eq!(push_end(&[block::STONE; 13]), None);
+
+        // Blocks that break don't count in the limit.
+        let mut blocks = [block::STONE; 13];
+        blocks[12] = block::TORCH;
+        assert_eq!(push_end(&blocks), Some(IVec3::new(13, 64, 0)));
+
+    }
+
+    #[test]
+    fn push_stop() {
+        assert_eq!(push_end(&[block::OBSIDIAN]), None);
+        assert_eq!(push_end(&[block::STONE, block::STONE, block::CHEST]), None);
+        assert_eq!(push_end(&[block::STONE, block::TORCH, block::OBSIDIAN]), Some(IVec3::new(2, 64, 0)));
+    }
+
+    #[test]
+    fn push_height() {
+        let end = find_push_end(IVec3::new(0, 125, 0), Face::PosY, |_| Some((block::STONE, 0)));
+        assert_eq!(end, None);
+        let end = find_push_end(IVec3::new(0, 125, 0), Face::PosY, |_| Some((block::AIR, 0)));
+        assert_eq!(end, Some(IVec3::new(0, 126, 0)));
+    }
+
+}
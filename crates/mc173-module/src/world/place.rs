@@ -3,6 +3,12 @@
 use glam::IVec3;
 
 // use crate::block_entity::BlockEntity;
+use crate::block_entity::chest::StdbChestBlockEntity;
+use crate::block_entity::dispenser::StdbDispenserBlockEntity;
+use crate::block_entity::furnace::StdbFurnaceBlockEntity;
+use crate::block_entity::sign::StdbSignBlockEntity;
+use crate::block_entity::note_block::StdbNoteBlockBlockEntity;
+use crate::block_entity::spawner::StdbSpawnerBlockEntity;
 use crate::block::material::Material;
 use crate::util::default as def;
 use crate::geom::Face;
@@ -149,7 +155,8 @@ impl StdbWorld {
         match id {
             block::BUTTON => self.place_faced(pos, face, id, metadata, block::button::set_face, cache),
             block::TRAPDOOR => self.place_faced(pos, face, id, metadata, block::trapdoor::set_face, cache),
-            block::PISTON => self.place_faced(pos, face, id, metadata, block::piston::set_face, cache),
+            block::PISTON |
+            block::STICKY_PISTON => self.place_faced(pos, face, id, metadata, block::piston::set_face, cache),
             block::WOOD_STAIR | 
             block::COBBLESTONE_STAIR => self.place_faced(pos, face, id, metadata, block::stair::set_face, cache),
             block::REPEATER | 
@@ -169,15 +176,17 @@ impl StdbWorld {
             }
         }
 
-        // match id {
-        //     block::CHEST => self.set_block_entity(pos, BlockEntity::Chest(def())),
-        //     block::FURNACE => self.set_block_entity(pos, BlockEntity::Furnace(def())),
-        //     block::DISPENSER => self.set_block_entity(pos, BlockEntity::Dispenser(def())),
-        //     block::SPAWNER => self.set_block_entity(pos, BlockEntity::Spawner(def())),
-        //     block::NOTE_BLOCK => self.set_block_entity(pos, BlockEntity::NoteBlock(def())),
-        //     block::JUKEBOX => self.set_block_entity(pos, BlockEntity::Jukebox(def())),
-        //     _ => {}
-        // }
+        match id {
+            block::CHEST => StdbChestBlockEntity::new(pos).set(),
+            block::FURNACE => StdbFurnaceBlockEntity::new(self.dimension_id, pos).set(),
+            block::SIGN |
+            block::WALL_SIGN => StdbSignBlockEntity::new(pos, None).set(),
+            block::DISPENSER => StdbDispenserBlockEntity::new(pos).set(),
+            block::SPAWNER => StdbSpawnerBlockEntity::new(self.dimension_id, pos).set(),
+            block::NOTE_BLOCK => StdbNoteBlockBlockEntity::new(pos).set(),
+            // block::JUKEBOX => self.set_block_entity(pos, BlockEntity::Jukebox(def())),
+            _ => {}
+        }
 
     }
 
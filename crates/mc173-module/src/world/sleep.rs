@@ -0,0 +1,339 @@
+//! Sleeping of players in beds and night skipping.
+
+use glam::{DVec3, IVec3};
+
+use crate::entity::{BaseKind, Entity, LivingKind};
+use crate::chunk_cache::ChunkCache;
+use crate::stdb::weather::StdbWeather;
+use crate::block;
+
+use super::{StdbWorld, Weather};
+
+
+/// Number of ticks a player must have slept to be fully asleep.
+const FULLY_ASLEEP_TIME: u8 = 100;
+
+
+/// Methods related to players sleeping in beds.
+impl StdbWorld {
+
+    /// Return true if it's currently day time in this world, players cannot sleep
+    /// during the day.
+    ///
+    /// REF: World::isDaytime
+    pub fn is_daytime(&self) -> bool {
+        self.sky_light_subtracted < 4
+    }
+
+    /// Try to make the given player entity sleep in the bed at the given position,
+    /// which should be the head piece of the bed.
+    ///
+    /// REF: EntityPlayer::sleepInBedAt
+    pub fn sleep_player(&mut self, id: u32, pos: IVec3, cache: &mut ChunkCache) -> SleepResult {
+
+        let Some((block::BED, mut metadata)) = self.get_block(pos, cache) else {
+            return SleepResult::OtherProblem;
+        };
+
+        // If the bed is marked occupied but no player is actually sleeping in it, the
+        // bed is freed.
+        if block::bed::is_occupied(metadata) {
+            let occupied = self.iter_player_entities().any(|(_, entity)| {
+                matches!(entity, Entity(_, BaseKind::Living(_, LivingKind::Human(human)))
+                    if human.sleeping && human.bed_pos == Some(pos))
+            });
+            if occupied {
+                return SleepResult::Occupied;
+            }
+            block::bed::set_occupied(&mut metadata, false);
+            self.set_block_notify(pos, block::BED, metadata, cache);
+        }
+
+        let Some(mut entity) = self.get_entity(id) else {
+            return SleepResult::OtherProblem;
+        };
+
+        let Entity(base, BaseKind::Living(living, LivingKind::Human(human))) = &mut entity else {
+            return SleepResult::OtherProblem;
+        };
+
+        if human.sleeping || living.health == 0 {
+            return SleepResult::OtherProblem;
+        } else if self.is_daytime() {
+            return SleepResult::NotPossibleNow;
+        }
+
+        let delta = (base.pos - pos.as_dvec3()).abs();
+        if delta.x > 3.0 || delta.y > 2.0 || delta.z > 3.0 {
+            return SleepResult::TooFarAway;
+        }
+
+        human.sleeping = true;
+        human.sleep_time = 0;
+        human.bed_pos = Some(pos);
+        base.vel = DVec3::ZERO;
+
+        // The player is laid toward the head of the bed.
+        let face_delta = block::bed::get_face(metadata).delta().as_dvec3();
+        let sleep_pos = pos.as_dvec3() + DVec3::new(0.5 + face_delta.x * 0.4, 0.9375, 0.5 + face_delta.z * 0.4);
+
+        entity.resize();
+        entity.teleport(sleep_pos);
+        self.set_entity(id, &entity);
+
+        block::bed::set_occupied(&mut metadata, true);
+        self.set_block_notify(pos, block::BED, metadata, cache);
+
+        SleepResult::Ok
+
+    }
+
+    /// Wake up the given player entity if it is sleeping, returning true if it was.
+    pub fn wake_up_player(&mut self, id: u32, cache: &mut ChunkCache) -> bool {
+        let Some(mut entity) = self.get_entity(id) else { return false };
+        if self.wake_up_entity(&mut entity, cache) {
+            self.set_entity(id, &entity);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Internal function to wake up a player entity, its bed is freed and the player is
+    /// moved next to it.
+    ///
+    /// REF: EntityPlayer::wakeUpPlayer
+    fn wake_up_entity(&mut self, entity: &mut Entity, cache: &mut ChunkCache) -> bool {
+
+        let Entity(base, BaseKind::Living(_, LivingKind::Human(human))) = entity else {
+            return false;
+        };
+
+        if !human.sleeping {
+            return false;
+        }
+
+        human.sleeping = false;
+        human.sleep_time = 0;
+
+        let mut pos = base.pos;
+        if let Some(bed_pos) = human.bed_pos {
+            if let Some((block::BED, mut metadata)) = self.get_block(bed_pos, cache) {
+                block::bed::set_occupied(&mut metadata, false);
+                self.set_block_notify(bed_pos, block::BED, metadata, cache);
+                let wake_pos = self.find_bed_spawn_pos(bed_pos, cache).unwrap_or(bed_pos + IVec3::Y);
+                pos = wake_pos.as_dvec3() + DVec3::new(0.5, 0.1, 0.5);
+            }
+        }
+
+        entity.resize();
+        entity.teleport(pos);
+        true
+
+    }
+
+    /// Find a free position around the bed at the given position where a player can
+    /// stand, none if the bed is missing or obstructed.
+    ///
+    /// REF: BlockBed::getNearestEmptyChunkCoordinates
+    pub fn find_bed_spawn_pos(&self, pos: IVec3, cache: &mut ChunkCache) -> Option<IVec3> {
+        find_bed_free_pos(pos, |pos| self.get_block(pos, cache))
+    }
+
+    /// Tick sleeping players, they wake up when it's day time or when their bed has been
+    /// removed. When every player in the world is fully asleep, the time skips to the
+    /// next morning and all players are woken up.
+    ///
+    /// REF: WorldServer::tick, EntityPlayer::onUpdate
+    pub(super) fn tick_sleep(&mut self, cache: &mut ChunkCache) {
+
+        let mut all_asleep = true;
+        let mut sleeping = Vec::new();
+
+        for (id, entity) in self.iter_player_entities() {
+            match &entity {
+                Entity(_, BaseKind::Living(_, LivingKind::Human(human))) if human.sleeping => {
+                    all_asleep &= human.sleep_time >= FULLY_ASLEEP_TIME;
+                    sleeping.push((id, entity));
+                }
+                _ => all_asleep = false,
+            }
+        }
+
+        if sleeping.is_empty() {
+            return;
+        }
+
+        if all_asleep {
+
+            // Block ticks scheduled during the skipped time are all due and will be
+            // processed on the next ticks.
+            let time = self.time + 24000;
+            self.time = time - time % 24000;
+
+            // Sleeping through the night also clears the weather.
+            self.set_weather(Weather::Clear);
+            if let Some(mut weather) = StdbWeather::filter_by_dimension_id(&self.dimension_id) {
+                weather.weather_next_time = self.time + self.rand.next_int_bounded(168000) as u64 + 12000;
+                StdbWeather::update_by_dimension_id(&self.dimension_id, weather);
+            }
+
+            for (id, mut entity) in sleeping {
+                self.wake_up_entity(&mut entity, cache);
+                self.set_entity(id, &entity);
+            }
+
+            return;
+
+        }
+
+        for (id, mut entity) in sleeping {
+
+            let Entity(_, BaseKind::Living(_, LivingKind::Human(human))) = &mut entity else {
+                unreachable!()
+            };
+
+            human.sleep_time = (human.sleep_time + 1).min(FULLY_ASLEEP_TIME);
+
+            let in_bed = human.bed_pos.is_some_and(|bed_pos| self.is_block(bed_pos, block::BED, cache));
+            if !in_bed || self.is_daytime() {
+                self.wake_up_entity(&mut entity, cache);
+            }
+
+            self.set_entity(id, &entity);
+
+        }
+
+    }
+
+}
+
+
+/// Internal function to find a free position around the bed at the given position,
+/// blocks are queried with the given function, see [`StdbWorld::find_bed_spawn_pos`].
+fn find_bed_free_pos(pos: IVec3, mut get_block: impl FnMut(IVec3) -> Option<(u8, u8)>) -> Option<IVec3> {
+
+    let Some((block::BED, metadata)) = get_block(pos) else {
+        return None;
+    };
+
+    let face_delta = block::bed::get_face(metadata).delta();
+
+    // Search around the head, and then around the foot of the bed.
+    for k in 0..=1 {
+        let min = pos - face_delta * k - IVec3::new(1, 0, 1);
+        for x in min.x..=min.x + 2 {
+            for z in min.z..=min.z + 2 {
+                let check_pos = IVec3::new(x, pos.y, z);
+                if get_block(check_pos - IVec3::Y).is_some_and(|(id, _)| block::material::is_opaque_cube(id))
+                && get_block(check_pos).is_none_or(|(id, _)| id == block::AIR)
+                && get_block(check_pos + IVec3::Y).is_none_or(|(id, _)| id == block::AIR) {
+                    return Some(check_pos);
+                }
+            }
+        }
+    }
+
+    None
+
+}
+
+
+/// The result of a player trying to sleep in a bed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SleepResult {
+    /// The player is now sleeping.
+    Ok,
+    /// Another player is already sleeping in this bed.
+    Occupied,
+    /// The player can only sleep at night.
+    NotPossibleNow,
+    /// The player is too far away from the bed.
+    TooFarAway,
+    /// The player cannot sleep for another reason, like being already asleep.
+    OtherProblem,
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::HashMap;
+
+    use crate::geom::Face;
+
+    use super::*;
+
+    /// Blocks of a test area with a stone floor below Y 64, and a bed with its head at
+    /// the given position, other blocks are air.
+    struct Area {
+        floor: bool,
+        blocks: HashMap<IVec3, (u8, u8)>,
+    }
+
+    impl Area {
+
+        fn new(floor: bool, head_pos: IVec3, face: Face) -> Self {
+            let mut metadata = 0;
+            block::bed::set_face(&mut metadata, face);
+            let mut blocks = HashMap::new();
+            blocks.insert(head_pos - face.delta(), (block::BED, metadata));
+            block::bed::set_head(&mut metadata, true);
+            blocks.insert(head_pos, (block::BED, metadata));
+            Self { floor, blocks }
+        }
+
+        fn get_block(&self, pos: IVec3) -> Option<(u8, u8)> {
+            match self.blocks.get(&pos) {
+                Some(&block) => Some(block),
+                None if self.floor && pos.y < 64 => Some((block::STONE, 0)),
+                None => Some((block::AIR, 0)),
+            }
+        }
+
+    }
+
+    #[test]
+    fn bed_spawn_pos() {
+
+        let head_pos = IVec3::new(8, 64, 8);
+
+        // The first free position is searched around the head.
+        let area = Area::new(true, head_pos, Face::PosZ);
+        assert_eq!(find_bed_free_pos(head_pos, |pos| area.get_block(pos)), Some(IVec3::new(7, 64, 7)));
+
+        // No bed at this position.
+        assert_eq!(find_bed_free_pos(head_pos + IVec3::X, |pos| area.get_block(pos)), None);
+
+        // No floor to stand on.
+        let area = Area::new(false, head_pos, Face::PosZ);
+        assert_eq!(find_bed_free_pos(head_pos, |pos| area.get_block(pos)), None);
+
+    }
+
+    #[test]
+    fn bed_spawn_pos_obstructed() {
+
+        let head_pos = IVec3::new(8, 64, 8);
+        let mut area = Area::new(true, head_pos, Face::PosZ);
+
+        // Fill around the head, the search continues around the foot.
+        for x in 7..=9 {
+            for z in 7..=9 {
+                area.blocks.entry(IVec3::new(x, 64, z)).or_insert((block::STONE, 0));
+            }
+        }
+        assert_eq!(find_bed_free_pos(head_pos, |pos| area.get_block(pos)), Some(IVec3::new(7, 64, 6)));
+
+        // Blocks above the free positions also obstruct them.
+        for x in 7..=9 {
+            for z in 6..=8 {
+                area.blocks.entry(IVec3::new(x, 65, z)).or_insert((block::STONE, 0));
+            }
+        }
+        assert_eq!(find_bed_free_pos(head_pos, |pos| area.get_block(pos)), None);
+
+    }
+
+}
@@ -1,9 +1,10 @@
 //! Item use in the world.
 
-use glam::{IVec3, DVec3, Vec3};
+use glam::{DVec3, IVec3, Vec2, Vec3};
 
 use crate::entity::{Arrow, Entity, Snowball, Tnt, Bobber, BaseKind, ProjectileKind, Item};
 use crate::inventory::InventoryHandle;
+use crate::block_entity::sign::StdbSignBlockEntity;
 use crate::gen::tree::TreeGenerator;
 use crate::block::sapling::TreeKind;
 use crate::item::{ItemStack, self};
@@ -19,8 +20,11 @@ impl StdbWorld {
 
     /// Use an item stack on a given block, this is basically the action of left click. 
     /// This function returns the item stack after, if used, this may return an item stack
-    /// with size of 0. The face is where the click has hit on the target block.
-    pub fn use_stack(&mut self, inv: &mut InventoryHandle, index: usize, pos: IVec3, face: Face, entity_id: u32, cache: &mut ChunkCache) {
+    /// with size of 0. The face is where the click has hit on the target block, the look
+    /// is the one of the entity using the stack (in radians) and is used to orient the
+    /// placed blocks.
+    #[allow(clippy::too_many_arguments)]
+    pub fn use_stack(&mut self, inv: &mut InventoryHandle, index: usize, pos: IVec3, face: Face, entity_id: u32, look: Vec2, cache: &mut ChunkCache) {
 
         let stack = inv.get(index);
         if stack.is_empty() {
@@ -29,14 +33,15 @@ impl StdbWorld {
         
         let success = match stack.id {
             0 => false,
-            1..=255 => self.use_block_stack(stack.id as u8, stack.damage as u8, pos, face, entity_id, cache),
-            item::SUGAR_CANES => self.use_block_stack(block::SUGAR_CANES, 0, pos, face, entity_id, cache),
-            item::CAKE => self.use_block_stack(block::CAKE, 0, pos, face, entity_id, cache),
-            item::REPEATER => self.use_block_stack(block::REPEATER, 0, pos, face, entity_id, cache),
-            item::REDSTONE => self.use_block_stack(block::REDSTONE, 0, pos, face, entity_id, cache),
-            item::WOOD_DOOR => self.use_door_stack(block::WOOD_DOOR, pos, face, entity_id, cache),
-            item::IRON_DOOR => self.use_door_stack(block::IRON_DOOR, pos, face, entity_id, cache),
-            item::BED => self.use_bed_stack(pos, face, entity_id, cache),
+            1..=255 => self.use_block_stack(stack.id as u8, stack.damage as u8, pos, face, look, cache),
+            item::SUGAR_CANES => self.use_block_stack(block::SUGAR_CANES, 0, pos, face, look, cache),
+            item::CAKE => self.use_block_stack(block::CAKE, 0, pos, face, look, cache),
+            item::REPEATER => self.use_block_stack(block::REPEATER, 0, pos, face, look, cache),
+            item::REDSTONE => self.use_block_stack(block::REDSTONE, 0, pos, face, look, cache),
+            item::WOOD_DOOR => self.use_door_stack(block::WOOD_DOOR, pos, face, look, cache),
+            item::IRON_DOOR => self.use_door_stack(block::IRON_DOOR, pos, face, look, cache),
+            item::BED => self.use_bed_stack(pos, face, look, cache),
+            item::SIGN => self.use_sign_stack(pos, face, entity_id, look, cache),
             item::DIAMOND_HOE |
             item::IRON_HOE |
             item::STONE_HOE |
@@ -54,32 +59,39 @@ impl StdbWorld {
 
     }
 
-    // /// Use an item that is not meant to be used on blocks. Such as buckets, boats, bows or
-    // /// food items...
-    // pub fn use_raw_stack(&mut self, inv: &mut InventoryHandle, index: usize, entity_id: u32) {
-    //
-    //     let stack = inv.get(index);
-    //     if stack.is_empty() {
-    //         return;
-    //     }
-    //
-    //     match stack.id {
-    //         item::BUCKET |
-    //         item::WATER_BUCKET |
-    //         item::LAVA_BUCKET => self.use_bucket_stack(inv, index, entity_id),
-    //         item::BOW => self.use_bow_stack(inv, index, entity_id),
-    //         item::SNOWBALL => self.use_snowball_stack(inv, index, entity_id),
-    //         item::FISHING_ROD => self.use_fishing_rod_stack(inv, index, entity_id),
-    //         _ => ()
-    //     }
-    //
-    // }
+    /// Use an item that is not meant to be used on blocks. Such as buckets, boats, bows or
+    /// food items...
+    pub fn use_raw_stack(&mut self, inv: &mut InventoryHandle, index: usize, entity_id: u32, cache: &mut ChunkCache) {
+
+        let stack = inv.get(index);
+        if stack.is_empty() {
+            return;
+        }
+
+        match stack.id {
+            item::BUCKET |
+            item::WATER_BUCKET |
+            item::LAVA_BUCKET => self.use_bucket_stack(inv, index, entity_id, cache),
+            item::BOW => self.use_bow_stack(inv, index, entity_id),
+            item::SNOWBALL => self.use_snowball_stack(inv, index, entity_id),
+            item::FISHING_ROD => self.use_fishing_rod_stack(inv, index, entity_id),
+            item::APPLE => self.use_food_stack(inv, index, entity_id, 4),
+            item::MUSHROOM_STEW => self.use_food_stack(inv, index, entity_id, 10),
+            item::BREAD => self.use_food_stack(inv, index, entity_id, 5),
+            item::RAW_PORKCHOP => self.use_food_stack(inv, index, entity_id, 3),
+            item::COOKED_PORKCHOP => self.use_food_stack(inv, index, entity_id, 8),
+            item::GOLD_APPLE => self.use_food_stack(inv, index, entity_id, 42),
+            item::RAW_FISH => self.use_food_stack(inv, index, entity_id, 2),
+            item::COOKED_FISH => self.use_food_stack(inv, index, entity_id, 5),
+            item::COOKIE => self.use_food_stack(inv, index, entity_id, 1),
+            _ => ()
+        }
+
+    }
 
     /// Place a block toward the given face. This is used for single blocks, multi blocks
     /// are handled apart by other functions that do not rely on the block placing logic.
-    fn use_block_stack(&mut self, id: u8, metadata: u8, mut pos: IVec3, mut face: Face, entity_id: u32, cache: &mut ChunkCache) -> bool {
-
-        // let look = self.get_entity(entity_id).unwrap().0.look;
+    fn use_block_stack(&mut self, id: u8, metadata: u8, mut pos: IVec3, mut face: Face, look: Vec2, cache: &mut ChunkCache) -> bool {
 
         if let Some((block::SNOW, _)) = self.get_block(pos, cache) {
             // If a block is placed by clicking on a snow block, replace that snow block.
@@ -95,18 +107,15 @@ impl StdbWorld {
         match id {
             block::WOOD_STAIR | block::COBBLESTONE_STAIR |
             block::REPEATER | block::REPEATER_LIT => {
-                // face = Face::from_yaw(look.x);
-                face = Face::NegY;
+                face = Face::from_yaw(look.x);
             }
             block::DISPENSER |
             block::FURNACE | block::FURNACE_LIT |
             block::PUMPKIN | block::PUMPKIN_LIT => {
-                // face = Face::from_yaw(look.x).opposite();
-                face = Face::NegY;
+                face = Face::from_yaw(look.x).opposite();
             }
-            block::PISTON => {
-                // face = Face::from_look(look.x, look.y).opposite();
-                face = Face::NegY;
+            block::PISTON | block::STICKY_PISTON => {
+                face = Face::from_look(look.x, look.y).opposite();
             }
             _ => {}
         }
@@ -123,7 +132,7 @@ impl StdbWorld {
     }
 
     /// Place a door item at given position.
-    fn use_door_stack(&mut self, block_id: u8, mut pos: IVec3, face: Face, entity_id: u32, cache: &mut ChunkCache) -> bool {
+    fn use_door_stack(&mut self, block_id: u8, mut pos: IVec3, face: Face, look: Vec2, cache: &mut ChunkCache) -> bool {
 
         if face != Face::PosY {
             return false;
@@ -138,9 +147,7 @@ impl StdbWorld {
         }
 
         // The door face the opposite of the placer's look.
-        // let look = self.get_entity(entity_id).unwrap().0.look;
-        // let mut door_face = Face::from_yaw(look.x).opposite();
-        let mut door_face = Face::NegX;
+        let mut door_face = Face::from_yaw(look.x).opposite();
         let mut flip = false;
         
         // Here we count the block on the left and right (from the door face), this will
@@ -192,7 +199,7 @@ impl StdbWorld {
 
     }
 
-    fn use_bed_stack(&mut self, mut pos: IVec3, face: Face, entity_id: u32, cache: &mut ChunkCache) -> bool {
+    fn use_bed_stack(&mut self, mut pos: IVec3, face: Face, look: Vec2, cache: &mut ChunkCache) -> bool {
 
         if face != Face::PosY {
             return false;
@@ -200,9 +207,7 @@ impl StdbWorld {
             pos += IVec3::Y;
         }
 
-        // let look = self.get_entity(entity_id).unwrap().0.look;
-        // let bed_face = Face::from_yaw(look.x);
-        let bed_face = Face::NegX;
+        let bed_face = Face::from_yaw(look.x);
         let head_pos = pos + bed_face.delta();
 
         if !matches!(self.get_block(pos, cache), Some((block::AIR, _))) {
@@ -223,6 +228,37 @@ impl StdbWorld {
 
     }
 
+    /// Place a sign item, a standing sign is placed on top of the clicked block and a wall
+    /// sign on its sides. The client opens the sign editor by itself, so the sign can
+    /// then be edited by the entity that placed it.
+    fn use_sign_stack(&mut self, mut pos: IVec3, face: Face, entity_id: u32, look: Vec2, cache: &mut ChunkCache) -> bool {
+
+        if face == Face::NegY || !self.get_block_material(pos, cache).is_solid() {
+            return false;
+        }
+
+        pos += face.delta();
+
+        let mut metadata = 0;
+        let id = if face == Face::PosY {
+            block::sign::set_yaw(&mut metadata, look.x);
+            block::SIGN
+        } else {
+            block::sign::set_wall_face(&mut metadata, face.opposite());
+            block::WALL_SIGN
+        };
+
+        if pos.y >= 127 || !self.can_place_block(pos, face.opposite(), id, cache) {
+            return false;
+        }
+
+        self.place_block(pos, face.opposite(), id, metadata, cache);
+        // Replace the sign created when placing the block by one editable by its placer.
+        StdbSignBlockEntity::new(pos, Some(entity_id)).set();
+        true
+
+    }
+
     fn use_hoe_stack(&mut self, pos: IVec3, face: Face, cache: &mut ChunkCache) -> bool {
         
         if let Some((id, _)) = self.get_block(pos, cache) {
@@ -278,11 +314,7 @@ impl StdbWorld {
     fn use_flint_and_steel(&mut self, pos: IVec3, face: Face, cache: &mut ChunkCache) -> bool {
 
         if self.is_block(pos, block::TNT, cache) {
-            // self.spawn_entity(Tnt::new_with(|new_base, new_tnt| {
-            //     new_base.pos = pos.as_dvec3() + 0.5;
-            //     new_tnt.fuse_time = 80;
-            // }));
-            // self.set_block_notify(pos, block::AIR, 0);
+            self.ignite_tnt(pos, cache);
         } else {
             let fire_pos = pos + face.delta();
             if self.is_block_air(fire_pos, cache) {
@@ -294,238 +326,285 @@ impl StdbWorld {
 
     }
 
-    // fn use_bucket_stack(&mut self, inv: &mut InventoryHandle, index: usize, entity_id: u32) {
-    //
-    //     let stack = inv.get(index);
-    //     let fluid_id = match stack.id {
-    //         item::BUCKET => block::AIR,
-    //         item::WATER_BUCKET => block::WATER_MOVING,
-    //         item::LAVA_BUCKET => block::LAVA_MOVING,
-    //         _ => unimplemented!()
-    //     };
-    //
-    //     let entity = self.get_entity(entity_id).unwrap();
-    //
-    //     let origin = entity.0.pos + DVec3::new(0.0, 1.62, 0.0);
-    //
-    //     let yaw_dx = -entity.0.look.x.sin();
-    //     let yaw_dz = entity.0.look.x.cos();
-    //     let pitch_dy = -entity.0.look.y.sin();
-    //     let pitch_h = entity.0.look.y.cos();
-    //     let ray = Vec3::new(yaw_dx * pitch_h, pitch_dy, yaw_dz * pitch_h).as_dvec3() * 5.0;
-    //
-    //     // NOTE: We only hit fluid sources when we use an empty bucket.
-    //     let kind = if fluid_id == block::AIR {
-    //         RayTraceKind::OverlayWithFluid
-    //     } else {
-    //         RayTraceKind::Overlay
-    //     };
-    //
-    //     let Some(hit) = self.ray_trace_blocks(origin, ray, kind) else {
-    //         // We did not hit anything...
-    //         return
-    //     };
-    //
-    //     let mut new_stack;
-    //
-    //     // The bucket is empty.
-    //     if fluid_id == block::AIR {
-    //
-    //         let Some((id, metadata)) = self.get_block(hit.pos) else { return };
-    //
-    //         // Fluid must be a source.
-    //         if !block::fluid::is_source(metadata) {
-    //             return;
-    //         }
-    //
-    //         new_stack = match id {
-    //             block::WATER_MOVING | block::WATER_STILL => ItemStack::new_single(item::WATER_BUCKET, 0),
-    //             block::LAVA_MOVING | block::LAVA_STILL => ItemStack::new_single(item::LAVA_BUCKET, 0),
-    //             _ => return
-    //         };
-    //
-    //         self.set_block_notify(hit.pos, block::AIR, 0);
-    //
-    //     } else {
-    //
-    //         let pos = hit.pos + hit.face.delta();
-    //         let Some((id, _)) = self.get_block(pos) else { return };
-    //
-    //         if id == block::AIR || !block::material::get_material(id).is_solid() {
-    //             self.set_block_notify(pos, fluid_id, 0);
-    //             // world.schedule_tick(pos, fluid_id, 5); // TODO: 30 for lava.
-    //         }
-    //
-    //         new_stack = ItemStack::new_single(item::BUCKET, 0);
-    //
-    //     }
-    //
-    //     if stack.size > 1 {
-    //         inv.push_front(&mut new_stack);
-    //         // Only if there was space in the inventory we actually remove previous one.
-    //         if new_stack.is_empty() {
-    //             inv.set(index, stack.with_size(stack.size - 1));
-    //         }
-    //     } else {
-    //         inv.set(index, new_stack);
-    //     }
-    //
-    // }
-
-    // fn use_bow_stack(&mut self, inv: &mut InventoryHandle, _index: usize, entity_id: u32) {
-    //
-    //     // Consume an arrow from the inventory.
-    //     if !inv.consume(ItemStack::new_single(item::ARROW, 0)) {
-    //         return;
-    //     }
-    //
-    //     let Entity(base, _) = self.get_entity(entity_id).unwrap();
-    //
-    //     let arrow = Arrow::new_with(|arrow_base, arrow_projectile, arrow| {
-    //
-    //         arrow_base.pos = base.pos;
-    //         arrow_base.pos.y += base.eye_height as f64;
-    //         arrow_base.look = base.look;
-    //
-    //         let (yaw_sin, yaw_cos) = arrow_base.look.x.sin_cos();
-    //         let (pitch_sin, pitch_cos) = arrow_base.look.y.sin_cos();
-    //
-    //         arrow_base.vel.x = (-yaw_sin * pitch_cos) as f64;
-    //         arrow_base.vel.z = (yaw_cos * pitch_cos) as f64;
-    //         arrow_base.vel.y = (-pitch_sin) as f64;
-    //
-    //         arrow_base.vel += arrow_base.rand.next_gaussian_vec() * 0.0075;
-    //         arrow_base.vel *= 1.5;
-    //
-    //         arrow_projectile.owner_id = Some(entity_id);
-    //         arrow.from_player = true;
-    //
-    //     });
-    //
-    //     self.spawn_entity(arrow);
-    //
-    // }
-
-    // fn use_snowball_stack(&mut self, inv: &mut InventoryHandle, index: usize, entity_id: u32) {
-    //
-    //     let stack = inv.get(index);
-    //     inv.set(index, stack.inc_damage(1));
-    //
-    //     let Entity(base, _) = self.get_entity(entity_id).unwrap();
-    //
-    //     let snowball = Snowball::new_with(|throw_base, throw_projectile, _| {
-    //
-    //         throw_base.pos = base.pos;
-    //         throw_base.pos.y += base.eye_height as f64 - 0.1;
-    //         throw_base.look = base.look;
-    //
-    //         let (yaw_sin, yaw_cos) = throw_base.look.x.sin_cos();
-    //         let (pitch_sin, pitch_cos) = throw_base.look.y.sin_cos();
-    //
-    //         // PARITY: Notchian implementation multiplies the initial velocity Y component
-    //         // by 0.4 for unknown reason, to fix the aim issue we removed this here.
-    //         throw_base.vel.x = (-yaw_sin * pitch_cos) as f64;
-    //         throw_base.vel.z = (yaw_cos * pitch_cos) as f64;
-    //         throw_base.vel.y = (-pitch_sin) as f64;
-    //
-    //         throw_base.pos.x += throw_base.vel.x * 0.16;
-    //         throw_base.pos.z += throw_base.vel.z * 0.16;
-    //
-    //         throw_base.vel += throw_base.rand.next_gaussian_vec() * 0.0075;
-    //         throw_base.vel *= 1.5;
-    //
-    //         throw_projectile.owner_id = Some(entity_id);
-    //
-    //     });
-    //
-    //     self.spawn_entity(snowball);
-    //
-    // }
-
-    // fn use_fishing_rod_stack(&mut self, inv: &mut InventoryHandle, index: usize, entity_id: u32) {
-    //
-    //     let Entity(base, _) = self.get_entity_mut(entity_id).unwrap();
-    //
-    //     // Save the pos before dropping the base reference.
-    //     let base_pos = base.pos;
-    //     let base_look = base.look;
-    //     let mut new_bobber_id = base.bobber_id;
-    //
-    //     let mut item_damage = 0;
-    //
-    //     if let Some(bobber_id) = new_bobber_id {
-    //
-    //         if let Some(Entity(bobber_base, BaseKind::Projectile(bobber_projectile, ProjectileKind::Bobber(bobber)))) = self.get_entity(bobber_id) {
-    //
-    //             let bobber_pos = bobber_base.pos;
-    //
-    //             let bobber_delta = base_pos - bobber_pos;
-    //             let bobber_dist = bobber_delta.length();
-    //             let mut bobber_accel = bobber_delta * 0.1;
-    //             bobber_accel.y += bobber_dist.sqrt() * 0.08;
-    //
-    //             if let Some(attached_id) = bobber.attached_id {
-    //                 if let Some(Entity(attached_base, _)) = self.get_entity_mut(attached_id) {
-    //                     attached_base.vel += bobber_accel;
-    //                     item_damage = 3;
-    //                 }
-    //             } else if bobber.catch_time > 0 {
-    //
-    //                 self.spawn_entity(Item::new_with(|item_base, item| {
-    //                     item_base.persistent = true;
-    //                     item_base.pos = bobber_pos;
-    //                     item_base.vel = bobber_accel;
-    //                     item.stack = ItemStack::new_single(item::RAW_FISH, 0);
-    //                 }));
-    //
-    //                 item_damage = 1;
-    //
-    //             } else if bobber_projectile.state.is_some() {
-    //                 item_damage = 2;
-    //             }
-    //
-    //         }
-    //
-    //         self.remove_entity(bobber_id, "bobber retracted");
-    //         new_bobber_id = None;
-    //
-    //     } else {
-    //
-    //         let bobber = Bobber::new_with(|throw_base, throw_projectile, _| {
-    //
-    //             throw_base.pos = base_pos;
-    //             throw_base.pos.y += 1.62 - 0.1;
-    //             throw_base.look = base_look;
-    //
-    //             let (yaw_sin, yaw_cos) = throw_base.look.x.sin_cos();
-    //             let (pitch_sin, pitch_cos) = throw_base.look.y.sin_cos();
-    //
-    //             // PARITY: Notchian implementation multiplies the initial velocity Y component
-    //             // by 0.4 for unknown reason, to fix the aim issue we removed this here.
-    //             throw_base.vel.x = (-yaw_sin * pitch_cos) as f64;
-    //             throw_base.vel.z = (yaw_cos * pitch_cos) as f64;
-    //             throw_base.vel.y = (-pitch_sin) as f64;
-    //
-    //             throw_base.pos.x += throw_base.vel.x * 0.16;
-    //             throw_base.pos.z += throw_base.vel.z * 0.16;
-    //
-    //             throw_base.vel += throw_base.rand.next_gaussian_vec() * 0.0075;
-    //             throw_base.vel *= 1.5;
-    //
-    //             throw_projectile.owner_id = Some(entity_id);
-    //
-    //         });
-    //
-    //         new_bobber_id = Some(self.spawn_entity(bobber));
-    //
-    //     }
-    //
-    //     let Entity(base, _) = self.get_entity_mut(entity_id).unwrap();
-    //     base.bobber_id = new_bobber_id;
-    //
-    //     let stack = inv.get(index);
-    //     inv.set(index, stack.inc_damage(item_damage));
-    //
-    // }
+    /// Eat a food item, the entity is healed by the given amount of health.
+    ///
+    /// REF: ItemFood::onItemRightClick
+    fn use_food_stack(&mut self, inv: &mut InventoryHandle, index: usize, entity_id: u32, heal: u16) {
+
+        /// Maximum health that can be reached by healing a player.
+        const MAX_HEALTH: u16 = 20;
+
+        let Some(mut entity) = self.get_entity(entity_id) else { return };
+        let Entity(_, BaseKind::Living(living, _)) = &mut entity else { return };
+
+        // PARITY: The Notchian impl consumes the food even when dead, but dead entities
+        // cannot be healed.
+        if living.health == 0 {
+            return;
+        }
+
+        living.health = living.health.saturating_add(heal).min(MAX_HEALTH);
+        self.set_entity(entity_id, &entity);
+
+        let stack = inv.get(index);
+        if stack.id == item::MUSHROOM_STEW {
+            inv.set(index, ItemStack::new_single(item::BOWL, 0));
+        } else if stack.size > 1 {
+            inv.set(index, stack.with_size(stack.size - 1));
+        } else {
+            inv.set(index, ItemStack::EMPTY);
+        }
+
+    }
+
+    /// Use a bucket, either to pick up a fluid source with an empty bucket or to place
+    /// the fluid of a filled bucket.
+    ///
+    /// REF: ItemBucket::onItemRightClick
+    fn use_bucket_stack(&mut self, inv: &mut InventoryHandle, index: usize, entity_id: u32, cache: &mut ChunkCache) {
+
+        let stack = inv.get(index);
+        let fluid_id = match stack.id {
+            item::BUCKET => block::AIR,
+            item::WATER_BUCKET => block::WATER_MOVING,
+            item::LAVA_BUCKET => block::LAVA_MOVING,
+            _ => unimplemented!()
+        };
+
+        let Some(Entity(base, _)) = self.get_entity(entity_id) else { return };
+
+        let origin = base.pos + DVec3::new(0.0, 1.62, 0.0);
+
+        let yaw_dx = -base.look.x.sin();
+        let yaw_dz = base.look.x.cos();
+        let pitch_dy = -base.look.y.sin();
+        let pitch_h = base.look.y.cos();
+        let ray = Vec3::new(yaw_dx * pitch_h, pitch_dy, yaw_dz * pitch_h).as_dvec3() * 5.0;
+
+        // NOTE: We only hit fluid sources when we use an empty bucket.
+        let kind = if fluid_id == block::AIR {
+            RayTraceKind::OverlayWithFluid
+        } else {
+            RayTraceKind::Overlay
+        };
+
+        let Some(hit) = self.ray_trace_blocks(origin, ray, kind, cache) else {
+            // We did not hit anything...
+            return
+        };
+
+        let mut new_stack;
+
+        // The bucket is empty.
+        if fluid_id == block::AIR {
+
+            let Some((id, metadata)) = self.get_block(hit.pos, cache) else { return };
+
+            // Fluid must be a source.
+            if !block::fluid::is_source(metadata) {
+                return;
+            }
+
+            new_stack = match id {
+                block::WATER_MOVING | block::WATER_STILL => ItemStack::new_single(item::WATER_BUCKET, 0),
+                block::LAVA_MOVING | block::LAVA_STILL => ItemStack::new_single(item::LAVA_BUCKET, 0),
+                _ => return
+            };
+
+            self.set_block_notify(hit.pos, block::AIR, 0, cache);
+
+        } else {
+
+            let pos = hit.pos + hit.face.delta();
+            let Some((id, _)) = self.get_block(pos, cache) else { return };
+
+            if id == block::AIR || !block::material::get_material(id).is_solid() {
+                self.set_block_notify(pos, fluid_id, 0, cache);
+            }
+
+            new_stack = ItemStack::new_single(item::BUCKET, 0);
+
+        }
+
+        if stack.size > 1 {
+            inv.push_front(&mut new_stack);
+            // Only if there was space in the inventory we actually remove previous one.
+            if new_stack.is_empty() {
+                inv.set(index, stack.with_size(stack.size - 1));
+            }
+        } else {
+            inv.set(index, new_stack);
+        }
+
+    }
+
+    /// Shoot an arrow from the entity's eyes, an arrow is consumed from the inventory.
+    ///
+    /// REF: ItemBow::onItemRightClick
+    fn use_bow_stack(&mut self, inv: &mut InventoryHandle, _index: usize, entity_id: u32) {
+
+        let Some(Entity(base, _)) = self.get_entity(entity_id) else { return };
+
+        // Consume an arrow from the inventory.
+        if !inv.consume(ItemStack::new_single(item::ARROW, 0)) {
+            return;
+        }
+
+        let arrow = Arrow::new_with(|arrow_base, arrow_projectile, arrow| {
+
+            arrow_base.pos = base.pos;
+            arrow_base.pos.y += base.eye_height as f64;
+            arrow_base.look = base.look;
+
+            let (yaw_sin, yaw_cos) = arrow_base.look.x.sin_cos();
+            let (pitch_sin, pitch_cos) = arrow_base.look.y.sin_cos();
+
+            arrow_base.vel.x = (-yaw_sin * pitch_cos) as f64;
+            arrow_base.vel.z = (yaw_cos * pitch_cos) as f64;
+            arrow_base.vel.y = (-pitch_sin) as f64;
+
+            arrow_base.vel += arrow_base.rand.next_gaussian_vec() * 0.0075;
+            arrow_base.vel *= 1.5;
+
+            arrow_projectile.owner_id = Some(entity_id);
+            arrow.from_player = true;
+
+        });
+
+        self.spawn_entity(arrow);
+
+    }
+
+    /// Throw a snowball from the entity's eyes, one snowball is consumed.
+    ///
+    /// REF: ItemSnowball::onItemRightClick
+    fn use_snowball_stack(&mut self, inv: &mut InventoryHandle, index: usize, entity_id: u32) {
+
+        let Some(Entity(base, _)) = self.get_entity(entity_id) else { return };
+
+        let stack = inv.get(index);
+        inv.set(index, stack.inc_damage(1));
+
+        let snowball = Snowball::new_with(|throw_base, throw_projectile, _| {
+
+            throw_base.pos = base.pos;
+            throw_base.pos.y += base.eye_height as f64 - 0.1;
+            throw_base.look = base.look;
+
+            let (yaw_sin, yaw_cos) = throw_base.look.x.sin_cos();
+            let (pitch_sin, pitch_cos) = throw_base.look.y.sin_cos();
+
+            // PARITY: Notchian implementation multiplies the initial velocity Y component
+            // by 0.4 for unknown reason, to fix the aim issue we removed this here.
+            throw_base.vel.x = (-yaw_sin * pitch_cos) as f64;
+            throw_base.vel.z = (yaw_cos * pitch_cos) as f64;
+            throw_base.vel.y = (-pitch_sin) as f64;
+
+            throw_base.pos.x += throw_base.vel.x * 0.16;
+            throw_base.pos.z += throw_base.vel.z * 0.16;
+
+            throw_base.vel += throw_base.rand.next_gaussian_vec() * 0.0075;
+            throw_base.vel *= 1.5;
+
+            throw_projectile.owner_id = Some(entity_id);
+
+        });
+
+        self.spawn_entity(snowball);
+
+    }
+
+    /// Throw a fishing rod bobber, or retract the current one if the entity already has
+    /// one, in which case the rod is damaged depending on what the bobber caught.
+    ///
+    /// REF: ItemFishingRod::onItemRightClick
+    fn use_fishing_rod_stack(&mut self, inv: &mut InventoryHandle, index: usize, entity_id: u32) {
+
+        let Some(Entity(base, _)) = self.get_entity(entity_id) else { return };
+
+        let base_pos = base.pos;
+        let base_look = base.look;
+        let mut new_bobber_id = base.bobber_id;
+
+        let mut item_damage = 0;
+
+        if let Some(bobber_id) = new_bobber_id {
+
+            if let Some(Entity(bobber_base, BaseKind::Projectile(bobber_projectile, ProjectileKind::Bobber(bobber)))) = self.get_entity(bobber_id) {
+
+                let bobber_pos = bobber_base.pos;
+
+                let bobber_delta = base_pos - bobber_pos;
+                let bobber_dist = bobber_delta.length();
+                let mut bobber_accel = bobber_delta * 0.1;
+                bobber_accel.y += bobber_dist.sqrt() * 0.08;
+
+                if let Some(attached_id) = bobber.attached_id {
+                    if let Some(mut attached) = self.get_entity(attached_id) {
+                        attached.0.vel += bobber_accel;
+                        self.set_entity(attached_id, &attached);
+                        item_damage = 3;
+                    }
+                } else if bobber.catch_time > 0 {
+
+                    self.spawn_entity(Item::new_with(|item_base, item| {
+                        item_base.persistent = true;
+                        item_base.pos = bobber_pos;
+                        item_base.vel = bobber_accel;
+                        item.stack = ItemStack::new_single(item::RAW_FISH, 0);
+                    }));
+
+                    item_damage = 1;
+
+                } else if bobber_projectile.state.is_some() {
+                    item_damage = 2;
+                }
+
+            }
+
+            self.remove_entity(bobber_id, "bobber retracted");
+            new_bobber_id = None;
+
+        } else {
+
+            let bobber = Bobber::new_with(|throw_base, throw_projectile, _| {
+
+                throw_base.pos = base_pos;
+                throw_base.pos.y += 1.62 - 0.1;
+                throw_base.look = base_look;
+
+                let (yaw_sin, yaw_cos) = throw_base.look.x.sin_cos();
+                let (pitch_sin, pitch_cos) = throw_base.look.y.sin_cos();
+
+                // PARITY: Notchian implementation multiplies the initial velocity Y component
+                // by 0.4 for unknown reason, to fix the aim issue we removed this here.
+                throw_base.vel.x = (-yaw_sin * pitch_cos) as f64;
+                throw_base.vel.z = (yaw_cos * pitch_cos) as f64;
+                throw_base.vel.y = (-pitch_sin) as f64;
+
+                throw_base.pos.x += throw_base.vel.x * 0.16;
+                throw_base.pos.z += throw_base.vel.z * 0.16;
+
+                throw_base.vel += throw_base.rand.next_gaussian_vec() * 0.0075;
+                throw_base.vel *= 1.5;
+
+                throw_projectile.owner_id = Some(entity_id);
+
+            });
+
+            new_bobber_id = Some(self.spawn_entity(bobber));
+
+        }
+
+        // The entity is reloaded because the bobber may have been attached to it.
+        if let Some(mut entity) = self.get_entity(entity_id) {
+            entity.0.bobber_id = new_bobber_id;
+            self.set_entity(entity_id, &entity);
+        }
+
+        let stack = inv.get(index);
+        inv.set(index, stack.inc_damage(item_damage));
+
+    }
 
 }
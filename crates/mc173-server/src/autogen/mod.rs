@@ -20,25 +20,51 @@ use spacetimedb_sdk::{
 };
 use std::sync::Arc;
 
+pub mod arrow;
 pub mod biome;
+pub mod bobber;
 pub mod breaking_block;
+pub mod chicken;
 pub mod chunk;
 pub mod chunk_event;
 pub mod chunk_nibble_array_3;
 pub mod chunk_update_type;
+pub mod creeper;
+pub mod entity_kind;
+pub mod entity_status;
+pub mod falling_block;
 pub mod generate_chunk_reducer;
 pub mod generate_chunks_reducer;
+pub mod handle_action_reducer;
 pub mod handle_break_block_reducer;
+pub mod handle_hand_slot_reducer;
+pub mod handle_interact_reducer;
 pub mod handle_look_reducer;
+pub mod handle_place_block_reducer;
 pub mod handle_position_look_reducer;
 pub mod handle_position_reducer;
+pub mod handle_respawn_reducer;
+pub mod handle_update_sign_reducer;
+pub mod handle_window_click_reducer;
+pub mod handle_window_close_reducer;
+pub mod handle_window_transaction_reducer;
+pub mod hurt;
+pub mod item_stack;
 pub mod java_random;
-pub mod light_kind;
-pub mod light_update;
+pub mod look_target;
+pub mod painting_art;
+pub mod painting_orientation;
+pub mod pig;
+pub mod pig_zombie;
 pub mod set_weather_reducer;
+pub mod sheep;
+pub mod slime;
+pub mod squid;
+pub mod stdb_block_action_event;
 pub mod stdb_block_set_update;
 pub mod stdb_break_block_packet;
 pub mod stdb_breaking_block;
+pub mod stdb_chest_block_entity;
 pub mod stdb_chunk;
 pub mod stdb_chunk_populated;
 pub mod stdb_chunk_update;
@@ -46,10 +72,20 @@ pub mod stdb_chunk_view;
 pub mod stdb_client_state;
 pub mod stdb_connection_status;
 pub mod stdb_d_vec_3;
+pub mod stdb_dispenser_block_entity;
+pub mod stdb_effect_event;
 pub mod stdb_entity;
+pub mod stdb_entity_pickup_event;
+pub mod stdb_entity_status_event;
 pub mod stdb_entity_tracker;
 pub mod stdb_entity_tracker_update_type;
+pub mod stdb_entity_velocity_event;
 pub mod stdb_entity_view;
+pub mod stdb_explode_event;
+pub mod stdb_fireball;
+pub mod stdb_furnace_block_entity;
+pub mod stdb_furnace_minecart;
+pub mod stdb_ghast;
 pub mod stdb_handle_accept_reducer;
 pub mod stdb_handle_login_reducer;
 pub mod stdb_handle_lost_reducer;
@@ -58,44 +94,91 @@ pub mod stdb_i_16_vec_3;
 pub mod stdb_i_32_vec_3;
 pub mod stdb_i_8_vec_2;
 pub mod stdb_in_login_packet;
+pub mod stdb_item_entity;
+pub mod stdb_kind_data;
+pub mod stdb_kind_entity;
+pub mod stdb_living_entity;
 pub mod stdb_look_packet;
+pub mod stdb_minecart;
+pub mod stdb_note_block_block_entity;
 pub mod stdb_offline_player;
 pub mod stdb_offline_server_player;
+pub mod stdb_painting;
+pub mod stdb_piston_block_entity;
+pub mod stdb_place_block_packet;
+pub mod stdb_player_inventory;
 pub mod stdb_playing_state;
 pub mod stdb_position_look_packet;
 pub mod stdb_position_packet;
+pub mod stdb_projectile_entity;
+pub mod stdb_projectile_hit;
 pub mod stdb_rand;
+pub mod stdb_redstone_torch_toggle;
 pub mod stdb_server_player;
 pub mod stdb_server_world;
 pub mod stdb_set_block_event;
+pub mod stdb_sign_block_entity;
+pub mod stdb_spawner_block_entity;
 pub mod stdb_tick_mode;
 pub mod stdb_time;
 pub mod stdb_tracked_player;
 pub mod stdb_vec_2;
 pub mod stdb_weather;
+pub mod stdb_window;
+pub mod stdb_window_click_packet;
+pub mod stdb_window_kind;
+pub mod stdb_window_transaction_packet;
 pub mod stdb_world;
 pub mod tick_reducer;
+pub mod tnt;
 pub mod weather;
+pub mod wolf;
 
+pub use arrow::*;
 pub use biome::*;
+pub use bobber::*;
 pub use breaking_block::*;
+pub use chicken::*;
 pub use chunk::*;
 pub use chunk_event::*;
 pub use chunk_nibble_array_3::*;
 pub use chunk_update_type::*;
+pub use creeper::*;
+pub use entity_kind::*;
+pub use entity_status::*;
+pub use falling_block::*;
 pub use generate_chunk_reducer::*;
 pub use generate_chunks_reducer::*;
+pub use handle_action_reducer::*;
 pub use handle_break_block_reducer::*;
+pub use handle_hand_slot_reducer::*;
+pub use handle_interact_reducer::*;
 pub use handle_look_reducer::*;
+pub use handle_place_block_reducer::*;
 pub use handle_position_look_reducer::*;
 pub use handle_position_reducer::*;
+pub use handle_respawn_reducer::*;
+pub use handle_update_sign_reducer::*;
+pub use handle_window_click_reducer::*;
+pub use handle_window_close_reducer::*;
+pub use handle_window_transaction_reducer::*;
+pub use hurt::*;
+pub use item_stack::*;
 pub use java_random::*;
-pub use light_kind::*;
-pub use light_update::*;
+pub use look_target::*;
+pub use painting_art::*;
+pub use painting_orientation::*;
+pub use pig::*;
+pub use pig_zombie::*;
 pub use set_weather_reducer::*;
+pub use sheep::*;
+pub use slime::*;
+pub use squid::*;
+pub use stdb_block_action_event::*;
 pub use stdb_block_set_update::*;
 pub use stdb_break_block_packet::*;
 pub use stdb_breaking_block::*;
+pub use stdb_chest_block_entity::*;
 pub use stdb_chunk::*;
 pub use stdb_chunk_populated::*;
 pub use stdb_chunk_update::*;
@@ -103,10 +186,20 @@ pub use stdb_chunk_view::*;
 pub use stdb_client_state::*;
 pub use stdb_connection_status::*;
 pub use stdb_d_vec_3::*;
+pub use stdb_dispenser_block_entity::*;
+pub use stdb_effect_event::*;
 pub use stdb_entity::*;
+pub use stdb_entity_pickup_event::*;
+pub use stdb_entity_status_event::*;
 pub use stdb_entity_tracker::*;
 pub use stdb_entity_tracker_update_type::*;
+pub use stdb_entity_velocity_event::*;
 pub use stdb_entity_view::*;
+pub use stdb_explode_event::*;
+pub use stdb_fireball::*;
+pub use stdb_furnace_block_entity::*;
+pub use stdb_furnace_minecart::*;
+pub use stdb_ghast::*;
 pub use stdb_handle_accept_reducer::*;
 pub use stdb_handle_login_reducer::*;
 pub use stdb_handle_lost_reducer::*;
@@ -115,34 +208,64 @@ pub use stdb_i_16_vec_3::*;
 pub use stdb_i_32_vec_3::*;
 pub use stdb_i_8_vec_2::*;
 pub use stdb_in_login_packet::*;
+pub use stdb_item_entity::*;
+pub use stdb_kind_data::*;
+pub use stdb_kind_entity::*;
+pub use stdb_living_entity::*;
 pub use stdb_look_packet::*;
+pub use stdb_minecart::*;
+pub use stdb_note_block_block_entity::*;
 pub use stdb_offline_player::*;
 pub use stdb_offline_server_player::*;
+pub use stdb_painting::*;
+pub use stdb_piston_block_entity::*;
+pub use stdb_place_block_packet::*;
+pub use stdb_player_inventory::*;
 pub use stdb_playing_state::*;
 pub use stdb_position_look_packet::*;
 pub use stdb_position_packet::*;
+pub use stdb_projectile_entity::*;
+pub use stdb_projectile_hit::*;
 pub use stdb_rand::*;
+pub use stdb_redstone_torch_toggle::*;
 pub use stdb_server_player::*;
 pub use stdb_server_world::*;
 pub use stdb_set_block_event::*;
+pub use stdb_sign_block_entity::*;
+pub use stdb_spawner_block_entity::*;
 pub use stdb_tick_mode::*;
 pub use stdb_time::*;
 pub use stdb_tracked_player::*;
 pub use stdb_vec_2::*;
 pub use stdb_weather::*;
+pub use stdb_window::*;
+pub use stdb_window_click_packet::*;
+pub use stdb_window_kind::*;
+pub use stdb_window_transaction_packet::*;
 pub use stdb_world::*;
 pub use tick_reducer::*;
+pub use tnt::*;
 pub use weather::*;
+pub use wolf::*;
 
 #[allow(unused)]
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 pub enum ReducerEvent {
     GenerateChunk(generate_chunk_reducer::GenerateChunkArgs),
     GenerateChunks(generate_chunks_reducer::GenerateChunksArgs),
+    HandleAction(handle_action_reducer::HandleActionArgs),
     HandleBreakBlock(handle_break_block_reducer::HandleBreakBlockArgs),
+    HandleHandSlot(handle_hand_slot_reducer::HandleHandSlotArgs),
+    HandleInteract(handle_interact_reducer::HandleInteractArgs),
     HandleLook(handle_look_reducer::HandleLookArgs),
+    HandlePlaceBlock(handle_place_block_reducer::HandlePlaceBlockArgs),
     HandlePosition(handle_position_reducer::HandlePositionArgs),
     HandlePositionLook(handle_position_look_reducer::HandlePositionLookArgs),
+    HandleRespawn(handle_respawn_reducer::HandleRespawnArgs),
+    HandleUpdateSign(handle_update_sign_reducer::HandleUpdateSignArgs),
+    HandleWindowClick(handle_window_click_reducer::HandleWindowClickArgs),
+    HandleWindowClose(handle_window_close_reducer::HandleWindowCloseArgs),
+    HandleWindowTransaction(handle_window_transaction_reducer::HandleWindowTransactionArgs),
     SetWeather(set_weather_reducer::SetWeatherArgs),
     StdbHandleAccept(stdb_handle_accept_reducer::StdbHandleAcceptArgs),
     StdbHandleLogin(stdb_handle_login_reducer::StdbHandleLoginArgs),
@@ -161,26 +284,46 @@ impl SpacetimeModule for Module {
     ) {
         let table_name = &table_update.table_name[..];
         match table_name {
-						"StdbBlockSetUpdate" => client_cache.handle_table_update_no_primary_key::<stdb_block_set_update::StdbBlockSetUpdate>(callbacks, table_update),
+						"StdbBlockActionEvent" => client_cache.handle_table_update_with_primary_key::<stdb_block_action_event::StdbBlockActionEvent>(callbacks, table_update),
+			"StdbBlockSetUpdate" => client_cache.handle_table_update_no_primary_key::<stdb_block_set_update::StdbBlockSetUpdate>(callbacks, table_update),
 			"StdbBreakingBlock" => client_cache.handle_table_update_no_primary_key::<stdb_breaking_block::StdbBreakingBlock>(callbacks, table_update),
+			"StdbChestBlockEntity" => client_cache.handle_table_update_with_primary_key::<stdb_chest_block_entity::StdbChestBlockEntity>(callbacks, table_update),
 			"StdbChunk" => client_cache.handle_table_update_with_primary_key::<stdb_chunk::StdbChunk>(callbacks, table_update),
 			"StdbChunkPopulated" => client_cache.handle_table_update_with_primary_key::<stdb_chunk_populated::StdbChunkPopulated>(callbacks, table_update),
 			"StdbChunkUpdate" => client_cache.handle_table_update_no_primary_key::<stdb_chunk_update::StdbChunkUpdate>(callbacks, table_update),
 			"StdbChunkView" => client_cache.handle_table_update_with_primary_key::<stdb_chunk_view::StdbChunkView>(callbacks, table_update),
 			"StdbConnectionStatus" => client_cache.handle_table_update_no_primary_key::<stdb_connection_status::StdbConnectionStatus>(callbacks, table_update),
+			"StdbDispenserBlockEntity" => client_cache.handle_table_update_with_primary_key::<stdb_dispenser_block_entity::StdbDispenserBlockEntity>(callbacks, table_update),
+			"StdbEffectEvent" => client_cache.handle_table_update_with_primary_key::<stdb_effect_event::StdbEffectEvent>(callbacks, table_update),
 			"StdbEntity" => client_cache.handle_table_update_with_primary_key::<stdb_entity::StdbEntity>(callbacks, table_update),
+			"StdbEntityPickupEvent" => client_cache.handle_table_update_with_primary_key::<stdb_entity_pickup_event::StdbEntityPickupEvent>(callbacks, table_update),
+			"StdbEntityStatusEvent" => client_cache.handle_table_update_with_primary_key::<stdb_entity_status_event::StdbEntityStatusEvent>(callbacks, table_update),
 			"StdbEntityTracker" => client_cache.handle_table_update_with_primary_key::<stdb_entity_tracker::StdbEntityTracker>(callbacks, table_update),
+			"StdbEntityVelocityEvent" => client_cache.handle_table_update_with_primary_key::<stdb_entity_velocity_event::StdbEntityVelocityEvent>(callbacks, table_update),
 			"StdbEntityView" => client_cache.handle_table_update_with_primary_key::<stdb_entity_view::StdbEntityView>(callbacks, table_update),
+			"StdbExplodeEvent" => client_cache.handle_table_update_with_primary_key::<stdb_explode_event::StdbExplodeEvent>(callbacks, table_update),
+			"StdbFurnaceBlockEntity" => client_cache.handle_table_update_with_primary_key::<stdb_furnace_block_entity::StdbFurnaceBlockEntity>(callbacks, table_update),
 			"StdbHuman" => client_cache.handle_table_update_with_primary_key::<stdb_human::StdbHuman>(callbacks, table_update),
+			"StdbItemEntity" => client_cache.handle_table_update_with_primary_key::<stdb_item_entity::StdbItemEntity>(callbacks, table_update),
+			"StdbKindEntity" => client_cache.handle_table_update_with_primary_key::<stdb_kind_entity::StdbKindEntity>(callbacks, table_update),
+			"StdbLivingEntity" => client_cache.handle_table_update_with_primary_key::<stdb_living_entity::StdbLivingEntity>(callbacks, table_update),
+			"StdbNoteBlockBlockEntity" => client_cache.handle_table_update_with_primary_key::<stdb_note_block_block_entity::StdbNoteBlockBlockEntity>(callbacks, table_update),
 			"StdbOfflinePlayer" => client_cache.handle_table_update_no_primary_key::<stdb_offline_player::StdbOfflinePlayer>(callbacks, table_update),
 			"StdbOfflineServerPlayer" => client_cache.handle_table_update_with_primary_key::<stdb_offline_server_player::StdbOfflineServerPlayer>(callbacks, table_update),
+			"StdbPistonBlockEntity" => client_cache.handle_table_update_with_primary_key::<stdb_piston_block_entity::StdbPistonBlockEntity>(callbacks, table_update),
+			"StdbPlayerInventory" => client_cache.handle_table_update_with_primary_key::<stdb_player_inventory::StdbPlayerInventory>(callbacks, table_update),
+			"StdbProjectileEntity" => client_cache.handle_table_update_with_primary_key::<stdb_projectile_entity::StdbProjectileEntity>(callbacks, table_update),
 			"StdbRand" => client_cache.handle_table_update_no_primary_key::<stdb_rand::StdbRand>(callbacks, table_update),
+			"StdbRedstoneTorchToggle" => client_cache.handle_table_update_with_primary_key::<stdb_redstone_torch_toggle::StdbRedstoneTorchToggle>(callbacks, table_update),
 			"StdbServerPlayer" => client_cache.handle_table_update_with_primary_key::<stdb_server_player::StdbServerPlayer>(callbacks, table_update),
 			"StdbServerWorld" => client_cache.handle_table_update_with_primary_key::<stdb_server_world::StdbServerWorld>(callbacks, table_update),
 			"StdbSetBlockEvent" => client_cache.handle_table_update_no_primary_key::<stdb_set_block_event::StdbSetBlockEvent>(callbacks, table_update),
+			"StdbSignBlockEntity" => client_cache.handle_table_update_with_primary_key::<stdb_sign_block_entity::StdbSignBlockEntity>(callbacks, table_update),
+			"StdbSpawnerBlockEntity" => client_cache.handle_table_update_with_primary_key::<stdb_spawner_block_entity::StdbSpawnerBlockEntity>(callbacks, table_update),
 			"StdbTime" => client_cache.handle_table_update_no_primary_key::<stdb_time::StdbTime>(callbacks, table_update),
 			"StdbTrackedPlayer" => client_cache.handle_table_update_with_primary_key::<stdb_tracked_player::StdbTrackedPlayer>(callbacks, table_update),
 			"StdbWeather" => client_cache.handle_table_update_with_primary_key::<stdb_weather::StdbWeather>(callbacks, table_update),
+			"StdbWindow" => client_cache.handle_table_update_with_primary_key::<stdb_window::StdbWindow>(callbacks, table_update),
 			"StdbWorld" => client_cache.handle_table_update_with_primary_key::<stdb_world::StdbWorld>(callbacks, table_update),
 			_ => spacetimedb_sdk::log::error!("TableRowOperation on unknown table {:?}", table_name),
 }
@@ -192,6 +335,7 @@ impl SpacetimeModule for Module {
         reducer_event: Option<Arc<AnyReducerEvent>>,
         state: &Arc<ClientCache>,
     ) {
+        reminders.invoke_callbacks::<stdb_block_action_event::StdbBlockActionEvent>(worker, &reducer_event, state);
         reminders.invoke_callbacks::<stdb_block_set_update::StdbBlockSetUpdate>(
             worker,
             &reducer_event,
@@ -202,6 +346,7 @@ impl SpacetimeModule for Module {
             &reducer_event,
             state,
         );
+        reminders.invoke_callbacks::<stdb_chest_block_entity::StdbChestBlockEntity>(worker, &reducer_event, state);
         reminders.invoke_callbacks::<stdb_chunk::StdbChunk>(worker, &reducer_event, state);
         reminders.invoke_callbacks::<stdb_chunk_populated::StdbChunkPopulated>(
             worker,
@@ -219,18 +364,29 @@ impl SpacetimeModule for Module {
             &reducer_event,
             state,
         );
+        reminders.invoke_callbacks::<stdb_dispenser_block_entity::StdbDispenserBlockEntity>(worker, &reducer_event, state);
+        reminders.invoke_callbacks::<stdb_effect_event::StdbEffectEvent>(worker, &reducer_event, state);
         reminders.invoke_callbacks::<stdb_entity::StdbEntity>(worker, &reducer_event, state);
+        reminders.invoke_callbacks::<stdb_entity_pickup_event::StdbEntityPickupEvent>(worker, &reducer_event, state);
+        reminders.invoke_callbacks::<stdb_entity_status_event::StdbEntityStatusEvent>(worker, &reducer_event, state);
         reminders.invoke_callbacks::<stdb_entity_tracker::StdbEntityTracker>(
             worker,
             &reducer_event,
             state,
         );
+        reminders.invoke_callbacks::<stdb_entity_velocity_event::StdbEntityVelocityEvent>(worker, &reducer_event, state);
         reminders.invoke_callbacks::<stdb_entity_view::StdbEntityView>(
             worker,
             &reducer_event,
             state,
         );
+        reminders.invoke_callbacks::<stdb_explode_event::StdbExplodeEvent>(worker, &reducer_event, state);
+        reminders.invoke_callbacks::<stdb_furnace_block_entity::StdbFurnaceBlockEntity>(worker, &reducer_event, state);
         reminders.invoke_callbacks::<stdb_human::StdbHuman>(worker, &reducer_event, state);
+        reminders.invoke_callbacks::<stdb_item_entity::StdbItemEntity>(worker, &reducer_event, state);
+        reminders.invoke_callbacks::<stdb_kind_entity::StdbKindEntity>(worker, &reducer_event, state);
+        reminders.invoke_callbacks::<stdb_living_entity::StdbLivingEntity>(worker, &reducer_event, state);
+        reminders.invoke_callbacks::<stdb_note_block_block_entity::StdbNoteBlockBlockEntity>(worker, &reducer_event, state);
         reminders.invoke_callbacks::<stdb_offline_player::StdbOfflinePlayer>(
             worker,
             &reducer_event,
@@ -241,7 +397,11 @@ impl SpacetimeModule for Module {
             &reducer_event,
             state,
         );
+        reminders.invoke_callbacks::<stdb_piston_block_entity::StdbPistonBlockEntity>(worker, &reducer_event, state);
+        reminders.invoke_callbacks::<stdb_player_inventory::StdbPlayerInventory>(worker, &reducer_event, state);
+        reminders.invoke_callbacks::<stdb_projectile_entity::StdbProjectileEntity>(worker, &reducer_event, state);
         reminders.invoke_callbacks::<stdb_rand::StdbRand>(worker, &reducer_event, state);
+        reminders.invoke_callbacks::<stdb_redstone_torch_toggle::StdbRedstoneTorchToggle>(worker, &reducer_event, state);
         reminders.invoke_callbacks::<stdb_server_player::StdbServerPlayer>(
             worker,
             &reducer_event,
@@ -257,6 +417,8 @@ impl SpacetimeModule for Module {
             &reducer_event,
             state,
         );
+        reminders.invoke_callbacks::<stdb_sign_block_entity::StdbSignBlockEntity>(worker, &reducer_event, state);
+        reminders.invoke_callbacks::<stdb_spawner_block_entity::StdbSpawnerBlockEntity>(worker, &reducer_event, state);
         reminders.invoke_callbacks::<stdb_time::StdbTime>(worker, &reducer_event, state);
         reminders.invoke_callbacks::<stdb_tracked_player::StdbTrackedPlayer>(
             worker,
@@ -264,6 +426,7 @@ impl SpacetimeModule for Module {
             state,
         );
         reminders.invoke_callbacks::<stdb_weather::StdbWeather>(worker, &reducer_event, state);
+        reminders.invoke_callbacks::<stdb_window::StdbWindow>(worker, &reducer_event, state);
         reminders.invoke_callbacks::<stdb_world::StdbWorld>(worker, &reducer_event, state);
     }
     fn handle_event(
@@ -278,12 +441,21 @@ impl SpacetimeModule for Module {
         };
         #[allow(clippy::match_single_binding)]
 match &function_call.reducer[..] {
-						"generate_chunk" => _reducer_callbacks.handle_event_of_type::<generate_chunk_reducer::GenerateChunkArgs, ReducerEvent>(event, _state, ReducerEvent::GenerateChunk),
+			"generate_chunk" => _reducer_callbacks.handle_event_of_type::<generate_chunk_reducer::GenerateChunkArgs, ReducerEvent>(event, _state, ReducerEvent::GenerateChunk),
 			"generate_chunks" => _reducer_callbacks.handle_event_of_type::<generate_chunks_reducer::GenerateChunksArgs, ReducerEvent>(event, _state, ReducerEvent::GenerateChunks),
+			"handle_action" => _reducer_callbacks.handle_event_of_type::<handle_action_reducer::HandleActionArgs, ReducerEvent>(event, _state, ReducerEvent::HandleAction),
 			"handle_break_block" => _reducer_callbacks.handle_event_of_type::<handle_break_block_reducer::HandleBreakBlockArgs, ReducerEvent>(event, _state, ReducerEvent::HandleBreakBlock),
+			"handle_hand_slot" => _reducer_callbacks.handle_event_of_type::<handle_hand_slot_reducer::HandleHandSlotArgs, ReducerEvent>(event, _state, ReducerEvent::HandleHandSlot),
+			"handle_interact" => _reducer_callbacks.handle_event_of_type::<handle_interact_reducer::HandleInteractArgs, ReducerEvent>(event, _state, ReducerEvent::HandleInteract),
 			"handle_look" => _reducer_callbacks.handle_event_of_type::<handle_look_reducer::HandleLookArgs, ReducerEvent>(event, _state, ReducerEvent::HandleLook),
+			"handle_place_block" => _reducer_callbacks.handle_event_of_type::<handle_place_block_reducer::HandlePlaceBlockArgs, ReducerEvent>(event, _state, ReducerEvent::HandlePlaceBlock),
 			"handle_position" => _reducer_callbacks.handle_event_of_type::<handle_position_reducer::HandlePositionArgs, ReducerEvent>(event, _state, ReducerEvent::HandlePosition),
 			"handle_position_look" => _reducer_callbacks.handle_event_of_type::<handle_position_look_reducer::HandlePositionLookArgs, ReducerEvent>(event, _state, ReducerEvent::HandlePositionLook),
+			"handle_respawn" => _reducer_callbacks.handle_event_of_type::<handle_respawn_reducer::HandleRespawnArgs, ReducerEvent>(event, _state, ReducerEvent::HandleRespawn),
+			"handle_update_sign" => _reducer_callbacks.handle_event_of_type::<handle_update_sign_reducer::HandleUpdateSignArgs, ReducerEvent>(event, _state, ReducerEvent::HandleUpdateSign),
+			"handle_window_click" => _reducer_callbacks.handle_event_of_type::<handle_window_click_reducer::HandleWindowClickArgs, ReducerEvent>(event, _state, ReducerEvent::HandleWindowClick),
+			"handle_window_close" => _reducer_callbacks.handle_event_of_type::<handle_window_close_reducer::HandleWindowCloseArgs, ReducerEvent>(event, _state, ReducerEvent::HandleWindowClose),
+			"handle_window_transaction" => _reducer_callbacks.handle_event_of_type::<handle_window_transaction_reducer::HandleWindowTransactionArgs, ReducerEvent>(event, _state, ReducerEvent::HandleWindowTransaction),
 			"set_weather" => _reducer_callbacks.handle_event_of_type::<set_weather_reducer::SetWeatherArgs, ReducerEvent>(event, _state, ReducerEvent::SetWeather),
 			"stdb_handle_accept" => _reducer_callbacks.handle_event_of_type::<stdb_handle_accept_reducer::StdbHandleAcceptArgs, ReducerEvent>(event, _state, ReducerEvent::StdbHandleAccept),
 			"stdb_handle_login" => _reducer_callbacks.handle_event_of_type::<stdb_handle_login_reducer::StdbHandleLoginArgs, ReducerEvent>(event, _state, ReducerEvent::StdbHandleLogin),
@@ -300,6 +472,7 @@ match &function_call.reducer[..] {
     ) {
         let table_name = &new_subs.table_name[..];
         match table_name {
+            "StdbBlockActionEvent" => client_cache.handle_resubscribe_for_type::<stdb_block_action_event::StdbBlockActionEvent>(callbacks, new_subs),
             "StdbBlockSetUpdate" => client_cache
                 .handle_resubscribe_for_type::<stdb_block_set_update::StdbBlockSetUpdate>(
                     callbacks, new_subs,
@@ -308,6 +481,7 @@ match &function_call.reducer[..] {
                 .handle_resubscribe_for_type::<stdb_breaking_block::StdbBreakingBlock>(
                     callbacks, new_subs,
                 ),
+            "StdbChestBlockEntity" => client_cache.handle_resubscribe_for_type::<stdb_chest_block_entity::StdbChestBlockEntity>(callbacks, new_subs),
             "StdbChunk" => client_cache
                 .handle_resubscribe_for_type::<stdb_chunk::StdbChunk>(callbacks, new_subs),
             "StdbChunkPopulated" => client_cache
@@ -324,18 +498,29 @@ match &function_call.reducer[..] {
                 .handle_resubscribe_for_type::<stdb_connection_status::StdbConnectionStatus>(
                     callbacks, new_subs,
                 ),
+            "StdbDispenserBlockEntity" => client_cache.handle_resubscribe_for_type::<stdb_dispenser_block_entity::StdbDispenserBlockEntity>(callbacks, new_subs),
+            "StdbEffectEvent" => client_cache.handle_resubscribe_for_type::<stdb_effect_event::StdbEffectEvent>(callbacks, new_subs),
             "StdbEntity" => client_cache
                 .handle_resubscribe_for_type::<stdb_entity::StdbEntity>(callbacks, new_subs),
+            "StdbEntityPickupEvent" => client_cache.handle_resubscribe_for_type::<stdb_entity_pickup_event::StdbEntityPickupEvent>(callbacks, new_subs),
+            "StdbEntityStatusEvent" => client_cache.handle_resubscribe_for_type::<stdb_entity_status_event::StdbEntityStatusEvent>(callbacks, new_subs),
             "StdbEntityTracker" => client_cache
                 .handle_resubscribe_for_type::<stdb_entity_tracker::StdbEntityTracker>(
                     callbacks, new_subs,
                 ),
+            "StdbEntityVelocityEvent" => client_cache.handle_resubscribe_for_type::<stdb_entity_velocity_event::StdbEntityVelocityEvent>(callbacks, new_subs),
             "StdbEntityView" => client_cache
                 .handle_resubscribe_for_type::<stdb_entity_view::StdbEntityView>(
                     callbacks, new_subs,
                 ),
+            "StdbExplodeEvent" => client_cache.handle_resubscribe_for_type::<stdb_explode_event::StdbExplodeEvent>(callbacks, new_subs),
+            "StdbFurnaceBlockEntity" => client_cache.handle_resubscribe_for_type::<stdb_furnace_block_entity::StdbFurnaceBlockEntity>(callbacks, new_subs),
             "StdbHuman" => client_cache
                 .handle_resubscribe_for_type::<stdb_human::StdbHuman>(callbacks, new_subs),
+            "StdbItemEntity" => client_cache.handle_resubscribe_for_type::<stdb_item_entity::StdbItemEntity>(callbacks, new_subs),
+            "StdbKindEntity" => client_cache.handle_resubscribe_for_type::<stdb_kind_entity::StdbKindEntity>(callbacks, new_subs),
+            "StdbLivingEntity" => client_cache.handle_resubscribe_for_type::<stdb_living_entity::StdbLivingEntity>(callbacks, new_subs),
+            "StdbNoteBlockBlockEntity" => client_cache.handle_resubscribe_for_type::<stdb_note_block_block_entity::StdbNoteBlockBlockEntity>(callbacks, new_subs),
             "StdbOfflinePlayer" => client_cache
                 .handle_resubscribe_for_type::<stdb_offline_player::StdbOfflinePlayer>(
                     callbacks, new_subs,
@@ -344,9 +529,13 @@ match &function_call.reducer[..] {
                 .handle_resubscribe_for_type::<stdb_offline_server_player::StdbOfflineServerPlayer>(
                 callbacks, new_subs,
             ),
+            "StdbPistonBlockEntity" => client_cache.handle_resubscribe_for_type::<stdb_piston_block_entity::StdbPistonBlockEntity>(callbacks, new_subs),
+            "StdbPlayerInventory" => client_cache.handle_resubscribe_for_type::<stdb_player_inventory::StdbPlayerInventory>(callbacks, new_subs),
+            "StdbProjectileEntity" => client_cache.handle_resubscribe_for_type::<stdb_projectile_entity::StdbProjectileEntity>(callbacks, new_subs),
             "StdbRand" => {
                 client_cache.handle_resubscribe_for_type::<stdb_rand::StdbRand>(callbacks, new_subs)
             }
+            "StdbRedstoneTorchToggle" => client_cache.handle_resubscribe_for_type::<stdb_redstone_torch_toggle::StdbRedstoneTorchToggle>(callbacks, new_subs),
             "StdbServerPlayer" => client_cache
                 .handle_resubscribe_for_type::<stdb_server_player::StdbServerPlayer>(
                     callbacks, new_subs,
@@ -359,6 +548,8 @@ match &function_call.reducer[..] {
                 .handle_resubscribe_for_type::<stdb_set_block_event::StdbSetBlockEvent>(
                     callbacks, new_subs,
                 ),
+            "StdbSignBlockEntity" => client_cache.handle_resubscribe_for_type::<stdb_sign_block_entity::StdbSignBlockEntity>(callbacks, new_subs),
+            "StdbSpawnerBlockEntity" => client_cache.handle_resubscribe_for_type::<stdb_spawner_block_entity::StdbSpawnerBlockEntity>(callbacks, new_subs),
             "StdbTime" => {
                 client_cache.handle_resubscribe_for_type::<stdb_time::StdbTime>(callbacks, new_subs)
             }
@@ -368,6 +559,7 @@ match &function_call.reducer[..] {
                 ),
             "StdbWeather" => client_cache
                 .handle_resubscribe_for_type::<stdb_weather::StdbWeather>(callbacks, new_subs),
+            "StdbWindow" => client_cache.handle_resubscribe_for_type::<stdb_window::StdbWindow>(callbacks, new_subs),
             "StdbWorld" => client_cache
                 .handle_resubscribe_for_type::<stdb_world::StdbWorld>(callbacks, new_subs),
             _ => {
@@ -397,3 +589,4 @@ where
         Ok(())
     })
 }
+
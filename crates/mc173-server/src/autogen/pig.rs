@@ -13,8 +13,6 @@ use spacetimedb_sdk::{
 };
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
-pub enum LightKind {
-    Block,
-
-    Sky,
+pub struct Pig {
+    pub saddle: bool,
 }
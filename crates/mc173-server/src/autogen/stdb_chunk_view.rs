@@ -15,6 +15,7 @@ use spacetimedb_sdk::{
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 pub struct StdbChunkView {
     pub view_id: u32,
+    pub dimension_id: i32,
     pub chunk_id: u32,
     pub observer_id: u32,
 }
@@ -41,6 +42,10 @@ impl StdbChunkView {
         Self::find(|row| row.view_id == view_id)
     }
     #[allow(unused)]
+    pub fn filter_by_dimension_id(dimension_id: i32) -> TableIter<Self> {
+        Self::filter(|row| row.dimension_id == dimension_id)
+    }
+    #[allow(unused)]
     pub fn filter_by_chunk_id(chunk_id: u32) -> TableIter<Self> {
         Self::filter(|row| row.chunk_id == chunk_id)
     }
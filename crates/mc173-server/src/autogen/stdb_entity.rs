@@ -2,6 +2,9 @@
 // WILL NOT BE SAVED. MODIFY TABLES IN RUST INSTEAD.
 
 #![allow(unused_imports)]
+use super::entity_kind::EntityKind;
+use super::hurt::Hurt;
+use super::java_random::JavaRandom;
 use super::stdb_d_vec_3::StdbDVec3;
 use super::stdb_vec_2::StdbVec2;
 use spacetimedb_sdk::{
@@ -17,10 +20,26 @@ use spacetimedb_sdk::{
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 pub struct StdbEntity {
     pub entity_id: u32,
-    pub on_ground: bool,
+    pub kind: EntityKind,
+    pub dimension_id: i32,
+    pub chunk_id: u32,
+    pub persistent: bool,
     pub pos: StdbDVec3,
+    pub vel: StdbDVec3,
     pub look: StdbVec2,
-    pub dimension_id: i32,
+    pub lifetime: u32,
+    pub can_pickup: bool,
+    pub no_clip: bool,
+    pub on_ground: bool,
+    pub in_water: bool,
+    pub in_lava: bool,
+    pub fall_distance: f32,
+    pub fire_time: u32,
+    pub air_time: u32,
+    pub hurt: Vec<Hurt>,
+    pub rider_id: Option<u32>,
+    pub bobber_id: Option<u32>,
+    pub rand: JavaRandom,
 }
 
 impl TableType for StdbEntity {
@@ -45,11 +64,47 @@ impl StdbEntity {
         Self::find(|row| row.entity_id == entity_id)
     }
     #[allow(unused)]
+    pub fn filter_by_dimension_id(dimension_id: i32) -> TableIter<Self> {
+        Self::filter(|row| row.dimension_id == dimension_id)
+    }
+    #[allow(unused)]
+    pub fn filter_by_chunk_id(chunk_id: u32) -> TableIter<Self> {
+        Self::filter(|row| row.chunk_id == chunk_id)
+    }
+    #[allow(unused)]
+    pub fn filter_by_persistent(persistent: bool) -> TableIter<Self> {
+        Self::filter(|row| row.persistent == persistent)
+    }
+    #[allow(unused)]
+    pub fn filter_by_lifetime(lifetime: u32) -> TableIter<Self> {
+        Self::filter(|row| row.lifetime == lifetime)
+    }
+    #[allow(unused)]
+    pub fn filter_by_can_pickup(can_pickup: bool) -> TableIter<Self> {
+        Self::filter(|row| row.can_pickup == can_pickup)
+    }
+    #[allow(unused)]
+    pub fn filter_by_no_clip(no_clip: bool) -> TableIter<Self> {
+        Self::filter(|row| row.no_clip == no_clip)
+    }
+    #[allow(unused)]
     pub fn filter_by_on_ground(on_ground: bool) -> TableIter<Self> {
         Self::filter(|row| row.on_ground == on_ground)
     }
     #[allow(unused)]
-    pub fn filter_by_dimension_id(dimension_id: i32) -> TableIter<Self> {
-        Self::filter(|row| row.dimension_id == dimension_id)
+    pub fn filter_by_in_water(in_water: bool) -> TableIter<Self> {
+        Self::filter(|row| row.in_water == in_water)
+    }
+    #[allow(unused)]
+    pub fn filter_by_in_lava(in_lava: bool) -> TableIter<Self> {
+        Self::filter(|row| row.in_lava == in_lava)
+    }
+    #[allow(unused)]
+    pub fn filter_by_fire_time(fire_time: u32) -> TableIter<Self> {
+        Self::filter(|row| row.fire_time == fire_time)
+    }
+    #[allow(unused)]
+    pub fn filter_by_air_time(air_time: u32) -> TableIter<Self> {
+        Self::filter(|row| row.air_time == air_time)
     }
 }
@@ -2,6 +2,7 @@
 // WILL NOT BE SAVED. MODIFY TABLES IN RUST INSTEAD.
 
 #![allow(unused_imports)]
+use super::stdb_i_32_vec_3::StdbI32Vec3;
 use spacetimedb_sdk::{
     anyhow::{anyhow, Result},
     identity::Identity,
@@ -17,6 +18,8 @@ pub struct StdbHuman {
     pub entity_id: u32,
     pub username: String,
     pub sleeping: bool,
+    pub sleep_time: u8,
+    pub bed_pos: Option<StdbI32Vec3>,
     pub sneaking: bool,
 }
 
@@ -54,6 +57,10 @@ impl StdbHuman {
         Self::filter(|row| row.sleeping == sleeping)
     }
     #[allow(unused)]
+    pub fn filter_by_sleep_time(sleep_time: u8) -> TableIter<Self> {
+        Self::filter(|row| row.sleep_time == sleep_time)
+    }
+    #[allow(unused)]
     pub fn filter_by_sneaking(sneaking: bool) -> TableIter<Self> {
         Self::filter(|row| row.sneaking == sneaking)
     }
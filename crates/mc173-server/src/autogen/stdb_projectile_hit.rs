@@ -2,7 +2,6 @@
 // WILL NOT BE SAVED. MODIFY TABLES IN RUST INSTEAD.
 
 #![allow(unused_imports)]
-use super::light_kind::LightKind;
 use super::stdb_i_32_vec_3::StdbI32Vec3;
 use spacetimedb_sdk::{
     anyhow::{anyhow, Result},
@@ -15,8 +14,8 @@ use spacetimedb_sdk::{
 };
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
-pub struct LightUpdate {
-    pub kind: LightKind,
+pub struct StdbProjectileHit {
     pub pos: StdbI32Vec3,
-    pub credit: u8,
+    pub block: u8,
+    pub metadata: u8,
 }
@@ -3,7 +3,6 @@
 
 #![allow(unused_imports)]
 use super::java_random::JavaRandom;
-use super::light_update::LightUpdate;
 use spacetimedb_sdk::{
     anyhow::{anyhow, Result},
     identity::Identity,
@@ -20,7 +19,7 @@ pub struct StdbWorld {
     pub time: u64,
     pub rand: JavaRandom,
     pub entities_count: u32,
-    pub light_updates: Vec<LightUpdate>,
+    pub block_ticks_time: u64,
     pub random_ticks_seed: i32,
     pub sky_light_subtracted: u8,
 }
@@ -55,6 +54,10 @@ impl StdbWorld {
         Self::filter(|row| row.entities_count == entities_count)
     }
     #[allow(unused)]
+    pub fn filter_by_block_ticks_time(block_ticks_time: u64) -> TableIter<Self> {
+        Self::filter(|row| row.block_ticks_time == block_ticks_time)
+    }
+    #[allow(unused)]
     pub fn filter_by_random_ticks_seed(random_ticks_seed: i32) -> TableIter<Self> {
         Self::filter(|row| row.random_ticks_seed == random_ticks_seed)
     }
@@ -0,0 +1,25 @@
+//! Block actions synchronization with the client.
+
+use crate::autogen::{StdbBlockActionEvent, StdbChunkView, StdbServerPlayer};
+use crate::player::ServerPlayer;
+use crate::proto::{self, OutPacket};
+use crate::server::Server;
+
+impl StdbBlockActionEvent {
+
+    /// Send this block action to every player observing its chunk.
+    pub fn send_to_observers(&self, server: &Server) {
+        for view in StdbChunkView::filter_by_chunk_id(self.chunk_id) {
+            if let Some(player) = StdbServerPlayer::find_by_entity_id(view.observer_id) {
+                ServerPlayer::send(server, player.connection_id, OutPacket::BlockAction(proto::BlockActionPacket {
+                    x: self.pos.x,
+                    y: self.pos.y as i16,
+                    z: self.pos.z,
+                    data0: self.data0,
+                    data1: self.data1,
+                }));
+            }
+        }
+    }
+
+}
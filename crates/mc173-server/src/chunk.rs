@@ -2,7 +2,7 @@ use std::io;
 use std::io::Write;
 use std::sync::Arc;
 use glam::{DVec3, IVec3};
-use crate::autogen::{StdbChunk, StdbServerPlayer};
+use crate::autogen::{StdbChunk, StdbSignBlockEntity};
 use crate::autogen::Biome;
 use crate::proto;
 use crate::proto::OutPacket;
@@ -78,6 +78,16 @@ impl StdbChunk {
         (x, z)
     }
 
+    /// Get block id and metadata at the given global position (rebased to chunk-local).
+    /// Panics if Y component of the position is not between 0 and 128 (excluded).
+    #[inline]
+    pub fn get_block(&self, pos: IVec3) -> (u8, u8) {
+        let index = calc_3d_index(pos);
+        let metadata = self.chunk.metadata.inner[index >> 1];
+        let metadata = if index & 1 == 0 { metadata & 0x0F } else { (metadata & 0xF0) >> 4 };
+        (self.chunk.block[index], metadata)
+    }
+
     /// Get the height at the given position, the Y component is ignored.
     ///
     /// The height value corresponds to the Y value of the first block above the column
@@ -128,6 +138,11 @@ impl StdbChunk {
         };
 
         server.net.send(client, OutPacket::ChunkData(new_chunk_data_packet(self, from, size)));
+
+        // Signs text is not part of the chunk data, it must be sent after.
+        for sign in StdbSignBlockEntity::filter_by_chunk_id(self.chunk_id) {
+            sign.send(server, connection_id);
+        }
     }
 
     /// Write this chunk's data to the given writer, the data is copied from the start
@@ -0,0 +1,22 @@
+use crate::autogen;
+use crate::item::ItemStack;
+
+impl From<ItemStack> for autogen::ItemStack {
+    fn from(value: ItemStack) -> Self {
+        autogen::ItemStack {
+            id: value.id,
+            size: value.size,
+            damage: value.damage,
+        }
+    }
+}
+
+impl From<autogen::ItemStack> for ItemStack {
+    fn from(value: autogen::ItemStack) -> Self {
+        ItemStack {
+            id: value.id,
+            size: value.size,
+            damage: value.damage,
+        }
+    }
+}
@@ -1,3 +1,4 @@
 pub mod glam;
 pub mod vec2;
 pub mod dvec3;
+pub mod item_stack;
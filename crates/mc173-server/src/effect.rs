@@ -0,0 +1,25 @@
+//! Sound and particle effects synchronization with the client.
+
+use crate::autogen::{StdbChunkView, StdbEffectEvent, StdbServerPlayer};
+use crate::player::ServerPlayer;
+use crate::proto::{self, OutPacket};
+use crate::server::Server;
+
+impl StdbEffectEvent {
+
+    /// Send this effect to every player observing its chunk.
+    pub fn send_to_observers(&self, server: &Server) {
+        for view in StdbChunkView::filter_by_chunk_id(self.chunk_id) {
+            if let Some(player) = StdbServerPlayer::find_by_entity_id(view.observer_id) {
+                ServerPlayer::send(server, player.connection_id, OutPacket::EffectPlay(proto::EffectPlayPacket {
+                    x: self.pos.x,
+                    y: self.pos.y as i8,
+                    z: self.pos.z,
+                    effect_id: self.effect_id,
+                    effect_data: self.effect_data,
+                }));
+            }
+        }
+    }
+
+}
@@ -0,0 +1,356 @@
+//! Entities synchronization with the client, players are synchronized separately through
+//! their entity views.
+
+use std::collections::HashSet;
+
+use glam::DVec3;
+
+use crate::autogen::{EntityKind, EntityStatus, StdbChunkView, StdbEntity, StdbEntityPickupEvent, StdbEntityStatusEvent, StdbEntityVelocityEvent, StdbHuman, StdbItemEntity, StdbKindData, StdbKindEntity, StdbLivingEntity, StdbMinecart, StdbServerPlayer};
+use crate::block;
+use crate::player::ServerPlayer;
+use crate::proto::{self, OutPacket};
+use crate::server::Server;
+use crate::{modify_look, modify_position};
+
+impl StdbEntity {
+
+    /// Return true if this entity is synchronized by this module, false for players.
+    pub fn is_synced(&self) -> bool {
+        self.kind != EntityKind::Human
+    }
+
+    /// Get the kind of object sent in the spawn packet of this entity, none if this
+    /// entity is not spawned as an object, like items.
+    fn object_kind(&self) -> Option<u8> {
+
+        let data = StdbKindEntity::find_by_entity_id(self.entity_id).map(|row| row.data);
+
+        Some(match self.kind {
+            EntityKind::Boat => 1,
+            EntityKind::Minecart => match data {
+                Some(StdbKindData::Minecart(StdbMinecart::Chest(_))) => 11,
+                Some(StdbKindData::Minecart(StdbMinecart::Furnace(_))) => 12,
+                _ => 10,
+            },
+            EntityKind::Tnt => 50,
+            EntityKind::Arrow => 60,
+            EntityKind::Snowball => 61,
+            EntityKind::Egg => 62,
+            EntityKind::Fireball => 63,
+            EntityKind::FallingBlock => match data {
+                Some(StdbKindData::FallingBlock(falling_block)) if falling_block.block_id == block::GRAVEL => 71,
+                _ => 70,
+            },
+            EntityKind::Bobber => 90,
+            // TODO: Paintings.
+            _ => return None,
+        })
+
+    }
+
+    /// Get the kind of mob sent in the spawn packet of this entity, none if this entity
+    /// is not a mob.
+    pub fn mob_kind(&self) -> Option<u8> {
+        Some(match self.kind {
+            EntityKind::Creeper => 50,
+            EntityKind::Skeleton => 51,
+            EntityKind::Spider => 52,
+            EntityKind::Giant => 53,
+            EntityKind::Zombie => 54,
+            EntityKind::Slime => 55,
+            EntityKind::Ghast => 56,
+            EntityKind::PigZombie => 57,
+            EntityKind::Pig => 90,
+            EntityKind::Sheep => 91,
+            EntityKind::Cow => 92,
+            EntityKind::Chicken => 93,
+            EntityKind::Squid => 94,
+            EntityKind::Wolf => 95,
+            _ => return None,
+        })
+    }
+
+    /// Build the metadata of this mob, as sent on spawn and on changes.
+    fn make_metadata(&self) -> Vec<proto::Metadata> {
+        let Some(kind_entity) = StdbKindEntity::find_by_entity_id(self.entity_id) else { return vec![] };
+        let living = StdbLivingEntity::find_by_entity_id(self.entity_id);
+        make_kind_metadata(&kind_entity.data, living.as_ref())
+    }
+
+    /// Send the spawn packet of this entity to the client.
+    pub fn send_spawn(&self, server: &Server, connection_id: u64) {
+
+        let pos = modify_position(self.pos.clone().as_dvec3());
+
+        let packet = if self.kind == EntityKind::Item {
+
+            let Some(item) = StdbItemEntity::find_by_entity_id(self.entity_id) else { return };
+            let vel = self.vel.clone().as_dvec3() * 128.0;
+
+            OutPacket::ItemSpawn(proto::ItemSpawnPacket {
+                entity_id: self.entity_id,
+                stack: item.stack.into(),
+                x: pos.x,
+                y: pos.y,
+                z: pos.z,
+                vx: vel.x as i8,
+                vy: vel.y as i8,
+                vz: vel.z as i8,
+            })
+
+        } else if let Some(kind) = self.object_kind() {
+
+            // Arrows and fireballs need their initial velocity.
+            let velocity = match self.kind {
+                EntityKind::Arrow | EntityKind::Fireball => {
+                    let vel = (self.vel.clone().as_dvec3().clamp(DVec3::splat(-3.9), DVec3::splat(3.9)) * 8000.0).as_ivec3();
+                    Some((vel.x as i16, vel.y as i16, vel.z as i16))
+                }
+                _ => None,
+            };
+
+            OutPacket::ObjectSpawn(proto::ObjectSpawnPacket {
+                entity_id: self.entity_id,
+                kind,
+                x: pos.x,
+                y: pos.y,
+                z: pos.z,
+                velocity,
+            })
+
+        } else if let Some(kind) = self.mob_kind() {
+
+            let look = modify_look(self.look.clone().into());
+
+            OutPacket::MobSpawn(proto::MobSpawnPacket {
+                entity_id: self.entity_id,
+                kind,
+                x: pos.x,
+                y: pos.y,
+                z: pos.z,
+                yaw: look.x as i8,
+                pitch: look.y as i8,
+                metadata: self.make_metadata(),
+            })
+
+        } else {
+            return;
+        };
+
+        ServerPlayer::send(server, connection_id, packet);
+
+    }
+
+    /// Send the spawn packet of this entity to every player observing its chunk.
+    pub fn send_spawn_to_observers(&self, server: &Server) {
+        self.for_each_observer(self.chunk_id, |connection_id| self.send_spawn(server, connection_id));
+    }
+
+    /// Send the current position of this entity to every player observing its chunk,
+    /// the client is simulating the movement so this is only used to correct it.
+    pub fn send_position_to_observers(&self, server: &Server) {
+        let pos = modify_position(self.pos.clone().as_dvec3());
+        let look = modify_look(self.look.clone().into());
+        self.for_each_observer(self.chunk_id, |connection_id| {
+            ServerPlayer::send(server, connection_id, OutPacket::EntityPositionAndLook(proto::EntityPositionAndLookPacket {
+                entity_id: self.entity_id,
+                x: pos.x,
+                y: pos.y,
+                z: pos.z,
+                yaw: look.x as i8,
+                pitch: look.y as i8,
+            }));
+        });
+    }
+
+    /// Send the current metadata of this mob to every player observing its chunk.
+    pub fn send_metadata_to_observers(&self, server: &Server) {
+        let metadata = self.make_metadata();
+        if metadata.is_empty() {
+            return;
+        }
+        self.for_each_observer(self.chunk_id, |connection_id| {
+            ServerPlayer::send(server, connection_id, OutPacket::EntityMetadata(proto::EntityMetadataPacket {
+                entity_id: self.entity_id,
+                metadata: metadata.clone(),
+            }));
+        });
+    }
+
+    /// Send the kill packet of this entity to the client.
+    pub fn send_kill(&self, server: &Server, connection_id: u64) {
+        ServerPlayer::send(server, connection_id, OutPacket::EntityKill(proto::EntityKillPacket {
+            entity_id: self.entity_id,
+        }));
+    }
+
+    /// Send the kill packet of this entity to every player observing its chunk. If the
+    /// entity has been picked up, the pickup animation is sent before, because the client
+    /// needs the entity to play it.
+    pub fn send_kill_to_observers(&self, server: &Server) {
+        for event in StdbEntityPickupEvent::filter_by_target_id(self.entity_id) {
+            event.send_to_observers(server);
+        }
+        self.for_each_observer(self.chunk_id, |connection_id| self.send_kill(server, connection_id));
+    }
+
+    /// When this entity moved from the given chunk to its current one, spawn it for
+    /// players that only observe the new chunk and kill it for players that only
+    /// observed the old one.
+    pub fn send_chunk_change_to_observers(&self, server: &Server, old_chunk_id: u32) {
+
+        let mut old_observers = HashSet::new();
+        let mut new_observers = HashSet::new();
+        self.for_each_observer(old_chunk_id, |connection_id| { old_observers.insert(connection_id); });
+        self.for_each_observer(self.chunk_id, |connection_id| { new_observers.insert(connection_id); });
+
+        for &connection_id in new_observers.difference(&old_observers) {
+            self.send_spawn(server, connection_id);
+        }
+
+        for &connection_id in old_observers.difference(&new_observers) {
+            self.send_kill(server, connection_id);
+        }
+
+    }
+
+    /// Call the given function with the connection id of each player observing the
+    /// given chunk.
+    fn for_each_observer(&self, chunk_id: u32, mut func: impl FnMut(u64)) {
+        for view in StdbChunkView::filter_by_chunk_id(chunk_id) {
+            if let Some(player) = StdbServerPlayer::find_by_entity_id(view.observer_id) {
+                func(player.connection_id);
+            }
+        }
+    }
+
+}
+
+impl StdbEntityPickupEvent {
+
+    /// Send the pickup animation to every player observing the chunk of the picked up
+    /// entity.
+    pub fn send_to_observers(&self, server: &Server) {
+        for view in StdbChunkView::filter_by_chunk_id(self.chunk_id) {
+            if let Some(player) = StdbServerPlayer::find_by_entity_id(view.observer_id) {
+                ServerPlayer::send(server, player.connection_id, OutPacket::EntityPickup(proto::EntityPickupPacket {
+                    entity_id: self.entity_id,
+                    picked_entity_id: self.target_id,
+                }));
+            }
+        }
+    }
+
+}
+
+impl StdbEntityStatusEvent {
+
+    /// Send the status animation to every player observing the chunk of the entity.
+    pub fn send_to_observers(&self, server: &Server) {
+        let status = match self.status {
+            EntityStatus::Hurt => 2,
+            EntityStatus::Dead => 3,
+        };
+        for view in StdbChunkView::filter_by_chunk_id(self.chunk_id) {
+            if let Some(player) = StdbServerPlayer::find_by_entity_id(view.observer_id) {
+                ServerPlayer::send(server, player.connection_id, OutPacket::EntityStatus(proto::EntityStatusPacket {
+                    entity_id: self.entity_id,
+                    status,
+                }));
+            }
+        }
+    }
+
+}
+
+impl StdbEntityVelocityEvent {
+
+    /// Send the new velocity to every player observing the chunk of the entity, this
+    /// includes the player itself when its own entity is knocked back.
+    pub fn send_to_observers(&self, server: &Server) {
+        let vel = (self.vel.clone().as_dvec3().clamp(DVec3::splat(-3.9), DVec3::splat(3.9)) * 8000.0).as_ivec3();
+        for view in StdbChunkView::filter_by_chunk_id(self.chunk_id) {
+            if let Some(player) = StdbServerPlayer::find_by_entity_id(view.observer_id) {
+                ServerPlayer::send(server, player.connection_id, OutPacket::EntityVelocity(proto::EntityVelocityPacket {
+                    entity_id: self.entity_id,
+                    vx: vel.x as i16,
+                    vy: vel.y as i16,
+                    vz: vel.z as i16,
+                }));
+            }
+        }
+    }
+
+}
+
+impl StdbHuman {
+
+    /// Send the sleeping state of this player to every player observing its chunk, this
+    /// includes the player itself, which is then teleported in or next to its bed.
+    pub fn send_sleep_to_observers(&self, server: &Server) {
+
+        let Some(entity) = StdbEntity::find_by_entity_id(self.entity_id) else { return };
+
+        entity.for_each_observer(entity.chunk_id, |connection_id| {
+            let packet = match (self.sleeping, &self.bed_pos) {
+                (true, Some(bed_pos)) => OutPacket::PlayerSleep(proto::PlayerSleepPacket {
+                    entity_id: self.entity_id,
+                    unused: 0,
+                    x: bed_pos.x,
+                    y: bed_pos.y as i8,
+                    z: bed_pos.z,
+                }),
+                // Animation 3 is leaving the bed.
+                _ => OutPacket::EntityAnimation(proto::AnimationPacket {
+                    entity_id: self.entity_id,
+                    animate: 3,
+                }),
+            };
+            ServerPlayer::send(server, connection_id, packet);
+        });
+
+        if let Some(player) = StdbServerPlayer::find_by_entity_id(self.entity_id) {
+            ServerPlayer::send(server, player.connection_id, OutPacket::PositionLook(proto::PositionLookPacket {
+                pos: entity.pos.clone().into(),
+                stance: entity.pos.y + 1.62,
+                look: entity.look.into(),
+                on_ground: false,
+            }));
+        }
+
+    }
+
+}
+
+/// Build the metadata of a mob from its kind data, the living component is only needed
+/// for the ghast charging state.
+pub fn make_kind_metadata(data: &StdbKindData, living: Option<&StdbLivingEntity>) -> Vec<proto::Metadata> {
+    match data {
+        StdbKindData::Ghast(_) => {
+            let charged = living.is_some_and(|living| living.attack_time > 50);
+            vec![proto::Metadata::new_byte(16, charged as _)]
+        }
+        StdbKindData::Slime(slime) => vec![
+            proto::Metadata::new_byte(16, (slime.size as i8).saturating_add(1)),
+        ],
+        StdbKindData::Pig(pig) => vec![
+            proto::Metadata::new_byte(16, pig.saddle as _),
+        ],
+        StdbKindData::Sheep(sheep) => vec![
+            proto::Metadata::new_byte(16,
+                ((sheep.sheared as i8) << 4) |
+                ((sheep.color as i8) & 15)),
+        ],
+        StdbKindData::Wolf(wolf) => vec![
+            proto::Metadata::new_byte(16,
+                (wolf.sitting as i8) |
+                ((wolf.angry as i8) << 1) |
+                ((wolf.owner.is_some() as i8) << 2)),
+        ],
+        StdbKindData::Creeper(creeper) => vec![
+            proto::Metadata::new_byte(16, if creeper.ignited_time.is_some() { 1 } else { -1 }),
+            proto::Metadata::new_byte(17, creeper.powered as _),
+        ],
+        _ => vec![],
+    }
+}
@@ -0,0 +1,26 @@
+//! Explosions synchronization with the client.
+
+use crate::autogen::{StdbChunkView, StdbExplodeEvent, StdbServerPlayer};
+use crate::player::ServerPlayer;
+use crate::proto::{self, OutPacket};
+use crate::server::Server;
+
+impl StdbExplodeEvent {
+
+    /// Send this explosion to every player observing its chunk, destroyed blocks are
+    /// not listed because they are already sent as block changes.
+    pub fn send_to_observers(&self, server: &Server) {
+        for view in StdbChunkView::filter_by_chunk_id(self.chunk_id) {
+            if let Some(player) = StdbServerPlayer::find_by_entity_id(view.observer_id) {
+                ServerPlayer::send(server, player.connection_id, OutPacket::Explosion(proto::ExplosionPacket {
+                    x: self.center.x,
+                    y: self.center.y,
+                    z: self.center.z,
+                    size: self.radius,
+                    blocks: vec![],
+                }));
+            }
+        }
+    }
+
+}
@@ -0,0 +1,52 @@
+//! Player inventory synchronization with the client.
+
+use crate::autogen::StdbPlayerInventory;
+use crate::item::ItemStack;
+use crate::player::ServerPlayer;
+use crate::proto::{self, OutPacket};
+use crate::server::Server;
+
+/// Number of slots in the player window, including crafting result, crafting grid,
+/// armor, main inventory and hotbar.
+pub const PLAYER_WINDOW_SIZE: usize = 45;
+
+/// Number of slots of the main inventory and hotbar, present at the end of every window.
+pub const MAIN_WINDOW_SIZE: usize = 36;
+
+impl StdbPlayerInventory {
+
+    /// Get the stack in the given slot of the player window (id 0), this returns none
+    /// if the slot is invalid.
+    pub fn get_window_stack(&self, slot: usize) -> Option<ItemStack> {
+        let stack = match slot {
+            0 => &self.craft_result,
+            // The 2x2 crafting grid is the top left of the 3x3 grid.
+            1 | 2 => &self.craft_inv[slot - 1],
+            3 | 4 => &self.craft_inv[slot],
+            5..=8 => &self.armor_inv[slot - 5],
+            _ => return self.get_main_window_stack(slot - 9),
+        };
+        Some(stack.clone().into())
+    }
+
+    /// Get the stack in the given slot of the main inventory, as laid out at the end of
+    /// every window: the storage rows first and then the hotbar.
+    pub fn get_main_window_stack(&self, slot: usize) -> Option<ItemStack> {
+        let stack = match slot {
+            0..=26 => &self.main_inv[slot + 9],
+            27..=35 => &self.main_inv[slot - 27],
+            _ => return None,
+        };
+        Some(stack.clone().into())
+    }
+
+    /// Send the stack in the window cursor to the client.
+    pub fn send_cursor(&self, server: &Server, connection_id: u64) {
+        ServerPlayer::send(server, connection_id, OutPacket::WindowSetItem(proto::WindowSetItemPacket {
+            window_id: 0xFF,
+            slot: -1,
+            stack: ItemStack::from(self.cursor_stack.clone()).to_non_empty(),
+        }));
+    }
+
+}
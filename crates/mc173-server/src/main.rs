@@ -12,8 +12,10 @@ use spacetimedb_sdk::{subscribe, Address, on_subscription_applied, log};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
-use tracing::warn;
-use crate::autogen::{connect, on_handle_look, on_handle_position, on_handle_position_look, on_stdb_handle_accept, on_stdb_handle_login, ChunkUpdateType, ReducerEvent, StdbBlockSetUpdate, StdbChunk, StdbChunkUpdate, StdbChunkView, StdbEntity, StdbEntityTracker, StdbEntityView, StdbHuman, StdbInLoginPacket, StdbLookPacket, StdbPositionLookPacket, StdbPositionPacket, StdbServerPlayer, StdbSetBlockEvent, StdbWeather};
+use tracing::{debug, warn};
+use crate::autogen::{connect, EntityKind, on_handle_look, on_handle_place_block, on_handle_position, on_handle_respawn, on_handle_position_look, on_stdb_handle_accept, on_stdb_handle_login, ChunkUpdateType, ReducerEvent, StdbBlockActionEvent, StdbBlockSetUpdate, StdbChestBlockEntity, StdbChunk, StdbChunkUpdate, StdbChunkView, StdbDispenserBlockEntity, StdbEffectEvent, StdbEntity, StdbEntityStatusEvent, StdbEntityTracker, StdbEntityVelocityEvent, StdbEntityView, StdbExplodeEvent, StdbFurnaceBlockEntity, StdbHuman, StdbInLoginPacket, StdbKindEntity, StdbLivingEntity, StdbLookPacket, StdbPlaceBlockPacket, StdbPlayerInventory, StdbPositionLookPacket, StdbPositionPacket, StdbServerPlayer, StdbSetBlockEvent, StdbSignBlockEntity, StdbWeather, StdbWindow, StdbWorld};
+use crate::chunk::calc_chunk_pos;
+use crate::geom::Face;
 use crate::player::ServerPlayer;
 use crate::proto::{InLoginPacket, OutPacket};
 use crate::server::Server;
@@ -44,6 +46,13 @@ mod world;
 mod convert;
 mod autogen;
 mod chunk;
+mod inventory;
+mod window;
+mod sign;
+mod block_action;
+mod effect;
+mod explode;
+mod entity;
 
 /// Storing true while the server should run.
 static RUNNING: AtomicBool = AtomicBool::new(true);
@@ -70,6 +79,27 @@ fn on_weather_updated(old_weather: &StdbWeather, new_weather: &StdbWeather, _red
     }
 }
 
+fn on_world_update(old_world: &StdbWorld, world: &StdbWorld, _reducer_event: Option<&ReducerEvent>) {
+    // The time is regularly sent to correct the clients, and immediately when it jumps,
+    // for example when the night is skipped by sleeping players.
+    if !world.time.is_multiple_of(20) && world.time == old_world.time + 1 {
+        return;
+    }
+    let s = SERVER.lock().unwrap();
+    let server = s.as_ref().unwrap();
+    for player in StdbServerPlayer::iter() {
+        if !server.clients.contains_key(&player.connection_id) {
+            continue;
+        }
+        let Some(entity) = StdbEntity::find_by_entity_id(player.entity_id) else { continue };
+        if entity.dimension_id == world.dimension_id {
+            ServerPlayer::send(server, player.connection_id, OutPacket::UpdateTime(proto::UpdateTimePacket {
+                time: world.time,
+            }));
+        }
+    }
+}
+
 fn on_chunk_inserted(chunk: &StdbChunk, _reducer_event: Option<&ReducerEvent>) {
     // println!("Received chunk inserted!");
     let mut s = SERVER.lock().unwrap();
@@ -98,6 +128,250 @@ fn on_chunk_update(
     }
 }
 
+fn on_player_inventory_update(
+    old_inventory: &StdbPlayerInventory,
+    inventory: &StdbPlayerInventory,
+    _reducer_event: Option<&ReducerEvent>,
+) {
+    let s = SERVER.lock().unwrap();
+    let server = s.as_ref().unwrap();
+
+    if let Some(player) = StdbServerPlayer::find_by_entity_id(inventory.entity_id) {
+        if let Some(window) = StdbWindow::find_by_entity_id(inventory.entity_id) {
+            window.send_changes(old_inventory, inventory, server, player.connection_id);
+        }
+    }
+}
+
+fn on_window_update(
+    old_window: &StdbWindow,
+    window: &StdbWindow,
+    reducer_event: Option<&ReducerEvent>,
+) {
+    let s = SERVER.lock().unwrap();
+    let server = s.as_ref().unwrap();
+
+    if let Some(player) = StdbServerPlayer::find_by_entity_id(window.entity_id) {
+
+        if window.id != old_window.id {
+            if window.id != 0 {
+                window.send_open(server, player.connection_id);
+            } else if !matches!(reducer_event, Some(ReducerEvent::HandleWindowClose(_))) {
+                // The window has been closed by the server, the client must be told.
+                ServerPlayer::send(server, player.connection_id, OutPacket::WindowClose(proto::WindowClosePacket {
+                    window_id: old_window.id,
+                }));
+            }
+        }
+
+        window.send_transaction(old_window, server, player.connection_id);
+
+    }
+}
+
+fn on_chest_block_entity_update(
+    old_chest: &StdbChestBlockEntity,
+    chest: &StdbChestBlockEntity,
+    _reducer_event: Option<&ReducerEvent>,
+) {
+    let s = SERVER.lock().unwrap();
+    let server = s.as_ref().unwrap();
+
+    for (index, (old_stack, stack)) in old_chest.inv.iter().zip(&chest.inv).enumerate() {
+        if old_stack != stack {
+            window::update_block_window_storage(server, &chest.pos, index, stack.clone().into());
+        }
+    }
+}
+
+fn on_dispenser_block_entity_update(
+    old_dispenser: &StdbDispenserBlockEntity,
+    dispenser: &StdbDispenserBlockEntity,
+    _reducer_event: Option<&ReducerEvent>,
+) {
+    let s = SERVER.lock().unwrap();
+    let server = s.as_ref().unwrap();
+
+    for (index, (old_stack, stack)) in old_dispenser.inv.iter().zip(&dispenser.inv).enumerate() {
+        if old_stack != stack {
+            window::update_block_window_storage(server, &dispenser.pos, index, stack.clone().into());
+        }
+    }
+}
+
+fn on_furnace_block_entity_update(
+    old_furnace: &StdbFurnaceBlockEntity,
+    furnace: &StdbFurnaceBlockEntity,
+    _reducer_event: Option<&ReducerEvent>,
+) {
+    let s = SERVER.lock().unwrap();
+    let server = s.as_ref().unwrap();
+
+    let stacks = [
+        (&old_furnace.input_stack, &furnace.input_stack),
+        (&old_furnace.fuel_stack, &furnace.fuel_stack),
+        (&old_furnace.output_stack, &furnace.output_stack),
+    ];
+
+    for (index, (old_stack, stack)) in stacks.into_iter().enumerate() {
+        if old_stack != stack {
+            window::update_block_window_storage(server, &furnace.pos, index, stack.clone().into());
+        }
+    }
+
+    let old_progress = old_furnace.progress();
+    for (bar_id, value) in furnace.progress().into_iter().enumerate() {
+        if old_progress[bar_id] != value {
+            window::update_block_window_progress(server, &furnace.pos, bar_id as u16, value);
+        }
+    }
+}
+
+fn on_sign_block_entity_update(
+    _old_sign: &StdbSignBlockEntity,
+    sign: &StdbSignBlockEntity,
+    _reducer_event: Option<&ReducerEvent>,
+) {
+    let s = SERVER.lock().unwrap();
+    let server = s.as_ref().unwrap();
+    sign.send_to_observers(server);
+}
+
+fn on_sign_block_entity_insert(sign: &StdbSignBlockEntity, _reducer_event: Option<&ReducerEvent>) {
+    let s = SERVER.lock().unwrap();
+    let server = s.as_ref().unwrap();
+    sign.send_to_observers(server);
+}
+
+fn on_block_action_event_insert(event: &StdbBlockActionEvent, _reducer_event: Option<&ReducerEvent>) {
+    let s = SERVER.lock().unwrap();
+    let server = s.as_ref().unwrap();
+    event.send_to_observers(server);
+}
+
+fn on_effect_event_insert(event: &StdbEffectEvent, _reducer_event: Option<&ReducerEvent>) {
+    let s = SERVER.lock().unwrap();
+    let server = s.as_ref().unwrap();
+    event.send_to_observers(server);
+}
+
+fn on_explode_event_insert(event: &StdbExplodeEvent, _reducer_event: Option<&ReducerEvent>) {
+    let s = SERVER.lock().unwrap();
+    let server = s.as_ref().unwrap();
+    event.send_to_observers(server);
+}
+
+fn on_entity_status_event_insert(event: &StdbEntityStatusEvent, _reducer_event: Option<&ReducerEvent>) {
+    let s = SERVER.lock().unwrap();
+    let server = s.as_ref().unwrap();
+    event.send_to_observers(server);
+}
+
+fn on_entity_velocity_event_insert(event: &StdbEntityVelocityEvent, _reducer_event: Option<&ReducerEvent>) {
+    let s = SERVER.lock().unwrap();
+    let server = s.as_ref().unwrap();
+    event.send_to_observers(server);
+}
+
+fn on_entity_insert(entity: &StdbEntity, _reducer_event: Option<&ReducerEvent>) {
+    if entity.is_synced() {
+        let s = SERVER.lock().unwrap();
+        let server = s.as_ref().unwrap();
+        entity.send_spawn_to_observers(server);
+    }
+}
+
+fn on_entity_update(
+    old_entity: &StdbEntity,
+    entity: &StdbEntity,
+    _reducer_event: Option<&ReducerEvent>,
+) {
+    if !entity.is_synced() {
+        return;
+    }
+    let s = SERVER.lock().unwrap();
+    let server = s.as_ref().unwrap();
+    if entity.chunk_id != old_entity.chunk_id {
+        entity.send_chunk_change_to_observers(server, old_entity.chunk_id);
+    }
+    // The client simulates the movement of objects by itself, their position is only
+    // corrected from time to time, but mobs are moved by the server.
+    if entity.mob_kind().is_some() {
+        if entity.lifetime.is_multiple_of(3) && (entity.pos != old_entity.pos || entity.look != old_entity.look) {
+            entity.send_position_to_observers(server);
+        }
+    } else if entity.lifetime.is_multiple_of(20) {
+        entity.send_position_to_observers(server);
+    }
+}
+
+fn on_kind_entity_update(
+    old_kind_entity: &StdbKindEntity,
+    kind_entity: &StdbKindEntity,
+    _reducer_event: Option<&ReducerEvent>,
+) {
+    // Kind data may change every tick, like the ignited time of creepers, so metadata
+    // is only sent when it actually changes.
+    if entity::make_kind_metadata(&kind_entity.data, None) == entity::make_kind_metadata(&old_kind_entity.data, None) {
+        return;
+    }
+    let Some(entity) = StdbEntity::find_by_entity_id(kind_entity.entity_id) else { return };
+    let s = SERVER.lock().unwrap();
+    let server = s.as_ref().unwrap();
+    entity.send_metadata_to_observers(server);
+}
+
+fn on_living_entity_update(
+    old_living: &StdbLivingEntity,
+    living: &StdbLivingEntity,
+    _reducer_event: Option<&ReducerEvent>,
+) {
+    // Health of players is sent to their own client.
+    if old_living.health != living.health {
+        if let Some(player) = StdbServerPlayer::find_by_entity_id(living.entity_id) {
+            let s = SERVER.lock().unwrap();
+            let server = s.as_ref().unwrap();
+            ServerPlayer::send(server, player.connection_id, OutPacket::UpdateHealth(proto::UpdateHealthPacket {
+                health: living.health.min(i16::MAX as u16) as i16,
+            }));
+            return;
+        }
+    }
+
+    // Only the ghast charging state is derived from the living component.
+    if (old_living.attack_time > 50) == (living.attack_time > 50) {
+        return;
+    }
+    let Some(entity) = StdbEntity::find_by_entity_id(living.entity_id) else { return };
+    if entity.kind != EntityKind::Ghast {
+        return;
+    }
+    let s = SERVER.lock().unwrap();
+    let server = s.as_ref().unwrap();
+    entity.send_metadata_to_observers(server);
+}
+
+fn on_human_update(
+    old_human: &StdbHuman,
+    human: &StdbHuman,
+    _reducer_event: Option<&ReducerEvent>,
+) {
+    if old_human.sleeping == human.sleeping {
+        return;
+    }
+    let s = SERVER.lock().unwrap();
+    let server = s.as_ref().unwrap();
+    human.send_sleep_to_observers(server);
+}
+
+fn on_entity_delete(entity: &StdbEntity, _reducer_event: Option<&ReducerEvent>) {
+    if entity.is_synced() {
+        let s = SERVER.lock().unwrap();
+        let server = s.as_ref().unwrap();
+        entity.send_kill_to_observers(server);
+    }
+}
+
 fn on_set_block_event_insert(event: &StdbSetBlockEvent, _reducer_event: Option<&ReducerEvent>) {
     let mut s = SERVER.lock().unwrap();
     let mut server = s.as_mut().unwrap();
@@ -192,6 +466,11 @@ fn on_chunk_view_inserted(
     let player = StdbServerPlayer::find_by_entity_id(new_view.observer_id);
     if let Some(player) = player {
         chunk.send_full(server, player.connection_id);
+        for entity in StdbEntity::filter_by_chunk_id(new_view.chunk_id) {
+            if entity.is_synced() {
+                entity.send_spawn(server, player.connection_id);
+            }
+        }
     }
 }
 
@@ -351,6 +630,79 @@ fn on_handle_look_callback(ident: &Identity, _addr: Option<Address>, status: &St
     }
 }
 
+fn on_handle_place_block_callback(_ident: &Identity, _addr: Option<Address>, status: &Status,
+                                  entity_id: &u32, packet: &StdbPlaceBlockPacket) {
+    // When the placement is refused, the client has already predicted the new block, so
+    // we send back the real blocks for the clicked position and its facing position.
+    let Status::Failed(reason) = status else { return };
+    debug!("block placement refused: {}", reason);
+
+    let mut s = SERVER.lock().unwrap();
+    let server = s.as_mut().unwrap();
+    let Some(player) = StdbServerPlayer::find_by_entity_id(*entity_id) else { return };
+
+    let pos = IVec3::new(packet.x, packet.y as i32, packet.z);
+    let face = match packet.direction {
+        0 => Face::NegY,
+        1 => Face::PosY,
+        2 => Face::NegZ,
+        3 => Face::PosZ,
+        4 => Face::NegX,
+        5 => Face::PosX,
+        _ => return,
+    };
+
+    for pos in [pos, pos + face.delta()] {
+        let Some((cx, cz)) = calc_chunk_pos(pos) else { continue };
+        let Some(chunk) = StdbChunk::find_by_chunk_id(StdbChunk::xz_to_chunk_id(cx, cz)) else { continue };
+        let (block, metadata) = chunk.get_block(pos);
+        ServerPlayer::send(server, player.connection_id, OutPacket::BlockSet(proto::BlockSetPacket {
+            x: pos.x,
+            y: pos.y as i8,
+            z: pos.z,
+            block,
+            metadata,
+        }));
+    }
+
+    // The client has also predicted the use of the stack in its hand.
+    if let (Some(inventory), Some(window)) = (
+        StdbPlayerInventory::find_by_entity_id(*entity_id),
+        StdbWindow::find_by_entity_id(*entity_id),
+    ) {
+        let slot = window.hotbar_slot(inventory.hand_slot);
+        ServerPlayer::send(server, player.connection_id, OutPacket::WindowSetItem(proto::WindowSetItemPacket {
+            window_id: window.id,
+            slot: slot as i16,
+            stack: inventory.get_main_window_stack(27 + inventory.hand_slot as usize).and_then(|stack| stack.to_non_empty()),
+        }));
+    }
+}
+
+fn on_handle_respawn_callback(_ident: &Identity, _addr: Option<Address>, status: &Status, entity_id: &u32) {
+    if let Status::Failed(reason) = status {
+        debug!("respawn refused: {}", reason);
+        return;
+    }
+
+    let s = SERVER.lock().unwrap();
+    let server = s.as_ref().unwrap();
+    let Some(player) = StdbServerPlayer::find_by_entity_id(*entity_id) else { return };
+    let Some(entity) = StdbEntity::find_by_entity_id(*entity_id) else { return };
+
+    // The client re-creates its player when receiving the respawn packet, and it is
+    // then moved to its new position.
+    ServerPlayer::send(server, player.connection_id, OutPacket::Respawn(proto::RespawnPacket {
+        dimension: entity.dimension_id as i8,
+    }));
+    ServerPlayer::send(server, player.connection_id, OutPacket::PositionLook(proto::PositionLookPacket {
+        pos: entity.pos.clone().into(),
+        stance: entity.pos.y + 1.62,
+        look: entity.look.into(),
+        on_ground: false,
+    }));
+}
+
 fn on_handle_accept_callback(ident: &Identity, _addr: Option<Address>, status: &Status, connection_id: &u64) {
     println!("stdb_handle_accept finished!")
 }
@@ -392,12 +744,33 @@ pub fn main() {
     StdbChunk::on_insert(on_chunk_inserted);
     StdbChunk::on_update(on_chunk_update);
     StdbWeather::on_update(on_weather_updated);
+    StdbWorld::on_update(on_world_update);
+    StdbPlayerInventory::on_update(on_player_inventory_update);
+    StdbWindow::on_update(on_window_update);
+    StdbChestBlockEntity::on_update(on_chest_block_entity_update);
+    StdbDispenserBlockEntity::on_update(on_dispenser_block_entity_update);
+    StdbFurnaceBlockEntity::on_update(on_furnace_block_entity_update);
+    StdbSignBlockEntity::on_insert(on_sign_block_entity_insert);
+    StdbSignBlockEntity::on_update(on_sign_block_entity_update);
     on_subscription_applied(on_subscription_applied_callback);
     StdbSetBlockEvent::on_insert(on_set_block_event_insert);
+    StdbBlockActionEvent::on_insert(on_block_action_event_insert);
+    StdbEffectEvent::on_insert(on_effect_event_insert);
+    StdbExplodeEvent::on_insert(on_explode_event_insert);
+    StdbEntityStatusEvent::on_insert(on_entity_status_event_insert);
+    StdbEntityVelocityEvent::on_insert(on_entity_velocity_event_insert);
+    StdbEntity::on_insert(on_entity_insert);
+    StdbEntity::on_update(on_entity_update);
+    StdbEntity::on_delete(on_entity_delete);
+    StdbKindEntity::on_update(on_kind_entity_update);
+    StdbLivingEntity::on_update(on_living_entity_update);
+    StdbHuman::on_update(on_human_update);
     // StdbServerPlayer::on_insert(on_stdb_server_player_inserted);
     on_handle_position(on_handle_position_callback);
     on_handle_position_look(on_handle_position_look_callback);
     on_handle_look(on_handle_look_callback);
+    on_handle_place_block(on_handle_place_block_callback);
+    on_handle_respawn(on_handle_respawn_callback);
     on_stdb_handle_accept(on_handle_accept_callback);
     on_stdb_handle_login(on_handle_login_callback);
     StdbEntityView::on_insert(on_entity_view_inserted);
@@ -9,7 +9,7 @@ use tracing::warn;
 use crate::proto::{self, Network, NetworkClient, OutPacket, InPacket};
 use crate::command::{self, CommandContext};
 use crate::{autogen, block, item};
-use crate::autogen::{StdbLookPacket, StdbPositionLookPacket, StdbPositionPacket, StdbServerPlayer};
+use crate::autogen::{StdbI32Vec3, StdbLookPacket, StdbPositionLookPacket, StdbPositionPacket, StdbServerPlayer};
 use crate::craft::CraftTracker;
 use crate::item::ItemStack;
 use crate::server::Server;
@@ -163,21 +163,26 @@ impl ServerPlayer {
                 ServerPlayer::handle_position_look(connection_id, packet),
             InPacket::BreakBlock(packet) =>
                 ServerPlayer::handle_break_block(connection_id, packet),
-            //InPacket::PlaceBlock(packet) =>
-                // self.handle_place_block(world, packet),
-            // InPacket::HandSlot(packet) =>
-            //     self.handle_hand_slot(world, packet.slot),
-            // InPacket::WindowClick(packet) =>
-            //     self.handle_window_click(world, packet),
-            // InPacket::WindowClose(packet) =>
-            //     self.handle_window_close(world, packet),
+            InPacket::PlaceBlock(packet) =>
+                ServerPlayer::handle_place_block(connection_id, packet),
+            InPacket::HandSlot(packet) =>
+                ServerPlayer::handle_hand_slot(connection_id, packet.slot),
+            InPacket::WindowClick(packet) =>
+                ServerPlayer::handle_window_click(connection_id, packet),
+            InPacket::WindowClose(packet) =>
+                ServerPlayer::handle_window_close(connection_id, packet),
+            InPacket::WindowTransaction(packet) =>
+                ServerPlayer::handle_window_transaction(connection_id, packet),
+            InPacket::UpdateSign(packet) =>
+                ServerPlayer::handle_update_sign(connection_id, packet),
             // InPacket::Animation(packet) =>
             //     self.handle_animation(world, packet),
-            // InPacket::Interact(packet) =>
-            //     self.handle_interact(world, packet),
-            // This is super specific to sneaking and sleeping
-            // InPacket::Action(packet) =>
-            //     self.handle_action(world, packet),
+            InPacket::Interact(packet) =>
+                ServerPlayer::handle_interact(connection_id, packet),
+            InPacket::Action(packet) =>
+                ServerPlayer::handle_action(connection_id, packet),
+            InPacket::Respawn(_) =>
+                ServerPlayer::handle_respawn(connection_id),
             _ => warn!("unhandled packet from connection ID: {} : {packet:?}", connection_id)
         }
 
@@ -372,6 +377,11 @@ impl ServerPlayer {
     //
     // }
 
+    fn handle_place_block(connection_id: u64, packet: proto::PlaceBlockPacket) {
+        let entity = StdbServerPlayer::find_by_connection_id(connection_id).unwrap();
+        autogen::handle_place_block(entity.entity_id, packet.into());
+    }
+
     // Handle a place block packet.
     // fn handle_place_block(&mut self, world: &mut World, packet: proto::PlaceBlockPacket) {
     //
@@ -429,6 +439,12 @@ impl ServerPlayer {
     //
     // }
 
+    /// Handle a hand slot packet.
+    fn handle_hand_slot(connection_id: u64, slot: i16) {
+        let entity = StdbServerPlayer::find_by_connection_id(connection_id).unwrap();
+        autogen::handle_hand_slot(entity.entity_id, slot);
+    }
+
     // /// Handle a hand slot packet.
     // fn handle_hand_slot(&mut self, world: &mut World, slot: i16) {
     //     if slot >= 0 && slot < 9 {
@@ -452,6 +468,32 @@ impl ServerPlayer {
     //     }
     // }
 
+    /// Handle a window click packet.
+    fn handle_window_click(connection_id: u64, packet: proto::WindowClickPacket) {
+        let entity = StdbServerPlayer::find_by_connection_id(connection_id).unwrap();
+        autogen::handle_window_click(entity.entity_id, packet.into());
+    }
+
+    /// Handle a window close packet.
+    fn handle_window_close(connection_id: u64, packet: proto::WindowClosePacket) {
+        let entity = StdbServerPlayer::find_by_connection_id(connection_id).unwrap();
+        autogen::handle_window_close(entity.entity_id, packet.window_id);
+    }
+
+    /// Handle a window transaction packet, sent by the client to acknowledge a refused
+    /// transaction.
+    fn handle_window_transaction(connection_id: u64, packet: proto::WindowTransactionPacket) {
+        let entity = StdbServerPlayer::find_by_connection_id(connection_id).unwrap();
+        autogen::handle_window_transaction(entity.entity_id, packet.into());
+    }
+
+    /// Handle an update sign packet, sent when the client is done editing a sign.
+    fn handle_update_sign(connection_id: u64, packet: proto::UpdateSignPacket) {
+        let entity = StdbServerPlayer::find_by_connection_id(connection_id).unwrap();
+        let pos = StdbI32Vec3 { x: packet.x, y: packet.y as i32, z: packet.z };
+        autogen::handle_update_sign(entity.entity_id, pos, packet.lines.to_vec());
+    }
+
     // /// Handle a window click packet.
     // fn handle_window_click(&mut self, world: &mut World, packet: proto::WindowClickPacket) {
     //
@@ -931,65 +973,38 @@ impl ServerPlayer {
     //     // TODO: Send animation to other players.
     // }
 
-    // /// Handle an entity interaction.
-    // fn handle_interact(&mut self, world: &mut World, packet: proto::InteractPacket) {
-    //     let entity = StdbEntity::find_by_entity_id(self.entity_id).unwrap();
-    //
-    //     if self.entity_id != packet.player_entity_id {
-    //         warn!("from {}, incoherent interact entity: {}, expected: {}", self.username, packet.player_entity_id, self.entity_id);
-    //     }
-    //
-    //     let Some(Entity(target_base, _)) = world.get_entity_mut(packet.target_entity_id) else {
-    //         warn!("from {}, incoherent interact entity target: {}", self.username, packet.target_entity_id);
-    //         return;
-    //     };
-    //
-    //     if entity.pos.as_dvec3().distance_squared(target_base.pos) >= 36.0 {
-    //         warn!("from {}, incoherent interact entity distance", self.username);
-    //         return;
-    //     }
-    //
-    //     let hand_stack = self.main_inv[self.hand_slot as usize];
-    //
-    //     if packet.left_click {
-    //
-    //         // TODO: Critical damage if vel.y < 0
-    //
-    //         let damage = item::attack::get_base_damage(hand_stack.id);
-    //         target_base.hurt.push(Hurt {
-    //             damage,
-    //             origin_id: Some(self.entity_id),
-    //         });
-    //
-    //     } else {
-    //
-    //     }
-    //
-    // }
+    /// Handle an entity interaction, the reach and the damage are checked by the module.
+    fn handle_interact(connection_id: u64, packet: proto::InteractPacket) {
 
-    // /// Handle an action packet from the player.
-    // fn handle_action(&mut self, world: &mut World, packet: proto::ActionPacket) {
-    //
-    //     if self.entity_id != packet.entity_id {
-    //         warn!("from {}, incoherent player entity: {}, expected: {}", self.username, packet.entity_id, self.entity_id);
-    //     }
-    //
-    //     // A player action is only relevant on human entities, ignore if the player is
-    //     // bound to any other entity kind.
-    //     let Some(Entity(_, BaseKind::Living(_, LivingKind::Human(human)))) = world.get_entity_mut(self.entity_id) else {
-    //         return;
-    //     };
-    //
-    //     match packet.state {
-    //         1 | 2 => {
-    //             human.sneaking = packet.state == 1;
-    //             world.push_event(Event::Entity { id: self.entity_id, inner: EntityEvent::Metadata });
-    //         }
-    //         3 => todo!("wake up..."),
-    //         _ => warn!("from {}, invalid action state: {}", self.username, packet.state)
-    //     }
-    //
-    // }
+        let player = StdbServerPlayer::find_by_connection_id(connection_id).unwrap();
+
+        if player.entity_id != packet.player_entity_id {
+            warn!("from {}, incoherent interact entity: {}, expected: {}", player.username, packet.player_entity_id, player.entity_id);
+        }
+
+        autogen::handle_interact(player.entity_id, packet.target_entity_id, packet.left_click);
+
+    }
+
+    /// Handle a respawn packet, the client is only respawned once the module has
+    /// re-created its player entity.
+    fn handle_respawn(connection_id: u64) {
+        let player = StdbServerPlayer::find_by_connection_id(connection_id).unwrap();
+        autogen::handle_respawn(player.entity_id);
+    }
+
+    /// Handle an action packet from the player, used to sneak and to leave the bed.
+    fn handle_action(connection_id: u64, packet: proto::ActionPacket) {
+
+        let player = StdbServerPlayer::find_by_connection_id(connection_id).unwrap();
+
+        if player.entity_id != packet.entity_id {
+            warn!("from {}, incoherent player entity: {}, expected: {}", player.username, packet.entity_id, player.entity_id);
+        }
+
+        autogen::handle_action(player.entity_id, packet.state);
+
+    }
 
     // /// Open the given window kind on client-side by sending appropriate packet. A new
     // /// window id is automatically associated to that window.
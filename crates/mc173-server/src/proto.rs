@@ -316,6 +316,17 @@ pub struct PlaceBlockPacket {
     pub stack: Option<ItemStack>,
 }
 
+impl From<PlaceBlockPacket> for autogen::StdbPlaceBlockPacket {
+    fn from(value: PlaceBlockPacket) -> Self {
+        autogen::StdbPlaceBlockPacket {
+            x: value.x,
+            y: value.y,
+            z: value.z,
+            direction: value.direction,
+        }
+    }
+}
+
 /// Packet 16
 #[derive(Debug, Clone)]
 pub struct HandSlotPacket {
@@ -663,6 +674,19 @@ pub struct WindowClickPacket {
     pub stack: Option<ItemStack>,
 }
 
+impl From<WindowClickPacket> for autogen::StdbWindowClickPacket {
+    fn from(value: WindowClickPacket) -> Self {
+        autogen::StdbWindowClickPacket {
+            window_id: value.window_id,
+            slot: value.slot,
+            right_click: value.right_click,
+            shift_click: value.shift_click,
+            transaction_id: value.transaction_id,
+            stack: value.stack.unwrap_or(ItemStack::EMPTY).into(),
+        }
+    }
+}
+
 /// Packet 103
 #[derive(Debug, Clone)]
 pub struct WindowSetItemPacket {
@@ -700,6 +724,26 @@ pub struct WindowTransactionPacket {
     pub accepted: bool,
 }
 
+impl From<WindowTransactionPacket> for autogen::StdbWindowTransactionPacket {
+    fn from(value: WindowTransactionPacket) -> Self {
+        autogen::StdbWindowTransactionPacket {
+            window_id: value.window_id,
+            transaction_id: value.transaction_id,
+            accepted: value.accepted,
+        }
+    }
+}
+
+impl From<autogen::StdbWindowTransactionPacket> for WindowTransactionPacket {
+    fn from(value: autogen::StdbWindowTransactionPacket) -> Self {
+        WindowTransactionPacket {
+            window_id: value.window_id,
+            transaction_id: value.transaction_id,
+            accepted: value.accepted,
+        }
+    }
+}
+
 /// Packet 130
 #[derive(Debug, Clone)]
 pub struct UpdateSignPacket {
@@ -732,13 +776,13 @@ pub struct DisconnectPacket {
 }
 
 /// A metadata for entity.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Metadata {
     pub id: u8,
     pub kind: MetadataKind,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum MetadataKind {
     Byte(i8),
     Short(i16),
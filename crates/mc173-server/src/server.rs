@@ -8,7 +8,7 @@ use std::io;
 use glam::{DVec3, Vec2};
 
 use tracing::{warn, info};
-use crate::autogen::{stdb_handle_accept, stdb_handle_lost, StdbClientState, StdbConnectionStatus, StdbEntity, StdbInLoginPacket, StdbServerPlayer, StdbServerWorld, StdbTime, StdbWeather, StdbWorld};
+use crate::autogen::{stdb_handle_accept, stdb_handle_lost, StdbClientState, StdbConnectionStatus, StdbEntity, StdbInLoginPacket, StdbLivingEntity, StdbServerPlayer, StdbServerWorld, StdbTime, StdbWeather, StdbWindow, StdbWorld};
 use crate::{autogen, config};
 use crate::player::ServerPlayer;
 use crate::proto::{self, Network, NetworkEvent, NetworkClient, InPacket, OutPacket};
@@ -254,6 +254,13 @@ impl Server {
             time: StdbTime::find_by_id(0).unwrap().time
         }));
 
+        // The health is persisted, so it must be sent for the client to be up to date.
+        if let Some(living) = StdbLivingEntity::find_by_entity_id(entity.entity_id) {
+            self.net.send(client, OutPacket::UpdateHealth(proto::UpdateHealthPacket {
+                health: living.health.min(i16::MAX as u16) as i16,
+            }));
+        }
+
         if StdbWeather::find_by_dimension_id(entity.dimension_id).unwrap().weather != autogen::Weather::Clear {
         // if world.world.get_weather() != Weather::Clear {
             self.net.send(client, OutPacket::Notification(proto::NotificationPacket {
@@ -261,6 +268,12 @@ impl Server {
             }));
         }
 
+        // The inventory is created by the login reducer, or restored if the player
+        // already played.
+        if let Some(window) = StdbWindow::find_by_entity_id(entity.entity_id) {
+            window.send_full(self, connection_id);
+        }
+
         // NOTE(jdetter): This is done in stdb
         // Get the offline player, if not existing we create a new one with the
         // let offline_player = self.offline_players.entry(new_player.username.clone())
@@ -0,0 +1,36 @@
+//! Sign synchronization with the client.
+
+use crate::autogen::{StdbChunkView, StdbServerPlayer, StdbSignBlockEntity};
+use crate::player::ServerPlayer;
+use crate::proto::{self, OutPacket};
+use crate::server::Server;
+
+impl StdbSignBlockEntity {
+
+    /// Send the text of this sign to the client.
+    pub fn send(&self, server: &Server, connection_id: u64) {
+
+        let mut lines: [String; 4] = Default::default();
+        for (line, text) in lines.iter_mut().zip(&self.lines) {
+            line.clone_from(text);
+        }
+
+        ServerPlayer::send(server, connection_id, OutPacket::UpdateSign(proto::UpdateSignPacket {
+            x: self.pos.x,
+            y: self.pos.y as i16,
+            z: self.pos.z,
+            lines: Box::new(lines),
+        }));
+
+    }
+
+    /// Send the text of this sign to every player observing its chunk.
+    pub fn send_to_observers(&self, server: &Server) {
+        for view in StdbChunkView::filter_by_chunk_id(self.chunk_id) {
+            if let Some(player) = StdbServerPlayer::find_by_entity_id(view.observer_id) {
+                self.send(server, player.connection_id);
+            }
+        }
+    }
+
+}
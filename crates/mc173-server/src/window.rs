@@ -0,0 +1,289 @@
+//! Player window synchronization with the client.
+
+use spacetimedb_sdk::table::TableType;
+
+use crate::autogen::{StdbChestBlockEntity, StdbDispenserBlockEntity, StdbFurnaceBlockEntity, StdbI32Vec3, StdbPlayerInventory, StdbServerPlayer, StdbWindow, StdbWindowKind};
+use crate::inventory::{MAIN_WINDOW_SIZE, PLAYER_WINDOW_SIZE};
+use crate::item::ItemStack;
+use crate::player::ServerPlayer;
+use crate::proto::{self, OutPacket};
+use crate::server::Server;
+
+/// Number of slots in a single chest, a large chest has two of them.
+pub const CHEST_INV_SIZE: usize = 27;
+/// Number of slots in a dispenser.
+pub const DISPENSER_INV_SIZE: usize = 9;
+
+impl StdbWindow {
+
+    /// Get the number of slots in this window, including the main inventory.
+    pub fn size(&self) -> usize {
+        match self.kind {
+            StdbWindowKind::Player => PLAYER_WINDOW_SIZE,
+            StdbWindowKind::CraftingTable(_) => 10 + MAIN_WINDOW_SIZE,
+            StdbWindowKind::Chest(ref pos) => pos.len() * CHEST_INV_SIZE + MAIN_WINDOW_SIZE,
+            StdbWindowKind::Furnace(_) => 3 + MAIN_WINDOW_SIZE,
+            StdbWindowKind::Dispenser(_) => DISPENSER_INV_SIZE + MAIN_WINDOW_SIZE,
+        }
+    }
+
+    /// Load the chest block entities referenced by this window, in window order. Chests
+    /// that no longer exist are not returned.
+    pub fn load_chests(&self) -> Vec<StdbChestBlockEntity> {
+        match self.kind {
+            StdbWindowKind::Chest(ref pos) => pos.iter()
+                .filter_map(|pos| StdbChestBlockEntity::find(|chest| &chest.pos == pos))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Load the furnace block entity referenced by this window, if any.
+    pub fn load_furnace(&self) -> Option<StdbFurnaceBlockEntity> {
+        match self.kind {
+            StdbWindowKind::Furnace(ref pos) => StdbFurnaceBlockEntity::find(|furnace| &furnace.pos == pos),
+            _ => None,
+        }
+    }
+
+    /// Load the dispenser block entity referenced by this window, if any.
+    pub fn load_dispenser(&self) -> Option<StdbDispenserBlockEntity> {
+        match self.kind {
+            StdbWindowKind::Dispenser(ref pos) => StdbDispenserBlockEntity::find(|dispenser| &dispenser.pos == pos),
+            _ => None,
+        }
+    }
+
+    /// Get the window slot where the given hotbar slot is.
+    pub fn hotbar_slot(&self, hand_slot: u8) -> usize {
+        self.size() - 9 + hand_slot as usize
+    }
+
+    /// Get the stack in the given slot of this window, this returns none if the slot
+    /// is invalid. The block entities must be those loaded for this window.
+    pub fn get_stack(&self, inventory: &StdbPlayerInventory, block_entities: &WindowBlockEntities, slot: usize) -> Option<ItemStack> {
+        match self.kind {
+            StdbWindowKind::Player => inventory.get_window_stack(slot),
+            StdbWindowKind::CraftingTable(_) => {
+                let stack = match slot {
+                    0 => &inventory.craft_result,
+                    1..=9 => &inventory.craft_inv[slot - 1],
+                    _ => return inventory.get_main_window_stack(slot - 10),
+                };
+                Some(stack.clone().into())
+            }
+            StdbWindowKind::Chest(ref pos) => {
+                let chests_size = pos.len() * CHEST_INV_SIZE;
+                if slot < chests_size {
+                    let stack = block_entities.chests.get(slot / CHEST_INV_SIZE)
+                        .map(|chest| chest.inv[slot % CHEST_INV_SIZE].clone().into())
+                        .unwrap_or(ItemStack::EMPTY);
+                    Some(stack)
+                } else {
+                    inventory.get_main_window_stack(slot - chests_size)
+                }
+            }
+            StdbWindowKind::Furnace(_) => {
+                let furnace = block_entities.furnace.as_ref();
+                let stack = match slot {
+                    0 => furnace.map(|furnace| &furnace.input_stack),
+                    1 => furnace.map(|furnace| &furnace.fuel_stack),
+                    2 => furnace.map(|furnace| &furnace.output_stack),
+                    _ => return inventory.get_main_window_stack(slot - 3),
+                };
+                Some(stack.map(|stack| stack.clone().into()).unwrap_or(ItemStack::EMPTY))
+            }
+            StdbWindowKind::Dispenser(_) => {
+                if slot < DISPENSER_INV_SIZE {
+                    let stack = block_entities.dispenser.as_ref()
+                        .map(|dispenser| dispenser.inv[slot].clone().into())
+                        .unwrap_or(ItemStack::EMPTY);
+                    Some(stack)
+                } else {
+                    inventory.get_main_window_stack(slot - DISPENSER_INV_SIZE)
+                }
+            }
+        }
+    }
+
+    /// Send to the client the packet opening this window, followed by its content. This
+    /// must not be called for the player window, which is never opened explicitly.
+    pub fn send_open(&self, server: &Server, connection_id: u64) {
+
+        let (inventory_type, title, slots_count) = match self.kind {
+            StdbWindowKind::Player => return,
+            StdbWindowKind::CraftingTable(_) => (1, "Crafting", 9),
+            StdbWindowKind::Chest(ref pos) => {
+                let title = if pos.len() > 1 { "Large chest" } else { "Chest" };
+                (0, title, (pos.len() * CHEST_INV_SIZE) as u8)
+            }
+            StdbWindowKind::Furnace(_) => (2, "Furnace", 3),
+            StdbWindowKind::Dispenser(_) => (3, "Dispenser", DISPENSER_INV_SIZE as u8),
+        };
+
+        ServerPlayer::send(server, connection_id, OutPacket::WindowOpen(proto::WindowOpenPacket {
+            window_id: self.id,
+            inventory_type,
+            title: title.to_string(),
+            slots_count,
+        }));
+
+        self.send_full(server, connection_id);
+
+    }
+
+    /// Send to the client the answer of the last transaction if it changed since the
+    /// given previous window. If the transaction has been refused, the whole window is
+    /// sent again so that the client gets back in sync.
+    pub fn send_transaction(&self, old: &StdbWindow, server: &Server, connection_id: u64) {
+
+        let Some(transaction) = &self.transaction else { return };
+        if old.transaction.as_ref() == Some(transaction) {
+            return;
+        }
+
+        ServerPlayer::send(server, connection_id, OutPacket::WindowTransaction(transaction.clone().into()));
+
+        if !transaction.accepted {
+            self.send_full(server, connection_id);
+        }
+
+    }
+
+    /// Send the whole content of this window to the client.
+    pub fn send_full(&self, server: &Server, connection_id: u64) {
+
+        let Some(inventory) = StdbPlayerInventory::find_by_entity_id(self.entity_id) else { return };
+        let block_entities = WindowBlockEntities::load(self);
+
+        let stacks = (0..self.size())
+            .map(|slot| self.get_stack(&inventory, &block_entities, slot).unwrap().to_non_empty())
+            .collect();
+
+        ServerPlayer::send(server, connection_id, OutPacket::WindowItems(proto::WindowItemsPacket {
+            window_id: self.id,
+            stacks,
+        }));
+
+        if let Some(furnace) = &block_entities.furnace {
+            for (bar_id, value) in furnace.progress().into_iter().enumerate() {
+                self.send_progress(server, connection_id, bar_id as u16, value);
+            }
+        }
+
+        inventory.send_cursor(server, connection_id);
+
+    }
+
+    /// Send to the client only the slots of this window that changed between the given
+    /// previous and new inventories.
+    pub fn send_changes(&self, old: &StdbPlayerInventory, inventory: &StdbPlayerInventory, server: &Server, connection_id: u64) {
+
+        // NOTE: The block entities are the same for both inventories, they are only
+        // loaded so that slots are resolved the same way.
+        let block_entities = WindowBlockEntities::load(self);
+
+        for slot in 0..self.size() {
+            let stack = self.get_stack(inventory, &block_entities, slot).unwrap();
+            if self.get_stack(old, &block_entities, slot).unwrap() != stack {
+                ServerPlayer::send(server, connection_id, OutPacket::WindowSetItem(proto::WindowSetItemPacket {
+                    window_id: self.id,
+                    slot: slot as i16,
+                    stack: stack.to_non_empty(),
+                }));
+            }
+        }
+
+        if old.cursor_stack != inventory.cursor_stack {
+            inventory.send_cursor(server, connection_id);
+        }
+
+    }
+
+    /// Send to the client the new value of a progress bar of this window.
+    fn send_progress(&self, server: &Server, connection_id: u64, bar_id: u16, value: i16) {
+        ServerPlayer::send(server, connection_id, OutPacket::WindowProgressBar(proto::WindowProgressBarPacket {
+            window_id: self.id,
+            bar_id,
+            value,
+        }));
+    }
+
+}
+
+impl StdbFurnaceBlockEntity {
+
+    /// Get the values of the furnace window progress bars: the cook time, the remaining
+    /// burn time and the max burn time.
+    pub fn progress(&self) -> [i16; 3] {
+        [self.smelt_ticks as i16, self.burn_remaining_ticks as i16, self.burn_max_ticks as i16]
+    }
+
+}
+
+/// The block entities referenced by a window, used to resolve its slots.
+pub struct WindowBlockEntities {
+    /// The chests of a chest window, in window order, those that no longer exist are
+    /// not present.
+    pub chests: Vec<StdbChestBlockEntity>,
+    /// The furnace of a furnace window.
+    pub furnace: Option<StdbFurnaceBlockEntity>,
+    /// The dispenser of a dispenser window.
+    pub dispenser: Option<StdbDispenserBlockEntity>,
+}
+
+impl WindowBlockEntities {
+
+    /// Load the block entities referenced by the given window.
+    pub fn load(window: &StdbWindow) -> Self {
+        Self {
+            chests: window.load_chests(),
+            furnace: window.load_furnace(),
+            dispenser: window.load_dispenser(),
+        }
+    }
+
+}
+
+/// Send the new stack at the given index of a block storage, such as chests, to every
+/// player currently viewing a window that references this block.
+pub fn update_block_window_storage(server: &Server, pos: &StdbI32Vec3, index: usize, stack: ItemStack) {
+    for window in StdbWindow::iter() {
+
+        let slot = match window.kind {
+            StdbWindowKind::Chest(ref chest_pos) => {
+                let Some(row) = chest_pos.iter().position(|chest_pos| chest_pos == pos) else { continue };
+                row * CHEST_INV_SIZE + index
+            }
+            StdbWindowKind::Furnace(ref block_pos) |
+            StdbWindowKind::Dispenser(ref block_pos) if block_pos == pos => index,
+            _ => continue,
+        };
+
+        if let Some(player) = StdbServerPlayer::find_by_entity_id(window.entity_id) {
+            ServerPlayer::send(server, player.connection_id, OutPacket::WindowSetItem(proto::WindowSetItemPacket {
+                window_id: window.id,
+                slot: slot as i16,
+                stack: stack.to_non_empty(),
+            }));
+        }
+
+    }
+}
+
+/// Send the new value of a progress bar of a block, such as furnaces, to every player
+/// currently viewing a window that references this block.
+pub fn update_block_window_progress(server: &Server, pos: &StdbI32Vec3, bar_id: u16, value: i16) {
+    for window in StdbWindow::iter() {
+
+        match window.kind {
+            StdbWindowKind::Furnace(ref furnace_pos) if furnace_pos == pos => {}
+            _ => continue,
+        }
+
+        if let Some(player) = StdbServerPlayer::find_by_entity_id(window.entity_id) {
+            window.send_progress(server, player.connection_id, bar_id, value);
+        }
+
+    }
+}
@@ -6,13 +6,14 @@ use glam::{DVec3, Vec2, IVec3};
 use spacetimedb::{query, spacetimedb, SpacetimeType};
 use mc173_module::dvec3::StdbDVec3;
 use mc173_module::entity::{Entity, EntityKind};
+use mc173_module::entity::component::StdbEntity;
 use mc173_module::i16vec3::StdbI16Vec3;
 use mc173_module::i32vec3::StdbI32Vec3;
 use mc173_module::i8vec3::StdbI8Vec2;
 use mc173_module::world::StdbWorld;
 use crate::proto::{OutPacket, self};
 use crate::config;
-use crate::player::{StdbEntity, StdbServerPlayer};
+use crate::player::StdbServerPlayer;
 
 /// This structure tracks every entity spawned in the world and save their previous
 /// position/look (and motion for some entities). It handle allows sending the right
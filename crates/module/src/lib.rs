@@ -14,14 +14,14 @@
 
 use std::process::exit;
 use std::time::Duration;
-use glam::{DVec3, IVec3};
-use mc173_module::world::{StdbWorld, DIMENSION_OVERWORLD};
+use glam::{DVec3, IVec3, Vec2};
+use mc173_module::world::{EntityStatus, StdbEntityPickupEvent, StdbEntityStatusEvent, StdbWorld, DIMENSION_OVERWORLD};
 use spacetimedb::{ReducerContext, schedule, spacetimedb, SpacetimeType, Timestamp};
 use mc173_module::{block, item};
 use mc173_module::chunk::calc_entity_chunk_pos;
 use mc173_module::chunk_cache::ChunkCache;
-use mc173_module::dvec3::StdbDVec3;
-use mc173_module::entity::StdbHuman;
+use mc173_module::entity::{BaseKind, Entity, Human};
+use mc173_module::entity::component::StdbEntity;
 use mc173_module::geom::Face;
 use mc173_module::i16vec3::StdbI16Vec3;
 use mc173_module::i32vec3::StdbI32Vec3;
@@ -29,12 +29,11 @@ use mc173_module::i8vec3::StdbI8Vec2;
 use mc173_module::stdb::chunk::{StdbBreakBlockPacket, BreakingBlock, StdbBreakingBlock, StdbTime, StdbChunkUpdate, StdbChunk, ChunkUpdateType};
 use mc173_module::stdb::weather::StdbWeather;
 use mc173_module::storage::ChunkStorage;
-use mc173_module::vec2::StdbVec2;
 use crate::config::SPAWN_POS;
 use crate::entity::{StdbEntityTracker, StdbEntityTrackerUpdateType};
 use crate::offline::StdbOfflinePlayer;
-use crate::player::{StdbClientState, StdbConnectionStatus, StdbEntity, StdbOfflineServerPlayer, StdbPlayingState, StdbServerPlayer};
-use crate::proto::{StdbLookPacket, StdbPositionLookPacket, StdbPositionPacket};
+use crate::player::{StdbClientState, StdbConnectionStatus, StdbOfflineServerPlayer, StdbPlayerInventory, StdbPlayingState, StdbServerPlayer, StdbWindow};
+use crate::proto::{StdbLookPacket, StdbPlaceBlockPacket, StdbPositionLookPacket, StdbPositionPacket, StdbWindowClickPacket, StdbWindowTransactionPacket};
 use crate::world::{StdbServerWorld, StdbTickMode};
 
 pub mod player;
@@ -126,30 +125,26 @@ fn stdb_handle_login(connection_id: u64, packet: proto::StdbInLoginPacket) {
         let player = StdbServerPlayer::insert(existing_player.clone()).unwrap();
         (StdbEntity::filter_by_entity_id(&existing_player.entity_id).unwrap(), player)
     } else {
-        let new_entity = StdbEntity::insert(StdbEntity {
-            entity_id: 0,
-            on_ground: false,
-            pos: std::convert::Into::<StdbDVec3>::into(spawn_pos).clone(),
-            look: StdbVec2 {
-                x: 1.0,
-                y: 0.0,
-            },
-            dimension_id: DIMENSION_OVERWORLD,
-        }).unwrap();
+        // Player entities are stored in the world like any other entity.
+        let mut world = StdbWorld::filter_by_dimension_id(&DIMENSION_OVERWORLD).unwrap();
+        let entity_id = world.spawn_entity(Human::new_with(|base, living, human| {
+            base.pos = spawn_pos;
+            base.look = Vec2::new(1.0, 0.0);
+            base.can_pickup = true;
+            living.artificial = true;
+            human.username = packet.username.clone();
+        }));
+        StdbWorld::update_by_dimension_id(&DIMENSION_OVERWORLD, world);
+
+        let new_entity = StdbEntity::filter_by_entity_id(&entity_id).unwrap();
 
         let player = StdbServerPlayer::insert(StdbServerPlayer {
-            entity_id: new_entity.entity_id.clone(),
+            entity_id,
             username: packet.username.clone(),
             connection_id,
             spawn_pos: spawn_pos.into(),
         }).unwrap();
 
-        StdbHuman::insert(StdbHuman {
-            entity_id: new_entity.entity_id.clone(),
-            username: packet.username,
-            sleeping: false,
-            sneaking: false,
-        }).unwrap();
         (new_entity, player)
     };
 
@@ -223,6 +218,16 @@ fn stdb_handle_login(connection_id: u64, packet: proto::StdbInLoginPacket) {
         }).unwrap();
     }
 
+    // The inventory is kept when the player leaves, so only create it the first time.
+    if StdbPlayerInventory::filter_by_entity_id(&player.entity_id).is_none() {
+        StdbPlayerInventory::insert(StdbPlayerInventory::new(player.entity_id)).unwrap();
+    }
+
+    // The client always starts with its inventory window opened.
+    if StdbWindow::filter_by_entity_id(&player.entity_id).is_none() {
+        StdbWindow::insert(StdbWindow::new(player.entity_id)).unwrap();
+    }
+
     // Finally insert the player tracker.
     // let server_player = ServerPlayer::new(&self.net, client, entity_id, packet.username, &offline_player);
     // let player_index = world.handle_player_join(server_player);
@@ -262,6 +267,19 @@ pub fn stdb_handle_lost(connection_id: u64, lost: bool) -> Result<(), String> {
         let mut world = StdbServerWorld::filter_by_dimension_id(&playing_state.dimension_id).ok_or(
             format!("Failed to find world with dimension ID: {}", &playing_state.dimension_id))?;
         // let world = &mut self.worlds[playing_state.dimension_id];
+
+        // The client restarts with its inventory window, so any opened window is closed.
+        if let (Some(mut stdb_world), Some(mut window), Some(mut inventory)) = (
+            StdbWorld::filter_by_dimension_id(&playing_state.dimension_id),
+            StdbWindow::filter_by_entity_id(&player.entity_id),
+            StdbPlayerInventory::filter_by_entity_id(&player.entity_id),
+        ) {
+            player.close_window(&mut stdb_world, &mut window, &mut inventory);
+            StdbWorld::update_by_dimension_id(&playing_state.dimension_id, stdb_world);
+            StdbPlayerInventory::update_by_entity_id(&player.entity_id, inventory);
+            StdbWindow::update_by_entity_id(&player.entity_id, window);
+        }
+
         world.handle_player_leave(player, lost);
         // if let Some(swapped_player) = world.handle_player_leave(player_index, true) {
         //     // If a player has been swapped in place of the removed one, update the
@@ -338,6 +356,38 @@ pub fn tick_world(world: &mut StdbWorld, state: &mut StdbServerWorld, cache: &mu
         }
     }
 
+    // Apply the pickups raised while ticking, the ones that failed are not played.
+    for event in StdbEntityPickupEvent::filter_by_dimension_id(&world.dimension_id).collect::<Vec<_>>() {
+        if !state.handle_entity_pickup(world, event.entity_id, event.target_id) {
+            StdbEntityPickupEvent::delete_by_event_id(&event.event_id);
+        }
+    }
+
+    // Players that died while ticking drop their inventory.
+    for event in StdbEntityStatusEvent::filter_by_dimension_id(&world.dimension_id).filter(|event| event.status == EntityStatus::Dead).collect::<Vec<_>>() {
+        if let Some(player) = StdbServerPlayer::filter_by_entity_id(&event.entity_id) {
+            state.handle_player_death(world, &player);
+        }
+    }
+
+    // Windows referencing a removed block entity or crafting table are closed.
+    for (id, _) in world.iter_player_entities().collect::<Vec<_>>() {
+        if let Some(player) = StdbServerPlayer::filter_by_entity_id(&id) {
+            player.check_window(world, cache);
+        }
+    }
+
+    // Dead players are hidden once their death animation is over, like dead mobs.
+    for (id, entity) in world.iter_player_entities().collect::<Vec<_>>() {
+        if let Entity(_, BaseKind::Living(living, _)) = entity {
+            if living.health == 0 && living.death_time == 20 {
+                if let Some(player) = StdbServerPlayer::filter_by_entity_id(&id) {
+                    state.handle_player_hide(&player);
+                }
+            }
+        }
+    }
+
     // Swap events out in order to proceed them.
     // let mut events = self.world.swap_events(None).expect("events should be enabled");
     // self.state.events_count.push(events.len() as f32, 0.001);
@@ -573,7 +623,7 @@ pub fn handle_break_block(entity_id: u32, packet: StdbBreakBlockPacket) {
     // NOTE: Instead of just grabbing an arbirary world, we should use the world that the player is in
     let entity = StdbEntity::filter_by_entity_id(&entity_id).unwrap();
     let player = StdbServerPlayer::filter_by_entity_id(&entity_id).unwrap();
-    let username = player.username;
+    let username = player.username.clone();
     let mut world = StdbWorld::filter_by_dimension_id(&entity.dimension_id).unwrap();
 
     let face = match packet.face {
@@ -675,24 +725,101 @@ pub fn handle_break_block(entity_id: u32, packet: StdbBreakBlockPacket) {
         }
     } else if packet.status == 4 {
         // Drop the selected item.
-
-        // if !stack.is_empty() {
-        //
-        //     stack.size -= 1;
-        //     self.main_inv[self.hand_slot as usize] = stack.to_non_empty().unwrap_or_default();
-        //
-        //     self.send(OutPacket::WindowSetItem(proto::WindowSetItemPacket {
-        //         window_id: 0,
-        //         slot: 36 + self.hand_slot as i16,
-        //         stack: stack.to_non_empty(),
-        //     }));
-        //
-        //     self.drop_stack(world, stack.with_size(1), false);
-        //
-        // }
+        let mut inventory = StdbPlayerInventory::filter_by_entity_id(&entity_id).unwrap();
+        let mut stack = inventory.hand_stack();
+        if !stack.is_empty() {
+            stack.size -= 1;
+            inventory.main_inv[inventory.hand_slot as usize] = stack.to_non_empty().unwrap_or_default();
+            StdbPlayerInventory::update_by_entity_id(&entity_id, inventory);
+            player.drop_stack(&mut world, stack.with_size(1), false);
+        }
     }
 
+    // Breaking blocks and dropping items may spawn entities.
+    StdbWorld::update_by_dimension_id(&entity.dimension_id, world);
+    cache.apply();
+}
+
+/// Handle a place block packet, the reducer fails if the placement has been refused.
+#[spacetimedb(reducer)]
+pub fn handle_place_block(entity_id: u32, packet: StdbPlaceBlockPacket) -> Result<(), String> {
+    let mut cache = ChunkCache::new();
+    let player = StdbServerPlayer::filter_by_entity_id(&entity_id).ok_or(
+        format!("Could not find player with id: {}", entity_id))?;
+    player.handle_place_block(packet, &mut cache)?;
+    cache.apply();
+    Ok(())
+}
+
+/// Handle a hand slot packet, the reducer fails if the slot is invalid.
+#[spacetimedb(reducer)]
+pub fn handle_hand_slot(entity_id: u32, slot: i16) -> Result<(), String> {
+    let player = StdbServerPlayer::filter_by_entity_id(&entity_id).ok_or(
+        format!("Could not find player with id: {}", entity_id))?;
+    player.handle_hand_slot(slot)
+}
+
+/// Handle an interact packet, the reducer fails if the target entity is invalid.
+#[spacetimedb(reducer)]
+pub fn handle_interact(entity_id: u32, target_id: u32, left_click: bool) -> Result<(), String> {
+    let player = StdbServerPlayer::filter_by_entity_id(&entity_id).ok_or(
+        format!("Could not find player with id: {}", entity_id))?;
+    player.handle_interact(target_id, left_click)
+}
+
+/// Handle an action packet, the reducer fails if the action state is invalid.
+#[spacetimedb(reducer)]
+pub fn handle_action(entity_id: u32, state: u8) -> Result<(), String> {
+    let mut cache = ChunkCache::new();
+    let player = StdbServerPlayer::filter_by_entity_id(&entity_id).ok_or(
+        format!("Could not find player with id: {}", entity_id))?;
+    player.handle_action(state, &mut cache)?;
     cache.apply();
+    Ok(())
+}
+
+/// Handle a respawn packet, the reducer fails if the player is not dead.
+#[spacetimedb(reducer)]
+pub fn handle_respawn(entity_id: u32) -> Result<(), String> {
+    let mut cache = ChunkCache::new();
+    let player = StdbServerPlayer::filter_by_entity_id(&entity_id).ok_or(
+        format!("Could not find player with id: {}", entity_id))?;
+    player.handle_respawn(&mut cache)?;
+    cache.apply();
+    Ok(())
+}
+
+/// Handle a window click packet, the transaction result is stored in the window table.
+#[spacetimedb(reducer)]
+pub fn handle_window_click(entity_id: u32, packet: StdbWindowClickPacket) -> Result<(), String> {
+    let player = StdbServerPlayer::filter_by_entity_id(&entity_id).ok_or(
+        format!("Could not find player with id: {}", entity_id))?;
+    player.handle_window_click(packet)
+}
+
+/// Handle a window close packet, the crafting grid and cursor are given back to the player.
+#[spacetimedb(reducer)]
+pub fn handle_window_close(entity_id: u32, window_id: u8) -> Result<(), String> {
+    let player = StdbServerPlayer::filter_by_entity_id(&entity_id).ok_or(
+        format!("Could not find player with id: {}", entity_id))?;
+    player.handle_window_close(window_id)
+}
+
+/// Handle a window transaction packet, acknowledging a previously refused transaction.
+#[spacetimedb(reducer)]
+pub fn handle_window_transaction(entity_id: u32, packet: StdbWindowTransactionPacket) -> Result<(), String> {
+    let player = StdbServerPlayer::filter_by_entity_id(&entity_id).ok_or(
+        format!("Could not find player with id: {}", entity_id))?;
+    player.handle_window_transaction(packet)
+}
+
+/// Handle an update sign packet, the reducer fails if there is no sign at the position
+/// or if the player is not allowed to edit it.
+#[spacetimedb(reducer)]
+pub fn handle_update_sign(entity_id: u32, pos: StdbI32Vec3, lines: Vec<String>) -> Result<(), String> {
+    let player = StdbServerPlayer::filter_by_entity_id(&entity_id).ok_or(
+        format!("Could not find player with id: {}", entity_id))?;
+    player.handle_update_sign(pos, lines)
 }
 
 #[spacetimedb(reducer)]
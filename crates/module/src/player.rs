@@ -16,14 +16,35 @@ use std::collections::HashSet;
 
 use glam::{DVec3, Vec2, IVec3};
 use spacetimedb::{query, spacetimedb, SpacetimeType};
-use mc173_module::chunk;
+use mc173_module::{block, chunk, item};
+use mc173_module::block_entity::chest::{StdbChestBlockEntity, CHEST_INV_SIZE};
+use mc173_module::block_entity::dispenser::{StdbDispenserBlockEntity, DISPENSER_INV_SIZE};
+use mc173_module::block_entity::furnace::StdbFurnaceBlockEntity;
+use mc173_module::block_entity::sign::{is_valid_line, StdbSignBlockEntity, SIGN_LINES_COUNT};
+use mc173_module::chunk_cache::ChunkCache;
+use mc173_module::craft::CraftTracker;
 use mc173_module::dvec3::StdbDVec3;
+use mc173_module::entity::{BaseKind, Entity, Human, Hurt, Item, LivingKind};
+use mc173_module::entity::component::StdbEntity;
+use mc173_module::geom::Face;
 use mc173_module::i32vec3::StdbI32Vec3;
+use mc173_module::inventory::InventoryHandle;
+use mc173_module::item::ItemStack;
 use mc173_module::stdb::chunk::{StdbChunk, StdbChunkView};
 use mc173_module::vec2::StdbVec2;
+use mc173_module::world::StdbWorld;
+use mc173_module::world::interact::Interaction;
 use crate::generate_chunk;
 use crate::player::StdbClientState::Playing;
-use crate::proto::{StdbLookPacket, StdbPositionLookPacket, StdbPositionPacket};
+use crate::proto::{StdbLookPacket, StdbPlaceBlockPacket, StdbPositionLookPacket, StdbPositionPacket, StdbWindowClickPacket, StdbWindowTransactionPacket};
+use crate::world::StdbServerWorld;
+
+/// Number of stacks in the main player inventory, including the hotbar.
+pub const MAIN_INV_SIZE: usize = 36;
+/// Number of stacks in the armor player inventory.
+pub const ARMOR_INV_SIZE: usize = 4;
+/// Number of stacks in the crafting grid, this is always the 3x3 grid.
+pub const CRAFT_INV_SIZE: usize = 9;
 
 /// A server player is an actual
 #[spacetimedb(table(public))]
@@ -76,6 +97,62 @@ pub struct StdbServerPlayer {
     // breaking_block: Option<BreakingBlock>,
 }
 
+/// The inventory of a player, this is not removed when the player leaves so it can be
+/// restored when it comes back.
+#[spacetimedb(table(public))]
+#[derive(Clone)]
+pub struct StdbPlayerInventory {
+    /// The entity id of the player owning this inventory.
+    #[primarykey]
+    pub entity_id: u32,
+    /// The main player inventory including the hotbar in the first 9 slots.
+    pub main_inv: Vec<ItemStack>,
+    /// The armor player inventory.
+    pub armor_inv: Vec<ItemStack>,
+    /// The item stacks for the 3x3 crafting grid. Also support the 2x2 as top left slots.
+    pub craft_inv: Vec<ItemStack>,
+    /// The result of the recipe in the crafting grid, empty if there is no recipe.
+    pub craft_result: ItemStack,
+    /// The item stack in the cursor of the client's using a window.
+    pub cursor_stack: ItemStack,
+    /// The slot current selected for the hand. Must be in range 0..9.
+    pub hand_slot: u8,
+}
+
+impl StdbPlayerInventory {
+
+    /// Create a new empty inventory for the given player entity.
+    pub fn new(entity_id: u32) -> Self {
+        Self {
+            entity_id,
+            main_inv: vec![ItemStack::EMPTY; MAIN_INV_SIZE],
+            armor_inv: vec![ItemStack::EMPTY; ARMOR_INV_SIZE],
+            craft_inv: vec![ItemStack::EMPTY; CRAFT_INV_SIZE],
+            craft_result: ItemStack::EMPTY,
+            cursor_stack: ItemStack::EMPTY,
+            hand_slot: 0,
+        }
+    }
+
+    /// Get the stack currently in the player's hand.
+    pub fn hand_stack(&self) -> ItemStack {
+        self.main_inv[self.hand_slot as usize]
+    }
+
+    /// Get a crafting tracker for the current crafting grid.
+    fn craft_tracker(&self) -> CraftTracker {
+        let mut craft_tracker = CraftTracker::default();
+        craft_tracker.update(self.craft_inv[..].try_into().unwrap());
+        craft_tracker
+    }
+
+    /// Update the crafting result from the current crafting grid.
+    pub fn update_craft(&mut self) {
+        self.craft_result = self.craft_tracker().recipe().unwrap_or_default();
+    }
+
+}
+
 #[spacetimedb(table(public))]
 pub struct StdbOfflineServerPlayer {
     #[primarykey]
@@ -118,57 +195,60 @@ pub struct StdbConnectionStatus {
     pub status: StdbClientState,
 }
 
-// TODO(jdetter): This will be removed when we actually implement entities
+/// Describe the window opened by a player and how to handle clicks into it.
 #[spacetimedb(table(public))]
 #[derive(Clone)]
-pub struct StdbEntity {
-    #[autoinc]
+pub struct StdbWindow {
+    /// The entity id of the player owning this window.
     #[primarykey]
     pub entity_id: u32,
-    // TODO: This should be part of proper entities
-    pub on_ground: bool,
-    /// Last position sent by the client.
-    pub pos: StdbDVec3,
-    /// Last look sent by the client.
-    pub look: StdbVec2,
-    /// The dimension in which this entity lives
-    pub dimension_id: i32,
+    /// The unique id of the currently opened window.
+    pub id: u8,
+    /// Specialization kind of window.
+    pub kind: StdbWindowKind,
+    /// The total number of windows that have been opened by this player, this is also
+    /// used to generate a unique window id. This id should never be zero because it is
+    /// reserved for the player inventory.
+    pub count: u32,
+    /// False after a transaction has been refused and until the client acknowledges it,
+    /// clicks are refused in the meantime.
+    pub synced: bool,
+    /// The last transaction answered to the client, the proxy sends it when it changes.
+    pub transaction: Option<StdbWindowTransactionPacket>,
 }
 
-/// Describe an opened window and how to handle clicks into it.
-// #[derive(Debug, Default)]
-// struct Window {
-//     /// The unique id of the currently opened window.
-//     id: u8,
-//     /// Specialization kind of window.
-//     kind: WindowKind,
-// }
+impl StdbWindow {
+
+    /// Create the default player inventory window for the given player entity.
+    pub fn new(entity_id: u32) -> Self {
+        Self {
+            entity_id,
+            id: 0,
+            kind: StdbWindowKind::Player,
+            count: 0,
+            synced: true,
+            transaction: None,
+        }
+    }
+
+}
 
 /// Describe a kind of opened window on the client side.
-// #[derive(Debug, Default)]
-// enum WindowKind {
-//     /// The player inventory is the default window that is always opened if no other
-//     /// window is opened, it also always has the id 0, it contains the armor and craft
-//     /// matrix.
-//     #[default]
-//     Player,
-//     /// The client-side has a crafting table window opened on the given block pos.
-//     CraftingTable {
-//         pos: IVec3,
-//     },
-//     /// The client-side has a chest window opened referencing the listed block entities.
-//     Chest {
-//         pos: Vec<IVec3>,
-//     },
-//     /// The client-side has a furnace window onto the given block entity.
-//     Furnace {
-//         pos: IVec3,
-//     },
-//     /// The client-side has a dispenser window onto the given block entity.
-//     Dispenser {
-//         pos: IVec3,
-//     }
-// }
+#[derive(SpacetimeType, Debug, Clone)]
+pub enum StdbWindowKind {
+    /// The player inventory is the default window that is always opened if no other
+    /// window is opened, it also always has the id 0, it contains the armor and craft
+    /// matrix.
+    Player,
+    /// The client-side has a crafting table window opened on the given block pos.
+    CraftingTable(StdbI32Vec3),
+    /// The client-side has a chest window opened referencing the listed block entities.
+    Chest(Vec<StdbI32Vec3>),
+    /// The client-side has a furnace window onto the given block entity.
+    Furnace(StdbI32Vec3),
+    /// The client-side has a dispenser window onto the given block entity.
+    Dispenser(StdbI32Vec3),
+}
 
 // /// State of a player breaking a block.
 // struct BreakingBlock {
@@ -292,12 +372,28 @@ impl StdbServerPlayer {
         let mut entity = StdbEntity::filter_by_entity_id(&self.entity_id).expect(
             format!("Could not find player with id: {}", self.entity_id).as_str());
         // let entity = world.get_entity_mut(self.entity_id).expect("incoherent player entity");
+
+        let dy = pos.map_or(0.0, |pos| pos.y - entity.pos.y);
+
+        // Update the fall distance and apply fall damage when landing, the ground state
+        // is given by the client.
+        // REF: Entity::updateFallState
+        if on_ground {
+            if entity.fall_distance > 0.0 {
+                let damage = (entity.fall_distance - 3.0).ceil();
+                if damage > 0.0 {
+                    entity.hurt.push(Hurt { damage: damage as u16, origin_id: None });
+                }
+                entity.fall_distance = 0.0;
+            }
+        } else if dy < 0.0 {
+            entity.fall_distance -= dy as f32;
+        }
+
         entity.on_ground = on_ground;
 
         if let Some(pos) = pos {
-            entity.pos = pos;
-            // TODO(jdetter): Add entity teleportation to players
-            // entity.teleport(pos);
+            entity.teleport(pos.into());
         }
 
         if let Some(look) = look {
@@ -416,1038 +512,965 @@ impl StdbServerPlayer {
     //
     // }
 
-    // Handle a place block packet.
-    // fn handle_place_block(&mut self, world: &mut World, packet: proto::PlaceBlockPacket) {
-    //
-    //     let face = match packet.direction {
-    //         0 => Some(Face::NegY),
-    //         1 => Some(Face::PosY),
-    //         2 => Some(Face::NegZ),
-    //         3 => Some(Face::PosZ),
-    //         4 => Some(Face::NegX),
-    //         5 => Some(Face::PosX),
-    //         0xFF => None,
-    //         _ => return,
-    //     };
-    //
-    //     let pos = IVec3 {
-    //         x: packet.x,
-    //         y: packet.y as i32,
-    //         z: packet.z,
-    //     };
-    //
-    //     let mut inv = InventoryHandle::new(&mut self.main_inv[..]);
-    //     let inv_index = self.hand_slot as usize;
-    //
-    //     // Check if the player is reasonably near the block.
-    //     if face.is_none() || self.pos.distance_squared(pos.as_dvec3() + 0.5) < 64.0 {
-    //         // The real action depends on
-    //         if let Some(face) = face {
-    //             match world.interact_block(pos) {
-    //                 Interaction::None => {
-    //                     // No interaction, use the item at that block.
-    //                     world.use_stack(&mut inv, inv_index, pos, face, self.entity_id);
-    //                 }
-    //                 Interaction::CraftingTable { pos } => {
-    //                     return self.open_window(world, WindowKind::CraftingTable { pos });
-    //                 }
-    //                 Interaction::Chest { pos } => {
-    //                     return self.open_window(world, WindowKind::Chest { pos });
-    //                 }
-    //                 Interaction::Furnace { pos } => {
-    //                     return self.open_window(world, WindowKind::Furnace { pos });
-    //                 }
-    //                 Interaction::Dispenser { pos } => {
-    //                     return self.open_window(world, WindowKind::Dispenser { pos });
-    //                 }
-    //                 Interaction::Handled => {}
-    //             }
-    //         } else {
-    //             world.use_raw_stack(&mut inv, inv_index, self.entity_id);
-    //         }
-    //     }
-    //
-    //     for index in inv.iter_changes() {
-    //         self.send_main_inv_item(index);
-    //     }
-    //
-    // }
+    /// Handle a place block packet. An error is returned if nothing has been placed, so
+    /// that the proxy can send back the real blocks to the client.
+    pub fn handle_place_block(&self, packet: StdbPlaceBlockPacket, cache: &mut ChunkCache) -> Result<(), String> {
 
-    //// Handle a hand slot packet.
-    // fn handle_hand_slot(&mut self, world: &mut World, slot: i16) {
-    //     if slot >= 0 && slot < 9 {
-    //
-    //         // If the previous item was a fishing rod, then we ensure that the bobber id
-    //         // is unset from the player's entity, so that the bobber will be removed.
-    //         let prev_stack = self.main_inv[self.hand_slot as usize];
-    //         if prev_stack.size != 0 && prev_stack.id == item::FISHING_ROD {
-    //             if prev_stack.id == item::FISHING_ROD {
-    //
-    //                 let Entity(base, _) = world.get_entity_mut(self.entity_id).expect("incoherent player entity");
-    //                 base.bobber_id = None;
-    //
-    //             }
-    //         }
-    //
-    //         self.hand_slot = slot as u8;
-    //
-    //     } else {
-    //         warn!("from {}, invalid hand slot: {slot}", self.username);
-    //     }
-    // }
+        let face = match packet.direction {
+            0 => Some(Face::NegY),
+            1 => Some(Face::PosY),
+            2 => Some(Face::NegZ),
+            3 => Some(Face::PosZ),
+            4 => Some(Face::NegX),
+            5 => Some(Face::PosX),
+            0xFF => None,
+            _ => return Err(format!("from {}, invalid place direction: {}", self.username, packet.direction)),
+        };
 
-    //// Handle a window click packet.
-    // fn handle_window_click(&mut self, world: &mut World, packet: proto::WindowClickPacket) {
-    //
-    //     // Holding the target slot's item stack.
-    //     let mut cursor_stack = self.cursor_stack;
-    //     let slot_stack;
-    //     let slot_notify;
-    //
-    //     // Check coherency of server/client windows.
-    //     if self.window.id != packet.window_id {
-    //         warn!("from {}, incoherent window id, expected {}, got {} from client", self.username, self.window.id, packet.window_id);
-    //         return;
-    //     }
-    //
-    //     if packet.slot == -999 {
-    //         slot_stack = ItemStack::EMPTY;
-    //         slot_notify = SlotNotify::None;
-    //         if !cursor_stack.is_empty() {
-    //
-    //             let mut drop_stack = cursor_stack;
-    //             if packet.right_click {
-    //                 drop_stack = drop_stack.with_size(1);
-    //             }
-    //
-    //             cursor_stack.size -= drop_stack.size;
-    //             self.drop_stack(world, drop_stack, false);
-    //
-    //         }
-    //     } else if packet.shift_click {
-    //
-    //         if packet.slot < 0 {
-    //             return;
-    //         }
-    //
-    //         // TODO: This whole branch should be reworked to use a similar approach to
-    //         // regular clicks. One idea would be to have some kind of "SlotTransfer"
-    //         // structure that references targets for transfers, like current "SlotHandle".
-    //
-    //         let slot = packet.slot as usize;
-    //
-    //         // Try to get the main slot, if any.
-    //         let main_slot = match self.window.kind {
-    //             WindowKind::Player => slot.checked_sub(9),
-    //             WindowKind::CraftingTable { .. } => slot.checked_sub(10),
-    //             WindowKind::Chest { ref pos } => slot.checked_sub(pos.len() * 27),
-    //             WindowKind::Furnace { .. } => slot.checked_sub(3),
-    //             WindowKind::Dispenser { .. } => slot.checked_sub(9),
-    //         };
-    //
-    //         // From the slot number, we get the index in the main inventory stacks.
-    //         // The the main slot is set by invalid, just abort.
-    //         let main_index = match main_slot {
-    //             Some(n @ 0..=26) => Some(n + 9),
-    //             Some(n @ 27..=35) => Some(n - 27),
-    //             Some(_) => return,
-    //             _ => None
-    //         };
-    //
-    //         // Create a handle to the main inventory.
-    //         let mut main_inv = InventoryHandle::new(&mut self.main_inv[..]);
-    //
-    //         // Each window kind has a different handling of shift click...
-    //         match self.window.kind {
-    //             WindowKind::Player => {
-    //                 if let Some(main_index) = main_index {
-    //                     // Between hotbar and inventory...
-    //                     slot_stack = main_inv.get(main_index);
-    //                     let mut stack = slot_stack;
-    //                     main_inv.push_front_in(&mut stack, if main_index < 9 { 9..36 } else { 0..9 });
-    //                     main_inv.set(main_index, stack);
-    //                     slot_notify = SlotNotify::None;
-    //                 } else if slot == 0 {
-    //
-    //                     // Craft result
-    //                     if let Some(mut result_stack) = self.craft_tracker.recipe() {
-    //                         slot_stack = result_stack;
-    //                         if main_inv.can_push(result_stack) {
-    //
-    //                             self.craft_tracker.consume(&mut self.craft_inv);
-    //
-    //                             main_inv.push_back_in(&mut result_stack, 0..9);
-    //                             main_inv.push_back_in(&mut result_stack, 9..36);
-    //                             assert!(result_stack.is_empty());
-    //
-    //                             slot_notify = SlotNotify::Craft {
-    //                                 mapping: Some(&[1, 2, -1, 3, 4, -1, -1, -1, -1]),
-    //                                 modified: true,
-    //                             };
-    //
-    //                         } else {
-    //                             slot_notify = SlotNotify::None;
-    //                         }
-    //                     } else {
-    //                         slot_stack = ItemStack::EMPTY;
-    //                         slot_notify = SlotNotify::None;
-    //                     }
-    //
-    //                 } else if slot >= 1 && slot <= 4 {
-    //
-    //                     // Craft matrix
-    //                     let stack = match slot {
-    //                         1 | 2 => &mut self.craft_inv[slot - 1],
-    //                         3 | 4 => &mut self.craft_inv[slot],
-    //                         _ => unreachable!()
-    //                     };
-    //
-    //                     slot_stack = *stack;
-    //                     main_inv.push_front_in(stack, 9..36);
-    //                     main_inv.push_front_in(stack, 0..9);
-    //
-    //                     slot_notify = SlotNotify::Craft {
-    //                         mapping: None,
-    //                         modified: true,
-    //                     };
-    //
-    //                 } else {
-    //                     // Armor
-    //                     let stack = &mut self.armor_inv[slot - 5];
-    //                     slot_stack = *stack;
-    //                     main_inv.push_front_in(stack, 9..36);
-    //                     main_inv.push_front_in(stack, 0..9);
-    //                     slot_notify = SlotNotify::None;
-    //                 }
-    //             }
-    //             WindowKind::CraftingTable { .. } => {
-    //
-    //                 if let Some(main_index) = main_index {
-    //                     // Between hotbar and inventory...
-    //                     slot_stack = main_inv.get(main_index);
-    //                     let mut stack = slot_stack;
-    //                     main_inv.push_front_in(&mut stack, if main_index < 9 { 9..36 } else { 0..9 });
-    //                     main_inv.set(main_index, stack);
-    //                     slot_notify = SlotNotify::None;
-    //                 } else if slot == 0 {
-    //
-    //                     // Craft result
-    //                     if let Some(mut result_stack) = self.craft_tracker.recipe() {
-    //                         slot_stack = result_stack;
-    //                         if main_inv.can_push(result_stack) {
-    //
-    //                             self.craft_tracker.consume(&mut self.craft_inv);
-    //
-    //                             main_inv.push_back_in(&mut result_stack, 0..9);
-    //                             main_inv.push_back_in(&mut result_stack, 9..36);
-    //                             assert!(result_stack.is_empty());
-    //
-    //                             slot_notify = SlotNotify::Craft {
-    //                                 mapping: Some(&[1, 2, 3, 4, 5, 6, 7, 8, 9]),
-    //                                 modified: true,
-    //                             };
-    //
-    //                         } else {
-    //                             slot_notify = SlotNotify::None;
-    //                         }
-    //                     } else {
-    //                         slot_stack = ItemStack::EMPTY;
-    //                         slot_notify = SlotNotify::None;
-    //                     }
-    //
-    //                 } else {
-    //
-    //                     // Craft matrix
-    //                     let stack = &mut self.craft_inv[slot - 1];
-    //
-    //                     slot_stack = *stack;
-    //                     main_inv.push_front_in(stack, 9..36);
-    //                     main_inv.push_front_in(stack, 0..9);
-    //
-    //                     slot_notify = SlotNotify::Craft {
-    //                         mapping: None,
-    //                         modified: true,
-    //                     };
-    //
-    //                 }
-    //
-    //             }
-    //             WindowKind::Chest { ref pos } => {
-    //
-    //                 if let Some(main_index) = main_index {
-    //
-    //                     // From hotbar/inventory to chest.
-    //                     slot_stack = main_inv.get(main_index);
-    //                     let mut stack = slot_stack;
-    //
-    //                     // Temporarily swap events out to avoid borrowing issues.
-    //                     let mut events = world.swap_events(None);
-    //
-    //                     // We try to insert
-    //                     for &pos in pos {
-    //                         if let Some(BlockEntity::Chest(chest)) = world.get_block_entity_mut(pos) {
-    //
-    //                             let mut chest_inv = InventoryHandle::new(&mut chest.inv[..]);
-    //                             chest_inv.push_front(&mut stack);
-    //
-    //                             // Push all changes in the chest inventory as world event.
-    //                             if let Some(events) = &mut events {
-    //                                 for index in chest_inv.iter_changes() {
-    //                                     events.push(Event::BlockEntity {
-    //                                         pos,
-    //                                         inner: BlockEntityEvent::Storage {
-    //                                             storage: BlockEntityStorage::Standard(index as u8),
-    //                                             stack: chest_inv.get(index),
-    //                                         },
-    //                                     });
-    //                                 }
-    //                             }
-    //
-    //                             if stack.is_empty() {
-    //                                 break;
-    //                             }
-    //
-    //                         }
-    //                     }
-    //
-    //                     main_inv.set(main_index, stack);
-    //
-    //                     // Swap events back in.
-    //                     world.swap_events(events);
-    //                     // No notify because we handled the events for all chest entities.
-    //                     slot_notify = SlotNotify::None;
-    //
-    //                 } else {
-    //
-    //                     // From the chest to hotbar/inventory
-    //
-    //                     let pos = pos[slot / 27];
-    //                     let Some(BlockEntity::Chest(chest)) = world.get_block_entity_mut(pos) else {
-    //                         return;
-    //                     };
-    //
-    //                     let index = slot % 27;
-    //                     let stack = &mut chest.inv[index];
-    //                     slot_stack = *stack;
-    //                     if !stack.is_empty() {
-    //                         main_inv.push_back_in(stack, 0..9);
-    //                         main_inv.push_back_in(stack, 9..36);
-    //                     }
-    //
-    //                     slot_notify = SlotNotify::BlockEntityStorageEvent {
-    //                         pos,
-    //                         storage: BlockEntityStorage::Standard(index as u8),
-    //                         stack: Some(*stack),
-    //                     };
-    //
-    //                 }
-    //
-    //             }
-    //             WindowKind::Furnace { pos } => {
-    //
-    //                 if let Some(main_index) = main_index {
-    //                     // Between hotbar and inventory...
-    //                     slot_stack = main_inv.get(main_index);
-    //                     let mut stack = slot_stack;
-    //                     main_inv.push_front_in(&mut stack, if main_index < 9 { 9..36 } else { 0..9 });
-    //                     main_inv.set(main_index, stack);
-    //                     slot_notify = SlotNotify::None;
-    //                 } else {
-    //
-    //                     // From furnace to inventory
-    //                     let Some(BlockEntity::Furnace(furnace)) = world.get_block_entity_mut(pos) else {
-    //                         return;
-    //                     };
-    //
-    //                     let (stack, storage) = match slot {
-    //                         0 => (&mut furnace.input_stack, BlockEntityStorage::FurnaceInput),
-    //                         1 => (&mut furnace.fuel_stack, BlockEntityStorage::FurnaceFuel),
-    //                         2 => (&mut furnace.output_stack, BlockEntityStorage::FurnaceOutput),
-    //                         _ => unreachable!()
-    //                     };
-    //
-    //                     slot_stack = *stack;
-    //                     main_inv.push_front_in(stack, 9..36);
-    //                     main_inv.push_front_in(stack, 0..9);
-    //
-    //                     slot_notify = SlotNotify::BlockEntityStorageEvent {
-    //                         pos,
-    //                         storage,
-    //                         stack: Some(*stack),
-    //                     };
-    //
-    //                 }
-    //
-    //             }
-    //             WindowKind::Dispenser { pos } => {
-    //
-    //                 // No shift click possible with dispenser, but we check coherency.
-    //                 if let Some(main_index) = main_index {
-    //                     slot_stack = main_inv.get(main_index);
-    //                 } else if let Some(BlockEntity::Dispenser(dispenser)) = world.get_block_entity_mut(pos) {
-    //                     slot_stack = dispenser.inv[slot];
-    //                 } else {
-    //                     // No dispenser block entity??
-    //                     return;
-    //                 }
-    //
-    //                 slot_notify = SlotNotify::None;
-    //
-    //             }
-    //         }
-    //
-    //     } else {
-    //
-    //         let slot_handle = self.make_window_slot_handle(world, packet.slot);
-    //         let Some(mut slot_handle) = slot_handle else {
-    //             warn!("from {}, cannot find a handle for slot {} in window {}", self.username, packet.slot, packet.window_id);
-    //             return;
-    //         };
-    //
-    //         slot_stack = slot_handle.get_stack();
-    //         let slot_access = slot_handle.get_access();
-    //
-    //         if slot_stack.is_empty() {
-    //             if !cursor_stack.is_empty() && slot_access.can_drop(cursor_stack) {
-    //
-    //                 let drop_size = if packet.right_click { 1 } else { cursor_stack.size };
-    //                 let drop_size = drop_size.min(slot_handle.max_stack_size());
-    //
-    //                 slot_handle.set_stack(cursor_stack.with_size(drop_size));
-    //                 cursor_stack.size -= drop_size;
-    //
-    //             }
-    //         } else if cursor_stack.is_empty() {
-    //
-    //             // Here the slot is not empty, but the cursor is.
-    //
-    //             // NOTE: Splitting is equivalent of taking and then drop (half), we check
-    //             // if the slot would accept that drop by checking validity.
-    //             cursor_stack = slot_stack;
-    //             if packet.right_click && slot_access.can_drop(cursor_stack) {
-    //                 cursor_stack.size = (cursor_stack.size + 1) / 2;
-    //             }
-    //
-    //             let mut new_slot_stack = slot_stack;
-    //             new_slot_stack.size -= cursor_stack.size;
-    //             if new_slot_stack.size == 0 {
-    //                 slot_handle.set_stack(ItemStack::EMPTY);
-    //             } else {
-    //                 slot_handle.set_stack(new_slot_stack);
-    //             }
-    //
-    //         } else if slot_access.can_drop(cursor_stack) {
-    //
-    //             // Here the slot and the cursor are not empty, we check if we can
-    //             // drop some item if compatible, or swap if not.
-    //
-    //             let cursor_item = item::from_id(cursor_stack.id);
-    //
-    //             if (slot_stack.id, slot_stack.damage) != (cursor_stack.id, cursor_stack.damage) {
-    //                 // Not the same item, we just swap with hand.
-    //                 if cursor_stack.size <= slot_handle.max_stack_size() {
-    //                     slot_handle.set_stack(cursor_stack);
-    //                     cursor_stack = slot_stack;
-    //                 }
-    //             } else {
-    //                 // Same item, just drop some into the existing stack.
-    //                 let max_stack_size = cursor_item.max_stack_size.min(slot_handle.max_stack_size());
-    //                 // Only drop if the stack is not full.
-    //                 if slot_stack.size < max_stack_size {
-    //
-    //                     let drop_size = if packet.right_click { 1 } else { cursor_stack.size };
-    //                     let drop_size = drop_size.min(max_stack_size - slot_stack.size);
-    //                     cursor_stack.size -= drop_size;
-    //
-    //                     let mut new_slot_stack = slot_stack;
-    //                     new_slot_stack.size += drop_size;
-    //                     slot_handle.set_stack(new_slot_stack);
-    //
-    //                 }
-    //             }
-    //
-    //         } else if let SlotAccess::Pickup(min_size) = slot_access {
-    //
-    //             // This last case is when the slot and the cursor are not empty, but we
-    //             // can't drop the cursor into the slot, in such case we try to pick item.
-    //
-    //             if (slot_stack.id, slot_stack.damage) == (cursor_stack.id, cursor_stack.damage) {
-    //                 let cursor_item = item::from_id(cursor_stack.id);
-    //                 if cursor_stack.size < cursor_item.max_stack_size {
-    //                     let available_size = cursor_item.max_stack_size - cursor_stack.size;
-    //                     if available_size >= min_size {
-    //                         let pick_size = slot_stack.size.min(available_size);
-    //                         cursor_stack.size += pick_size;
-    //                         let new_slot_stack = slot_stack.with_size(slot_stack.size - pick_size);
-    //                         slot_handle.set_stack(new_slot_stack.to_non_empty().unwrap_or_default());
-    //                     }
-    //                 }
-    //             }
-    //
-    //         }
-    //
-    //         slot_notify = slot_handle.notify;
-    //
-    //     }
-    //
-    //     // Handle notification if the slot has changed.
-    //     match slot_notify {
-    //         SlotNotify::Craft {
-    //             mapping,
-    //             modified: true,
-    //         } => {
-    //
-    //             self.craft_tracker.update(&self.craft_inv);
-    //
-    //             self.net.send(self.client, OutPacket::WindowSetItem(proto::WindowSetItemPacket {
-    //                 window_id: packet.window_id,
-    //                 slot: 0,
-    //                 stack: self.craft_tracker.recipe(),
-    //             }));
-    //
-    //             if let Some(mapping) = mapping {
-    //                 for (index, &slot) in mapping.iter().enumerate() {
-    //                     if slot >= 0 {
-    //                         self.net.send(self.client, OutPacket::WindowSetItem(proto::WindowSetItemPacket {
-    //                             window_id: packet.window_id,
-    //                             slot,
-    //                             stack: self.craft_inv[index].to_non_empty(),
-    //                         }));
-    //                     }
-    //                 }
-    //             }
-    //
-    //         }
-    //         SlotNotify::BlockEntityStorageEvent {
-    //             pos,
-    //             storage,
-    //             stack: Some(stack),
-    //         } => {
-    //             world.push_event(Event::BlockEntity {
-    //                 pos,
-    //                 inner: BlockEntityEvent::Storage {
-    //                     storage,
-    //                     stack,
-    //                 },
-    //             });
-    //         }
-    //         _ => {}
-    //     }
-    //
-    //     // Answer with a transaction packet that is accepted if the packet's stack is
-    //     // the same as the server's slot stack.
-    //     let accepted = slot_stack.to_non_empty() == packet.stack;
-    //     self.send(OutPacket::WindowTransaction(proto::WindowTransactionPacket {
-    //         window_id: packet.window_id,
-    //         transaction_id: packet.transaction_id,
-    //         accepted,
-    //     }));
-    //
-    //     if !accepted {
-    //         warn!("from {}, incoherent item at {} in window {}", self.username, packet.slot, packet.window_id);
-    //     }
-    //
-    //     if cursor_stack != self.cursor_stack || !accepted {
-    //
-    //         // Send the new cursor item.
-    //         if cursor_stack.size == 0 {
-    //             cursor_stack = ItemStack::EMPTY;
-    //         }
-    //
-    //         self.send(OutPacket::WindowSetItem(proto::WindowSetItemPacket {
-    //             window_id: 0xFF,
-    //             slot: -1,
-    //             stack: cursor_stack.to_non_empty(),
-    //         }));
-    //
-    //         self.cursor_stack = cursor_stack;
-    //
-    //     }
-    //
-    // }
+        let pos = IVec3 {
+            x: packet.x,
+            y: packet.y as i32,
+            z: packet.z,
+        };
 
-    //// Handle a window close packet, it just forget the current window.
-    // fn handle_window_close(&mut self, world: &mut World, packet: proto::WindowClosePacket) {
-    //     self.close_window(world, Some(packet.window_id), false);
-    // }
+        let entity = StdbEntity::filter_by_entity_id(&self.entity_id).ok_or(
+            format!("Could not find entity with id: {}", self.entity_id))?;
+        let mut world = StdbWorld::filter_by_dimension_id(&entity.dimension_id).ok_or(
+            format!("Could not find world with dimension id: {}", entity.dimension_id))?;
 
-    // fn handle_animation(&mut self, _world: &mut World, _packet: proto::AnimationPacket) {
-    //     // TODO: Send animation to other players.
-    // }
+        let mut inventory = StdbPlayerInventory::filter_by_entity_id(&self.entity_id).ok_or(
+            format!("Could not find inventory for entity id: {}", self.entity_id))?;
+        let inv_index = inventory.hand_slot as usize;
 
-    //// Handle an entity interaction.
-    // fn handle_interact(&mut self, world: &mut World, packet: proto::InteractPacket) {
-    //
-    //     if self.entity_id != packet.player_entity_id {
-    //         warn!("from {}, incoherent interact entity: {}, expected: {}", self.username, packet.player_entity_id, self.entity_id);
-    //     }
-    //
-    //     let Some(Entity(target_base, _)) = world.get_entity_mut(packet.target_entity_id) else {
-    //         warn!("from {}, incoherent interact entity target: {}", self.username, packet.target_entity_id);
-    //         return;
-    //     };
-    //
-    //     if self.pos.distance_squared(target_base.pos) >= 36.0 {
-    //         warn!("from {}, incoherent interact entity distance", self.username);
-    //         return;
-    //     }
-    //
-    //     let hand_stack = self.main_inv[self.hand_slot as usize];
-    //
-    //     if packet.left_click {
-    //
-    //         // TODO: Critical damage if vel.y < 0
-    //
-    //         let damage = item::attack::get_base_damage(hand_stack.id);
-    //         target_base.hurt.push(Hurt {
-    //             damage,
-    //             origin_id: Some(self.entity_id),
-    //         });
-    //
-    //     } else {
-    //
-    //     }
-    //
-    // }
+        let Some(face) = face else {
+            // No face, the item is used in the air (food, buckets...).
+            let mut inv = InventoryHandle::new(&mut inventory.main_inv[..]);
+            world.use_raw_stack(&mut inv, inv_index, self.entity_id, cache);
+            if inv.iter_changes().next().is_some() {
+                StdbPlayerInventory::update_by_entity_id(&self.entity_id, inventory);
+            }
+            StdbWorld::update_by_dimension_id(&entity.dimension_id, world);
+            return Ok(());
+        };
 
-    //// Handle an action packet from the player.
-    // fn handle_action(&mut self, world: &mut World, packet: proto::ActionPacket) {
-    //
-    //     if self.entity_id != packet.entity_id {
-    //         warn!("from {}, incoherent player entity: {}, expected: {}", self.username, packet.entity_id, self.entity_id);
-    //     }
-    //
-    //     // A player action is only relevant on human entities, ignore if the player is
-    //     // bound to any other entity kind.
-    //     let Some(Entity(_, BaseKind::Living(_, LivingKind::Human(human)))) = world.get_entity_mut(self.entity_id) else {
-    //         return;
-    //     };
-    //
-    //     match packet.state {
-    //         1 | 2 => {
-    //             human.sneaking = packet.state == 1;
-    //             world.push_event(Event::Entity { id: self.entity_id, inner: EntityEvent::Metadata });
-    //         }
-    //         3 => todo!("wake up..."),
-    //         _ => warn!("from {}, invalid action state: {}", self.username, packet.state)
-    //     }
-    //
-    // }
+        // Check if the player is reasonably near the block.
+        if entity.pos.as_dvec3().distance_squared(pos.as_dvec3() + 0.5) >= 64.0 {
+            return Err(format!("from {}, block too far to be placed: {}", self.username, pos));
+        }
 
-    //// Open the given window kind on client-side by sending appropriate packet. A new
-    //// window id is automatically associated to that window.
-    // fn open_window(&mut self, world: &mut World, kind: WindowKind) {
-    //
-    //     // Close any already opened window.
-    //     self.close_window(world, None, true);
-    //
-    //     // NOTE: We should never get a window id of 0 because it is the player inventory.
-    //     let window_id = (self.window_count % 100 + 1) as u8;
-    //     self.window_count += 1;
-    //
-    //     match kind {
-    //         WindowKind::Player => panic!("cannot force open the player window"),
-    //         WindowKind::CraftingTable { .. } => {
-    //             self.send(OutPacket::WindowOpen(proto::WindowOpenPacket {
-    //                 window_id,
-    //                 inventory_type: 1,
-    //                 title: "Crafting".to_string(),
-    //                 slots_count: 9,
-    //             }));
-    //         }
-    //         WindowKind::Chest { ref pos } => {
-    //
-    //             self.send(OutPacket::WindowOpen(proto::WindowOpenPacket {
-    //                 window_id,
-    //                 inventory_type: 0,
-    //                 title: if pos.len() <= 1 { "Chest" } else { "Large Chest" }.to_string(),
-    //                 slots_count: (pos.len() * 27) as u8,  // TODO: Checked cast
-    //             }));
-    //
-    //             let mut stacks = Vec::new();
-    //
-    //             for &pos in pos {
-    //                 if let Some(BlockEntity::Chest(chest)) = world.get_block_entity(pos) {
-    //                     stacks.extend(chest.inv.iter().map(|stack| stack.to_non_empty()));
-    //                 } else {
-    //                     stacks.extend(std::iter::repeat(None).take(27));
-    //                 }
-    //             }
-    //
-    //             self.send(OutPacket::WindowItems(proto::WindowItemsPacket {
-    //                 window_id,
-    //                 stacks,
-    //             }));
-    //
-    //         }
-    //         WindowKind::Furnace { pos } => {
-    //
-    //             self.send(OutPacket::WindowOpen(proto::WindowOpenPacket {
-    //                 window_id,
-    //                 inventory_type: 2,
-    //                 title: format!("Furnace"),
-    //                 slots_count: 3,
-    //             }));
-    //
-    //             if let Some(BlockEntity::Furnace(furnace)) = world.get_block_entity(pos) {
-    //
-    //                 self.send(OutPacket::WindowProgressBar(proto::WindowProgressBarPacket {
-    //                     window_id,
-    //                     bar_id: 0,
-    //                     value: furnace.smelt_ticks as i16,
-    //                 }));
-    //
-    //                 self.send(OutPacket::WindowProgressBar(proto::WindowProgressBarPacket {
-    //                     window_id,
-    //                     bar_id: 1,
-    //                     value: furnace.burn_remaining_ticks as i16,
-    //                 }));
-    //
-    //                 self.send(OutPacket::WindowProgressBar(proto::WindowProgressBarPacket {
-    //                     window_id,
-    //                     bar_id: 2,
-    //                     value: furnace.burn_max_ticks as i16,
-    //                 }));
-    //
-    //                 self.send(OutPacket::WindowItems(proto::WindowItemsPacket {
-    //                     window_id,
-    //                     stacks: vec![
-    //                         furnace.input_stack.to_non_empty(),
-    //                         furnace.fuel_stack.to_non_empty(),
-    //                         furnace.output_stack.to_non_empty()
-    //                     ],
-    //                 }));
-    //
-    //             }
-    //
-    //         }
-    //         WindowKind::Dispenser { pos } => {
-    //
-    //             self.send(OutPacket::WindowOpen(proto::WindowOpenPacket {
-    //                 window_id,
-    //                 inventory_type: 3,
-    //                 title: format!("Dispenser"),
-    //                 slots_count: 9,
-    //             }));
-    //
-    //             if let Some(BlockEntity::Dispenser(dispenser)) = world.get_block_entity(pos) {
-    //                 self.send(OutPacket::WindowItems(proto::WindowItemsPacket {
-    //                     window_id,
-    //                     stacks: dispenser.inv.iter().map(|stack| stack.to_non_empty()).collect()
-    //                 }));
-    //             }
-    //
-    //         }
-    //     };
-    //
-    //     self.window.id = window_id;
-    //     self.window.kind = kind;
-    //
-    // }
+        // The real action depends on the interaction with the block.
+        let used = match world.interact_block(pos, cache) {
+            Interaction::None => {
+                // No interaction, use the item at that block.
+                let mut inv = InventoryHandle::new(&mut inventory.main_inv[..]);
+                world.use_stack(&mut inv, inv_index, pos, face, self.entity_id, entity.look.into(), cache);
+                inv.iter_changes().next().is_some()
+            }
+            Interaction::CraftingTable { pos } => {
+                self.open_window(&mut world, &mut inventory, StdbWindowKind::CraftingTable(pos.into()))?;
+                true
+            }
+            Interaction::Chest { pos } => {
+                self.open_window(&mut world, &mut inventory, StdbWindowKind::Chest(pos.into_iter().map(Into::into).collect()))?;
+                true
+            }
+            Interaction::Furnace { pos } => {
+                self.open_window(&mut world, &mut inventory, StdbWindowKind::Furnace(pos.into()))?;
+                true
+            }
+            Interaction::Dispenser { pos } => {
+                self.open_window(&mut world, &mut inventory, StdbWindowKind::Dispenser(pos.into()))?;
+                true
+            }
+            Interaction::Bed { pos } => {
+                // TODO: Send the "tile.bed.occupied" and "tile.bed.noSleep" messages.
+                world.sleep_player(self.entity_id, pos, cache);
+                true
+            }
+            Interaction::Handled => true,
+        };
 
-    //// Close the current window opened by the player. If the window id argument is
-    //// provided, then this will only work if the current server-side window is matching.
-    //// The send boolean indicates if a window close packet must also be sent.
-    // fn close_window(&mut self, world: &mut World, window_id: Option<u8>, send: bool) {
-    //
-    //     if let Some(window_id) = window_id {
-    //         if self.window.id != window_id {
-    //             return;
-    //         }
-    //     }
-    //
-    //     // For any closed inventory, we drop the cursor stack and crafting matrix.
-    //     let mut drop_stacks = Vec::new();
-    //     drop_stacks.extend(self.cursor_stack.take_non_empty());
-    //     for stack in self.craft_inv.iter_mut() {
-    //         drop_stacks.extend(stack.take_non_empty());
-    //     }
-    //
-    //     for drop_stack in drop_stacks {
-    //         self.drop_stack(world, drop_stack, false);
-    //     }
-    //
-    //     // Closing the player inventory so we clear the crafting matrix.
-    //     if self.window.id == 0 {
-    //         for slot in 1..=4 {
-    //             self.send(OutPacket::WindowSetItem(proto::WindowSetItemPacket {
-    //                 window_id: 0,
-    //                 slot,
-    //                 stack: None,
-    //             }));
-    //         }
-    //     }
-    //
-    //     // Reset to the default window.
-    //     self.window.id = 0;
-    //     self.window.kind = WindowKind::Player;
-    //
-    //     if send {
-    //         self.send(OutPacket::WindowClose(proto::WindowClosePacket {
-    //             window_id: self.window.id,
-    //         }));
-    //     }
-    //
-    // }
+        StdbWorld::update_by_dimension_id(&entity.dimension_id, world);
 
-    //// Internal function to create a window slot handle specifically for a player main
-    //// inventory slot, the offset of the first player inventory slot is also given.
-    // fn make_player_window_slot_handle(&mut self, slot: i16, offset: i16) -> Option<SlotHandle<'_>> {
-    //
-    //     let index = match slot - offset {
-    //         0..=26 => slot - offset + 9,
-    //         27..=35 => slot - offset - 27,
-    //         _ => return None,
-    //     } as usize;
-    //
-    //     Some(SlotHandle {
-    //         kind: SlotKind::Standard {
-    //             stack: &mut self.main_inv[index],
-    //             access: SlotAccess::PickupDrop,
-    //             max_size: 64,
-    //         },
-    //         notify: SlotNotify::None
-    //     })
-    //
-    // }
+        if used {
+            StdbPlayerInventory::update_by_entity_id(&self.entity_id, inventory);
+            Ok(())
+        } else {
+            Err(format!("from {}, cannot use {:?} at {}", self.username, inventory.hand_stack(), pos))
+        }
 
-    //// Internal function to create a window slot handle. This handle is temporary and
-    //// own two mutable reference to the player itself and the world, it can only work
-    //// on the given slot.
-    // fn make_window_slot_handle<'a>(&'a mut self, world: &'a mut World, slot: i16) -> Option<SlotHandle<'a>> {
-    //
-    //     // This avoid temporary cast issues afterward, even if we keep the signed type.
-    //     if slot < 0 {
-    //         return None;
-    //     }
-    //
-    //     Some(match self.window.kind {
-    //         WindowKind::Player => {
-    //             match slot {
-    //                 0 => SlotHandle {
-    //                     kind: SlotKind::CraftingResult {
-    //                         craft_inv: &mut self.craft_inv,
-    //                         craft_tracker: &mut self.craft_tracker,
-    //                     },
-    //                     notify: SlotNotify::Craft {
-    //                         mapping: Some(&[1, 2, -1, 3, 4, -1, -1, -1, -1]),
-    //                         modified: false,
-    //                     },
-    //                 },
-    //                 1..=4 => SlotHandle {
-    //                     kind: SlotKind::Standard {
-    //                         stack: &mut self.craft_inv[match slot {
-    //                             1 => 0,
-    //                             2 => 1,
-    //                             3 => 3,
-    //                             4 => 4,
-    //                             _ => unreachable!()
-    //                         }],
-    //                         access: SlotAccess::PickupDrop,
-    //                         max_size: 64,
-    //                     },
-    //                     notify: SlotNotify::Craft {
-    //                         mapping: None,
-    //                         modified: false,
-    //                     },
-    //                 },
-    //                 5..=8 => SlotHandle {
-    //                     kind: SlotKind::Standard {
-    //                         stack: &mut self.armor_inv[slot as usize - 5],
-    //                         access: match slot {
-    //                             5 => SlotAccess::ArmorHelmet,
-    //                             6 => SlotAccess::ArmorChestplate,
-    //                             7 => SlotAccess::ArmorLeggings,
-    //                             8 => SlotAccess::ArmorBoots,
-    //                             _ => unreachable!(),
-    //                         }, max_size: 1,
-    //                     },
-    //                     notify: SlotNotify::None,
-    //                 },
-    //                 _ => self.make_player_window_slot_handle(slot, 9)?
-    //             }
-    //         }
-    //         WindowKind::CraftingTable { .. } => {
-    //             match slot {
-    //                 0 => SlotHandle {
-    //                     kind: SlotKind::CraftingResult {
-    //                         craft_inv: &mut self.craft_inv,
-    //                         craft_tracker: &mut self.craft_tracker,
-    //                     },
-    //                     notify: SlotNotify::Craft {
-    //                         mapping: Some(&[1, 2, 3, 4, 5, 6, 7, 8, 9]),
-    //                         modified: false,
-    //                     },
-    //                 },
-    //                 1..=9 => SlotHandle {
-    //                     kind: SlotKind::Standard {
-    //                         stack: &mut self.craft_inv[slot as usize - 1],
-    //                         access: SlotAccess::PickupDrop,
-    //                         max_size: 64,
-    //                     },
-    //                     notify: SlotNotify::Craft {
-    //                         mapping: None,
-    //                         modified: false,
-    //                     },
-    //                 },
-    //                 _ => self.make_player_window_slot_handle(slot, 10)?
-    //             }
-    //         }
-    //         WindowKind::Chest { ref pos } => {
-    //
-    //             if let Some(&pos) = pos.get(slot as usize / 27) {
-    //
-    //                 // Get the chest tile entity corresponding to the clicked slot,
-    //                 // if not found we just ignore.
-    //                 let Some(BlockEntity::Chest(chest)) = world.get_block_entity_mut(pos) else {
-    //                     return None
-    //                 };
-    //
-    //                 let index = slot as usize % 27;
-    //
-    //                 SlotHandle {
-    //                     kind: SlotKind::Standard {
-    //                         stack: &mut chest.inv[index],
-    //                         access: SlotAccess::PickupDrop,
-    //                         max_size: 64,
-    //                     },
-    //                     notify: SlotNotify::BlockEntityStorageEvent {
-    //                         pos,
-    //                         storage: BlockEntityStorage::Standard(index as u8),
-    //                         stack: None,
-    //                     },
-    //                 }
-    //
-    //             } else {
-    //                 self.make_player_window_slot_handle(slot, pos.len() as i16 * 27)?
-    //             }
-    //
-    //         }
-    //         WindowKind::Furnace { pos } => {
-    //
-    //             if slot <= 2 {
-    //
-    //                 let Some(BlockEntity::Furnace(furnace)) = world.get_block_entity_mut(pos) else {
-    //                     return None
-    //                 };
-    //
-    //                 let (stack, access, storage) = match slot {
-    //                     0 => (&mut furnace.input_stack, SlotAccess::PickupDrop, BlockEntityStorage::FurnaceInput),
-    //                     1 => (&mut furnace.fuel_stack, SlotAccess::PickupDrop, BlockEntityStorage::FurnaceFuel),
-    //                     2 => (&mut furnace.output_stack, SlotAccess::Pickup(1), BlockEntityStorage::FurnaceOutput),
-    //                     _ => unreachable!()
-    //                 };
-    //
-    //                 SlotHandle {
-    //                     kind: SlotKind::Standard {
-    //                         stack,
-    //                         access,
-    //                         max_size: 64,
-    //                     },
-    //                     notify: SlotNotify::BlockEntityStorageEvent {
-    //                         pos,
-    //                         storage,
-    //                         stack: None,
-    //                     },
-    //                 }
-    //
-    //             } else {
-    //                 self.make_player_window_slot_handle(slot, 3)?
-    //             }
-    //
-    //         }
-    //         WindowKind::Dispenser { pos } => {
-    //
-    //             if slot < 9 {
-    //
-    //                 let Some(BlockEntity::Dispenser(dispenser)) = world.get_block_entity_mut(pos) else {
-    //                     return None
-    //                 };
-    //
-    //                 SlotHandle {
-    //                     kind: SlotKind::Standard {
-    //                         stack: &mut dispenser.inv[slot as usize],
-    //                         access: SlotAccess::PickupDrop,
-    //                         max_size: 64,
-    //                     },
-    //                     notify: SlotNotify::BlockEntityStorageEvent {
-    //                         pos,
-    //                         storage: BlockEntityStorage::Standard(slot as u8),
-    //                         stack: None,
-    //                     },
-    //                 }
-    //
-    //             } else {
-    //                 self.make_player_window_slot_handle(slot, 9)?
-    //             }
-    //
-    //         }
-    //     })
-    //
-    // }
+    }
 
-    //// Send the main inventory item at given index to the client.
-    // fn send_main_inv_item(&self, index: usize) {
-    //
-    //     let slot = match index {
-    //         0..=8 => 36 + index,
-    //         _ => index,
-    //     };
-    //
-    //     self.send(OutPacket::WindowSetItem(proto::WindowSetItemPacket {
-    //         window_id: 0,
-    //         slot: slot as i16,
-    //         stack: self.main_inv[index].to_non_empty(),
-    //     }));
-    //
-    // }
+    /// Handle a hand slot packet.
+    pub fn handle_hand_slot(&self, slot: i16) -> Result<(), String> {
+        if slot >= 0 && slot < 9 {
 
-    //// Drop an item from the player's entity, items are drop in front of the player, but
-    //// the `on_ground` argument can be set to true in order to drop item on the ground.
-    // pub fn drop_stack(&mut self, world: &mut World, stack: ItemStack, on_ground: bool) {
-    //
-    //     let Entity(origin_base, _) = world.get_entity_mut(self.entity_id).expect("incoherent player entity");
-    //
-    //     let entity = e::Item::new_with(|base, item| {
-    //
-    //         base.persistent = true;
-    //         base.pos = origin_base.pos;
-    //         base.pos.y += 1.3;  // TODO: Adjust depending on eye height.
-    //
-    //         if on_ground {
-    //
-    //             let rand_drop_speed = origin_base.rand.next_float() * 0.5;
-    //             let rand_yaw = origin_base.rand.next_float() * std::f32::consts::TAU;
-    //
-    //             base.vel.x = (rand_yaw.sin() * rand_drop_speed) as f64;
-    //             base.vel.z = (rand_yaw.cos() * rand_drop_speed) as f64;
-    //             base.vel.y = 0.2;
-    //
-    //         } else {
-    //
-    //             let drop_speed = 0.3;
-    //             let rand_yaw = base.rand.next_float() * std::f32::consts::TAU;
-    //             let rand_drop_speed = base.rand.next_float() * 0.02;
-    //             let rand_vel_y = (base.rand.next_float() - base.rand.next_float()) * 0.1;
-    //
-    //             base.vel.x = (-origin_base.look.x.sin() * origin_base.look.y.cos() * drop_speed) as f64;
-    //             base.vel.z = (origin_base.look.x.cos() * origin_base.look.y.cos() * drop_speed) as f64;
-    //             base.vel.y = (-origin_base.look.y.sin() * drop_speed + 0.1) as f64;
-    //             base.vel.x += (rand_yaw.cos() * rand_drop_speed) as f64;
-    //             base.vel.z += (rand_yaw.sin() * rand_drop_speed) as f64;
-    //             base.vel.y += rand_vel_y as f64;
-    //
-    //         }
-    //
-    //         item.frozen_time = 40;
-    //         item.stack = stack;
-    //
-    //     });
-    //
-    //     world.spawn_entity(entity);
-    //
+            let mut inventory = StdbPlayerInventory::filter_by_entity_id(&self.entity_id).ok_or(
+                format!("Could not find inventory for entity id: {}", self.entity_id))?;
+
+            // If the previous item was a fishing rod, then the bobber should be removed,
+            // the bobber removes itself when its owner no longer references it.
+            if inventory.hand_stack().id == item::FISHING_ROD {
+                let entity = StdbEntity::filter_by_entity_id(&self.entity_id).ok_or(
+                    format!("Could not find entity with id: {}", self.entity_id))?;
+                let mut world = StdbWorld::filter_by_dimension_id(&entity.dimension_id).ok_or(
+                    format!("Could not find world for dimension: {}", entity.dimension_id))?;
+                if let Some(mut player_entity) = world.get_entity(self.entity_id) {
+                    player_entity.0.bobber_id = None;
+                    world.set_entity(self.entity_id, &player_entity);
+                }
+            }
+
+            inventory.hand_slot = slot as u8;
+            StdbPlayerInventory::update_by_entity_id(&self.entity_id, inventory);
+            Ok(())
+
+        } else {
+            Err(format!("from {}, invalid hand slot: {slot}", self.username))
+        }
+    }
+
+    /// Handle a window click packet, the click is applied to the player's inventory and
+    /// the transaction is answered through the window table.
+    pub fn handle_window_click(&self, packet: StdbWindowClickPacket) -> Result<(), String> {
+
+        let mut window = StdbWindow::filter_by_entity_id(&self.entity_id).ok_or(
+            format!("Could not find window for entity id: {}", self.entity_id))?;
+        let mut inventory = StdbPlayerInventory::filter_by_entity_id(&self.entity_id).ok_or(
+            format!("Could not find inventory for entity id: {}", self.entity_id))?;
+
+        // Check coherency of server/client windows.
+        if window.id != packet.window_id {
+            return Err(format!("from {}, incoherent window id, expected {}, got {} from client", self.username, window.id, packet.window_id));
+        }
+
+        let entity = StdbEntity::filter_by_entity_id(&self.entity_id).ok_or(
+            format!("Could not find entity with id: {}", self.entity_id))?;
+        let mut world = StdbWorld::filter_by_dimension_id(&entity.dimension_id).ok_or(
+            format!("Could not find world with dimension id: {}", entity.dimension_id))?;
+
+        // Load the block entities referenced by the window, if one of them has been
+        // removed the window is closed, the proxy will tell the client.
+        let Some(mut block_entities) = WindowBlockEntities::load(&window) else {
+            self.close_window(&mut world, &mut window, &mut inventory);
+            StdbWorld::update_by_dimension_id(&entity.dimension_id, world);
+            StdbPlayerInventory::update_by_entity_id(&self.entity_id, inventory);
+            StdbWindow::update_by_entity_id(&self.entity_id, window);
+            return Ok(());
+        };
+
+        // While the client has not acknowledged a refused transaction, we refuse any
+        // click because the client's window content is not known.
+        let prev_block_entities = block_entities.clone();
+        let accepted = window.synced && self.click_window(&mut world, &window, &mut inventory, &mut block_entities, &packet);
+        if !accepted {
+            log::warn!("from {}, incoherent item at {} in window {}", self.username, packet.slot, packet.window_id);
+        }
+
+        window.synced = accepted;
+        window.transaction = Some(StdbWindowTransactionPacket {
+            window_id: packet.window_id,
+            transaction_id: packet.transaction_id,
+            accepted,
+        });
+
+        block_entities.save(prev_block_entities);
+
+        StdbWorld::update_by_dimension_id(&entity.dimension_id, world);
+        StdbPlayerInventory::update_by_entity_id(&self.entity_id, inventory);
+        StdbWindow::update_by_entity_id(&self.entity_id, window);
+        Ok(())
+
+    }
+
+    /// Internal function to apply a window click to the player's inventory, returning
+    /// true if the client's view of the clicked slot was coherent with the server.
+    fn click_window(&self, world: &mut StdbWorld, window: &StdbWindow, inventory: &mut StdbPlayerInventory, block_entities: &mut WindowBlockEntities, packet: &StdbWindowClickPacket) -> bool {
+
+        // Holding the target slot's item stack.
+        let mut cursor_stack = inventory.cursor_stack;
+        let slot_stack;
+
+        if packet.slot == -999 {
+            slot_stack = ItemStack::EMPTY;
+            if !cursor_stack.is_empty() {
+                // Clicking outside of the window drops the cursor stack, or only one item
+                // with a right click.
+                let drop_size = if packet.right_click { 1 } else { cursor_stack.size };
+                self.drop_stack(world, cursor_stack.with_size(drop_size), false);
+                cursor_stack.size -= drop_size;
+            }
+        } else if packet.shift_click {
+
+            if packet.slot < 0 {
+                return false;
+            }
+
+            // TODO: This whole branch should be reworked to use a similar approach to
+            // regular clicks. One idea would be to have some kind of "SlotTransfer"
+            // structure that references targets for transfers, like current "SlotHandle".
+
+            let slot = packet.slot as usize;
+
+            // Try to get the main slot, if any.
+            let main_slot = match window.kind {
+                StdbWindowKind::Player => slot.checked_sub(9),
+                StdbWindowKind::CraftingTable(_) => slot.checked_sub(10),
+                StdbWindowKind::Chest(_) => slot.checked_sub(block_entities.chests.len() * CHEST_INV_SIZE),
+                StdbWindowKind::Furnace(_) => slot.checked_sub(3),
+                StdbWindowKind::Dispenser(_) => slot.checked_sub(DISPENSER_INV_SIZE),
+            };
+
+            // From the slot number, we get the index in the main inventory stacks.
+            // The the main slot is set by invalid, just abort.
+            let main_index = match main_slot {
+                Some(n @ 0..=26) => Some(n + 9),
+                Some(n @ 27..=35) => Some(n - 27),
+                Some(_) => return false,
+                _ => None
+            };
+
+            // Create a handle to the main inventory.
+            let mut main_inv = InventoryHandle::new(&mut inventory.main_inv[..]);
+
+            // Each window kind has a different handling of shift click...
+            match window.kind {
+                StdbWindowKind::Player => {
+                    if let Some(main_index) = main_index {
+                        // Between hotbar and inventory...
+                        slot_stack = main_inv.get(main_index);
+                        let mut stack = slot_stack;
+                        main_inv.push_front_in(&mut stack, if main_index < 9 { 9..36 } else { 0..9 });
+                        main_inv.set(main_index, stack);
+                    } else if slot == 0 {
+                        // Craft result
+                        slot_stack = Self::take_craft_result(&mut main_inv, &mut inventory.craft_inv, inventory.craft_result);
+                    } else if slot >= 1 && slot <= 4 {
+
+                        // Craft matrix
+                        let stack = match slot {
+                            1 | 2 => &mut inventory.craft_inv[slot - 1],
+                            3 | 4 => &mut inventory.craft_inv[slot],
+                            _ => unreachable!()
+                        };
+
+                        slot_stack = *stack;
+                        main_inv.push_front_in(stack, 9..36);
+                        main_inv.push_front_in(stack, 0..9);
+
+                    } else {
+                        // Armor
+                        let stack = &mut inventory.armor_inv[slot - 5];
+                        slot_stack = *stack;
+                        main_inv.push_front_in(stack, 9..36);
+                        main_inv.push_front_in(stack, 0..9);
+                    }
+                }
+                StdbWindowKind::CraftingTable(_) => {
+
+                    if let Some(main_index) = main_index {
+                        // Between hotbar and inventory...
+                        slot_stack = main_inv.get(main_index);
+                        let mut stack = slot_stack;
+                        main_inv.push_front_in(&mut stack, if main_index < 9 { 9..36 } else { 0..9 });
+                        main_inv.set(main_index, stack);
+                    } else if slot == 0 {
+                        // Craft result
+                        slot_stack = Self::take_craft_result(&mut main_inv, &mut inventory.craft_inv, inventory.craft_result);
+                    } else {
+
+                        // Craft matrix
+                        let stack = &mut inventory.craft_inv[slot - 1];
+
+                        slot_stack = *stack;
+                        main_inv.push_front_in(stack, 9..36);
+                        main_inv.push_front_in(stack, 0..9);
+
+                    }
+
+                }
+                StdbWindowKind::Chest(_) => {
+
+                    if let Some(main_index) = main_index {
+                        // From hotbar or inventory to the chests, in window order.
+                        slot_stack = main_inv.get(main_index);
+                        let mut stack = slot_stack;
+                        for chest in block_entities.chests.iter_mut() {
+                            InventoryHandle::new(&mut chest.inv[..]).push_front(&mut stack);
+                            if stack.is_empty() {
+                                break;
+                            }
+                        }
+                        main_inv.set(main_index, stack);
+                    } else {
+                        // From a chest to hotbar or inventory.
+                        let stack = &mut block_entities.chests[slot / CHEST_INV_SIZE].inv[slot % CHEST_INV_SIZE];
+                        slot_stack = *stack;
+                        main_inv.push_back_in(stack, 0..9);
+                        main_inv.push_back_in(stack, 9..36);
+                    }
+
+                }
+                StdbWindowKind::Furnace(_) => {
+
+                    if let Some(main_index) = main_index {
+                        // Between hotbar and inventory...
+                        slot_stack = main_inv.get(main_index);
+                        let mut stack = slot_stack;
+                        main_inv.push_front_in(&mut stack, if main_index < 9 { 9..36 } else { 0..9 });
+                        main_inv.set(main_index, stack);
+                    } else {
+
+                        // From furnace to inventory
+                        let Some(furnace) = &mut block_entities.furnace else { return false };
+                        let stack = match slot {
+                            0 => &mut furnace.input_stack,
+                            1 => &mut furnace.fuel_stack,
+                            2 => &mut furnace.output_stack,
+                            _ => unreachable!()
+                        };
+
+                        slot_stack = *stack;
+                        main_inv.push_front_in(stack, 9..36);
+                        main_inv.push_front_in(stack, 0..9);
+
+                    }
+
+                }
+                StdbWindowKind::Dispenser(_) => {
+
+                    let Some(dispenser) = &mut block_entities.dispenser else { return false };
+
+                    if let Some(main_index) = main_index {
+                        // From hotbar or inventory to the dispenser.
+                        slot_stack = main_inv.get(main_index);
+                        let mut stack = slot_stack;
+                        InventoryHandle::new(&mut dispenser.inv[..]).push_front(&mut stack);
+                        main_inv.set(main_index, stack);
+                    } else {
+                        // From the dispenser to hotbar or inventory.
+                        let stack = &mut dispenser.inv[slot];
+                        slot_stack = *stack;
+                        main_inv.push_back_in(stack, 0..9);
+                        main_inv.push_back_in(stack, 9..36);
+                    }
+
+                }
+            }
+
+        } else {
+
+            let slot_handle = Self::make_window_slot_handle(window, inventory, block_entities, packet.slot);
+            let Some(mut slot_handle) = slot_handle else {
+                log::warn!("from {}, cannot find a handle for slot {} in window {}", self.username, packet.slot, packet.window_id);
+                return false;
+            };
+
+            slot_stack = slot_handle.get_stack();
+            let slot_access = slot_handle.get_access();
+
+            if slot_stack.is_empty() {
+                if !cursor_stack.is_empty() && slot_access.can_drop(cursor_stack) {
+
+                    let drop_size = if packet.right_click { 1 } else { cursor_stack.size };
+                    let drop_size = drop_size.min(slot_handle.max_stack_size());
+
+                    slot_handle.set_stack(cursor_stack.with_size(drop_size));
+                    cursor_stack.size -= drop_size;
+
+                }
+            } else if cursor_stack.is_empty() {
+
+                // Here the slot is not empty, but the cursor is.
+
+                // NOTE: Splitting is equivalent of taking and then drop (half), we check
+                // if the slot would accept that drop by checking validity.
+                cursor_stack = slot_stack;
+                if packet.right_click && slot_access.can_drop(cursor_stack) {
+                    cursor_stack.size = (cursor_stack.size + 1) / 2;
+                }
+
+                let mut new_slot_stack = slot_stack;
+                new_slot_stack.size -= cursor_stack.size;
+                if new_slot_stack.size == 0 {
+                    slot_handle.set_stack(ItemStack::EMPTY);
+                } else {
+                    slot_handle.set_stack(new_slot_stack);
+                }
+
+            } else if slot_access.can_drop(cursor_stack) {
+
+                // Here the slot and the cursor are not empty, we check if we can
+                // drop some item if compatible, or swap if not.
+
+                let cursor_item = item::from_id(cursor_stack.id);
+
+                if (slot_stack.id, slot_stack.damage) != (cursor_stack.id, cursor_stack.damage) {
+                    // Not the same item, we just swap with hand.
+                    if cursor_stack.size <= slot_handle.max_stack_size() {
+                        slot_handle.set_stack(cursor_stack);
+                        cursor_stack = slot_stack;
+                    }
+                } else {
+                    // Same item, just drop some into the existing stack.
+                    let max_stack_size = cursor_item.max_stack_size.min(slot_handle.max_stack_size());
+                    // Only drop if the stack is not full.
+                    if slot_stack.size < max_stack_size {
+
+                        let drop_size = if packet.right_click { 1 } else { cursor_stack.size };
+                        let drop_size = drop_size.min(max_stack_size - slot_stack.size);
+                        cursor_stack.size -= drop_size;
+
+                        let mut new_slot_stack = slot_stack;
+                        new_slot_stack.size += drop_size;
+                        slot_handle.set_stack(new_slot_stack);
+
+                    }
+                }
+
+            } else if let SlotAccess::Pickup(min_size) = slot_access {
+
+                // This last case is when the slot and the cursor are not empty, but we
+                // can't drop the cursor into the slot, in such case we try to pick item.
+
+                if (slot_stack.id, slot_stack.damage) == (cursor_stack.id, cursor_stack.damage) {
+                    let cursor_item = item::from_id(cursor_stack.id);
+                    if cursor_stack.size < cursor_item.max_stack_size {
+                        let available_size = cursor_item.max_stack_size - cursor_stack.size;
+                        if available_size >= min_size {
+                            let pick_size = slot_stack.size.min(available_size);
+                            cursor_stack.size += pick_size;
+                            let new_slot_stack = slot_stack.with_size(slot_stack.size - pick_size);
+                            slot_handle.set_stack(new_slot_stack.to_non_empty().unwrap_or_default());
+                        }
+                    }
+                }
+
+            }
+
+        }
+
+        inventory.cursor_stack = cursor_stack.to_non_empty().unwrap_or_default();
+        inventory.update_craft();
+
+        // The click is accepted if the packet's stack is the same as the server's slot
+        // stack, the proxy resend the whole window otherwise.
+        slot_stack.to_non_empty() == packet.stack.to_non_empty()
+
+    }
+
+    /// Internal function to shift click the crafting result, the result is moved to the
+    /// main inventory and the recipe consumed only if it can fully fit. The crafting
+    /// result is returned because it is the clicked slot's stack.
+    fn take_craft_result(main_inv: &mut InventoryHandle, craft_inv: &mut [ItemStack], craft_result: ItemStack) -> ItemStack {
+
+        let mut result_stack = craft_result;
+        if !result_stack.is_empty() && main_inv.can_push(result_stack) {
+
+            let craft_inv: &mut [ItemStack; 9] = craft_inv.try_into().unwrap();
+            let mut craft_tracker = CraftTracker::default();
+            craft_tracker.update(craft_inv);
+            craft_tracker.consume(craft_inv);
+
+            main_inv.push_back_in(&mut result_stack, 0..9);
+            main_inv.push_back_in(&mut result_stack, 9..36);
+            assert!(result_stack.is_empty());
+
+        }
+
+        craft_result
+
+    }
+
+    /// Handle a window close packet, the current window is closed and the player goes
+    /// back to its inventory window.
+    pub fn handle_window_close(&self, window_id: u8) -> Result<(), String> {
+
+        let mut window = StdbWindow::filter_by_entity_id(&self.entity_id).ok_or(
+            format!("Could not find window for entity id: {}", self.entity_id))?;
+        let mut inventory = StdbPlayerInventory::filter_by_entity_id(&self.entity_id).ok_or(
+            format!("Could not find inventory for entity id: {}", self.entity_id))?;
+
+        if window.id != window_id {
+            return Err(format!("from {}, incoherent window id to close, expected {}, got {} from client", self.username, window.id, window_id));
+        }
+
+        let entity = StdbEntity::filter_by_entity_id(&self.entity_id).ok_or(
+            format!("Could not find entity with id: {}", self.entity_id))?;
+        let mut world = StdbWorld::filter_by_dimension_id(&entity.dimension_id).ok_or(
+            format!("Could not find world with dimension id: {}", entity.dimension_id))?;
+
+        self.close_window(&mut world, &mut window, &mut inventory);
+
+        StdbWorld::update_by_dimension_id(&entity.dimension_id, world);
+        StdbPlayerInventory::update_by_entity_id(&self.entity_id, inventory);
+        StdbWindow::update_by_entity_id(&self.entity_id, window);
+        Ok(())
+
+    }
+
+    /// Handle a window transaction packet, the client sends it to acknowledge a refused
+    /// transaction after which its window can be trusted again.
+    pub fn handle_window_transaction(&self, packet: StdbWindowTransactionPacket) -> Result<(), String> {
+
+        let mut window = StdbWindow::filter_by_entity_id(&self.entity_id).ok_or(
+            format!("Could not find window for entity id: {}", self.entity_id))?;
+
+        let Some(transaction) = &window.transaction else {
+            return Err(format!("from {}, unexpected transaction {}", self.username, packet.transaction_id));
+        };
+
+        if transaction.window_id != packet.window_id || transaction.transaction_id != packet.transaction_id {
+            return Err(format!("from {}, incoherent transaction {} in window {}", self.username, packet.transaction_id, packet.window_id));
+        }
+
+        if packet.accepted && !window.synced {
+            window.synced = true;
+            StdbWindow::update_by_entity_id(&self.entity_id, window);
+        }
+
+        Ok(())
+
+    }
+
+    /// Handle an update sign packet, sent when the player is done editing a sign. Lines
+    /// that are too long or contain characters that the client cannot render are replaced,
+    /// like the notchian server does. A sign can only be edited once, by its placer.
+    pub fn handle_update_sign(&self, pos: StdbI32Vec3, lines: Vec<String>) -> Result<(), String> {
+
+        let entity = StdbEntity::filter_by_entity_id(&self.entity_id).ok_or(
+            format!("Could not find entity with id: {}", self.entity_id))?;
+
+        let mut sign = StdbSignBlockEntity::find(pos.into()).ok_or(
+            format!("from {}, no sign to update at {:?}", self.username, pos))?;
+
+        // Check if the player is reasonably near the sign, like when placing it.
+        if entity.pos.as_dvec3().distance_squared(IVec3::from(pos).as_dvec3() + 0.5) >= 64.0 {
+            return Err(format!("from {}, sign too far to be updated: {:?}", self.username, pos));
+        }
+
+        // REF: NetServerHandler::handleUpdateSign
+        if !sign.can_edit(self.entity_id) {
+            return Err(format!("from {}, sign not editable at {:?}", self.username, pos));
+        }
+
+        if lines.len() != SIGN_LINES_COUNT {
+            return Err(format!("from {}, invalid sign lines count {}", self.username, lines.len()));
+        }
+
+        sign.lines = lines.into_iter()
+            .map(|line| if is_valid_line(&line) { line } else { "!?".to_string() })
+            .collect();
+        sign.editable = false;
+
+        sign.set();
+        Ok(())
+
+    }
+
+    // fn handle_animation(&mut self, _world: &mut World, _packet: proto::AnimationPacket) {
+    //     // TODO: Send animation to other players.
     // }
 
+    /// Handle an interact packet, sent when the player left clicks (attack) or right
+    /// clicks an entity. The hurt is applied to the target on the next world tick.
+    pub fn handle_interact(&self, target_id: u32, left_click: bool) -> Result<(), String> {
+
+        let entity = StdbEntity::filter_by_entity_id(&self.entity_id).ok_or(
+            format!("Could not find entity with id: {}", self.entity_id))?;
+        let mut world = StdbWorld::filter_by_dimension_id(&entity.dimension_id).ok_or(
+            format!("Could not find world for dimension: {}", entity.dimension_id))?;
+
+        if target_id == self.entity_id {
+            return Err(format!("from {}, cannot interact with itself", self.username));
+        }
+
+        let Some(Entity(_, BaseKind::Living(living, _))) = world.get_entity(self.entity_id) else {
+            return Err(format!("from {}, incoherent player entity: {}", self.username, self.entity_id));
+        };
+
+        // Dead players waiting to respawn cannot interact.
+        if living.health == 0 {
+            return Err(format!("from {}, cannot interact while dead", self.username));
+        }
+
+        let Some(mut target) = world.get_entity(target_id) else {
+            return Err(format!("from {}, incoherent interact entity target: {}", self.username, target_id));
+        };
+
+        let Entity(target_base, target_kind) = &mut target;
+
+        if DVec3::from(entity.pos).distance_squared(target_base.pos) >= 36.0 {
+            return Err(format!("from {}, incoherent interact entity distance", self.username));
+        }
+
+        if left_click {
+
+            // Only living entities that are still alive can be attacked for now.
+            let BaseKind::Living(target_living, _) = target_kind else {
+                return Ok(());
+            };
+
+            if target_living.health == 0 {
+                return Ok(());
+            }
+
+            let inventory = StdbPlayerInventory::filter_by_entity_id(&self.entity_id).ok_or(
+                format!("Could not find inventory for entity id: {}", self.entity_id))?;
+
+            // TODO: Critical damage if vel.y < 0
+
+            let damage = item::attack::get_base_damage(inventory.hand_stack().id);
+            target_base.hurt.push(Hurt {
+                damage,
+                origin_id: Some(self.entity_id),
+            });
+
+            world.set_entity(target_id, &target);
+
+        } else {
+            // TODO: Right click interactions, like riding or shearing.
+        }
+
+        Ok(())
+
+    }
+
+    /// Handle a respawn packet from a dead player, its entity is created again next to
+    /// its bed if still valid, or at the world spawn otherwise.
+    ///
+    /// REF: ServerConfigurationManager::recreatePlayerEntity
+    pub fn handle_respawn(&self, cache: &mut ChunkCache) -> Result<(), String> {
+
+        let entity = StdbEntity::filter_by_entity_id(&self.entity_id).ok_or(
+            format!("Could not find entity with id: {}", self.entity_id))?;
+        let mut world = StdbWorld::filter_by_dimension_id(&entity.dimension_id).ok_or(
+            format!("Could not find world for dimension: {}", entity.dimension_id))?;
+
+        let Some(Entity(base, BaseKind::Living(living, LivingKind::Human(human)))) = world.get_entity(self.entity_id) else {
+            return Err(format!("from {}, incoherent player entity: {}", self.username, self.entity_id));
+        };
+
+        if living.health != 0 {
+            return Err(format!("from {}, cannot respawn while alive", self.username));
+        }
+
+        // TODO: Send the "tile.bed.notValid" notification when the bed is missing.
+        let bed_spawn_pos = human.bed_pos.and_then(|bed_pos| world.find_bed_spawn_pos(bed_pos, cache));
+        let pos = match bed_spawn_pos {
+            Some(pos) => pos.as_dvec3() + DVec3::new(0.5, 0.1, 0.5),
+            None => self.spawn_pos.as_dvec3(),
+        };
+
+        world.set_entity(self.entity_id, &Human::new_with(|new_base, new_living, new_human| {
+            new_base.pos = pos;
+            new_base.look = base.look;
+            new_base.can_pickup = true;
+            new_living.artificial = true;
+            new_human.username = human.username;
+            new_human.bed_pos = bed_spawn_pos.and(human.bed_pos);
+        }));
+
+        StdbWorld::update_by_dimension_id(&entity.dimension_id, world);
+
+        let mut state = StdbServerWorld::filter_by_dimension_id(&entity.dimension_id).ok_or(
+            format!("Could not find server world for dimension: {}", entity.dimension_id))?;
+        state.handle_player_respawn(self.clone());
+
+        Ok(())
+
+    }
+
+    /// Handle an action packet from the player, used to sneak and to leave the bed.
+    pub fn handle_action(&self, state: u8, cache: &mut ChunkCache) -> Result<(), String> {
+
+        let entity = StdbEntity::filter_by_entity_id(&self.entity_id).ok_or(
+            format!("Could not find entity with id: {}", self.entity_id))?;
+        let mut world = StdbWorld::filter_by_dimension_id(&entity.dimension_id).ok_or(
+            format!("Could not find world for dimension: {}", entity.dimension_id))?;
+
+        match state {
+            1 | 2 => {
+                // A player action is only relevant on human entities.
+                let Some(mut player_entity) = world.get_entity(self.entity_id) else {
+                    return Err(format!("from {}, incoherent player entity: {}", self.username, self.entity_id));
+                };
+                if let Entity(_, BaseKind::Living(_, LivingKind::Human(human))) = &mut player_entity {
+                    human.sneaking = state == 1;
+                    world.set_entity(self.entity_id, &player_entity);
+                }
+            }
+            3 => {
+                world.wake_up_player(self.entity_id, cache);
+            }
+            _ => return Err(format!("from {}, invalid action state: {}", self.username, state)),
+        }
+
+        StdbWorld::update_by_dimension_id(&entity.dimension_id, world);
+        Ok(())
+
+    }
+
+    /// Close the current window opened by the player and go back to the player window.
+    /// The cursor stack and the crafting matrix are moved back to the main inventory,
+    /// stacks that don't fit are dropped in front of the player.
+    pub fn close_window(&self, world: &mut StdbWorld, window: &mut StdbWindow, inventory: &mut StdbPlayerInventory) {
+
+        let mut main_inv = InventoryHandle::new(&mut inventory.main_inv[..]);
+
+        let stacks = std::iter::once(&mut inventory.cursor_stack)
+            .chain(inventory.craft_inv.iter_mut());
+
+        for stack in stacks {
+            main_inv.push_front(stack);
+            if !stack.is_empty() {
+                self.drop_stack(world, *stack, false);
+                *stack = ItemStack::EMPTY;
+            }
+        }
+
+        inventory.update_craft();
+
+        // Reset to the default window.
+        window.id = 0;
+        window.kind = StdbWindowKind::Player;
+
+    }
+
+    /// Open the given window kind, the proxy opens it on client-side when the window id
+    /// changes. A new window id is automatically associated to that window.
+    pub fn open_window(&self, world: &mut StdbWorld, inventory: &mut StdbPlayerInventory, kind: StdbWindowKind) -> Result<(), String> {
+
+        let mut window = StdbWindow::filter_by_entity_id(&self.entity_id).ok_or(
+            format!("Could not find window for entity id: {}", self.entity_id))?;
+
+        // Close any already opened window.
+        self.close_window(world, &mut window, inventory);
+
+        // NOTE: We should never get a window id of 0 because it is the player inventory.
+        window.id = (window.count % 100 + 1) as u8;
+        window.count += 1;
+        window.kind = kind;
+        window.synced = true;
+
+        StdbWindow::update_by_entity_id(&self.entity_id, window);
+        Ok(())
+
+    }
+
+    /// Close the window opened by the player if the block entities or the crafting table
+    /// it references have been removed, the proxy then closes it on client side.
+    ///
+    /// REF: EntityPlayerMP::onUpdate
+    pub fn check_window(&self, world: &mut StdbWorld, cache: &mut ChunkCache) {
+
+        let Some(mut window) = StdbWindow::filter_by_entity_id(&self.entity_id) else { return };
+
+        let valid = match &window.kind {
+            StdbWindowKind::Player => return,
+            StdbWindowKind::CraftingTable(pos) => world.is_block((*pos).into(), block::CRAFTING_TABLE, cache),
+            _ => WindowBlockEntities::load(&window).is_some(),
+        };
+
+        if !valid {
+            let Some(mut inventory) = StdbPlayerInventory::filter_by_entity_id(&self.entity_id) else { return };
+            self.close_window(world, &mut window, &mut inventory);
+            StdbPlayerInventory::update_by_entity_id(&self.entity_id, inventory);
+            StdbWindow::update_by_entity_id(&self.entity_id, window);
+        }
+
+    }
+
+    /// Internal function to create a window slot handle specifically for a player main
+    /// inventory slot, the offset of the first player inventory slot is also given.
+    fn make_player_window_slot_handle(inventory: &mut StdbPlayerInventory, slot: i16, offset: i16) -> Option<SlotHandle<'_>> {
+
+        let index = match slot - offset {
+            0..=26 => slot - offset + 9,
+            27..=35 => slot - offset - 27,
+            _ => return None,
+        } as usize;
+
+        Some(SlotHandle {
+            kind: SlotKind::Standard {
+                stack: &mut inventory.main_inv[index],
+                access: SlotAccess::PickupDrop,
+                max_size: 64,
+            },
+        })
+
+    }
+
+    /// Internal function to create a window slot handle. This handle is temporary and
+    /// own a mutable reference to the player's inventory, it can only work on the given
+    /// slot.
+    fn make_window_slot_handle<'a>(window: &StdbWindow, inventory: &'a mut StdbPlayerInventory, block_entities: &'a mut WindowBlockEntities, slot: i16) -> Option<SlotHandle<'a>> {
+
+        // This avoid temporary cast issues afterward, even if we keep the signed type.
+        if slot < 0 {
+            return None;
+        }
+
+        Some(match window.kind {
+            StdbWindowKind::Player => {
+                match slot {
+                    0 => SlotHandle {
+                        kind: SlotKind::CraftingResult {
+                            craft_inv: (&mut inventory.craft_inv[..]).try_into().unwrap(),
+                            craft_result: inventory.craft_result,
+                        },
+                    },
+                    1..=4 => SlotHandle {
+                        kind: SlotKind::Standard {
+                            stack: &mut inventory.craft_inv[match slot {
+                                1 => 0,
+                                2 => 1,
+                                3 => 3,
+                                4 => 4,
+                                _ => unreachable!()
+                            }],
+                            access: SlotAccess::PickupDrop,
+                            max_size: 64,
+                        },
+                    },
+                    5..=8 => SlotHandle {
+                        kind: SlotKind::Standard {
+                            stack: &mut inventory.armor_inv[slot as usize - 5],
+                            access: match slot {
+                                5 => SlotAccess::ArmorHelmet,
+                                6 => SlotAccess::ArmorChestplate,
+                                7 => SlotAccess::ArmorLeggings,
+                                8 => SlotAccess::ArmorBoots,
+                                _ => unreachable!(),
+                            }, max_size: 1,
+                        },
+                    },
+                    _ => Self::make_player_window_slot_handle(inventory, slot, 9)?
+                }
+            }
+            StdbWindowKind::CraftingTable(_) => {
+                match slot {
+                    0 => SlotHandle {
+                        kind: SlotKind::CraftingResult {
+                            craft_inv: (&mut inventory.craft_inv[..]).try_into().unwrap(),
+                            craft_result: inventory.craft_result,
+                        },
+                    },
+                    1..=9 => SlotHandle {
+                        kind: SlotKind::Standard {
+                            stack: &mut inventory.craft_inv[slot as usize - 1],
+                            access: SlotAccess::PickupDrop,
+                            max_size: 64,
+                        },
+                    },
+                    _ => Self::make_player_window_slot_handle(inventory, slot, 10)?
+                }
+            }
+            StdbWindowKind::Chest(_) => {
+                let index = slot as usize;
+                let chests_size = block_entities.chests.len() * CHEST_INV_SIZE;
+                if let Some(chest) = block_entities.chests.get_mut(index / CHEST_INV_SIZE) {
+                    SlotHandle {
+                        kind: SlotKind::Standard {
+                            stack: &mut chest.inv[index % CHEST_INV_SIZE],
+                            access: SlotAccess::PickupDrop,
+                            max_size: 64,
+                        },
+                    }
+                } else {
+                    Self::make_player_window_slot_handle(inventory, slot, chests_size as i16)?
+                }
+            }
+            StdbWindowKind::Furnace(_) => {
+                if slot <= 2 {
+
+                    let furnace = block_entities.furnace.as_mut()?;
+                    let (stack, access) = match slot {
+                        0 => (&mut furnace.input_stack, SlotAccess::PickupDrop),
+                        1 => (&mut furnace.fuel_stack, SlotAccess::PickupDrop),
+                        2 => (&mut furnace.output_stack, SlotAccess::Pickup(1)),
+                        _ => unreachable!()
+                    };
+
+                    SlotHandle {
+                        kind: SlotKind::Standard {
+                            stack,
+                            access,
+                            max_size: 64,
+                        },
+                    }
+
+                } else {
+                    Self::make_player_window_slot_handle(inventory, slot, 3)?
+                }
+            }
+            StdbWindowKind::Dispenser(_) => {
+                if (slot as usize) < DISPENSER_INV_SIZE {
+                    let dispenser = block_entities.dispenser.as_mut()?;
+                    SlotHandle {
+                        kind: SlotKind::Standard {
+                            stack: &mut dispenser.inv[slot as usize],
+                            access: SlotAccess::PickupDrop,
+                            max_size: 64,
+                        },
+                    }
+                } else {
+                    Self::make_player_window_slot_handle(inventory, slot, DISPENSER_INV_SIZE as i16)?
+                }
+            }
+        })
+
+    }
+
+    /// Drop an item from the player's entity, items are drop in front of the player, but
+    /// the `on_ground` argument can be set to true in order to drop item on the ground.
+    pub fn drop_stack(&self, world: &mut StdbWorld, stack: ItemStack, on_ground: bool) {
+
+        let Some(mut origin) = world.get_entity(self.entity_id) else { return };
+        let Entity(origin_base, _) = &mut origin;
+
+        let entity = Item::new_with(|base, item| {
+
+            base.persistent = true;
+            base.pos = origin_base.pos;
+            base.pos.y += 1.3;  // TODO: Adjust depending on eye height.
+
+            if on_ground {
+
+                let rand_drop_speed = origin_base.rand.next_float() * 0.5;
+                let rand_yaw = origin_base.rand.next_float() * std::f32::consts::TAU;
+
+                base.vel.x = (rand_yaw.sin() * rand_drop_speed) as f64;
+                base.vel.z = (rand_yaw.cos() * rand_drop_speed) as f64;
+                base.vel.y = 0.2;
+
+            } else {
+
+                let drop_speed = 0.3;
+                let rand_yaw = base.rand.next_float() * std::f32::consts::TAU;
+                let rand_drop_speed = base.rand.next_float() * 0.02;
+                let rand_vel_y = (base.rand.next_float() - base.rand.next_float()) * 0.1;
+
+                base.vel.x = (-origin_base.look.x.sin() * origin_base.look.y.cos() * drop_speed) as f64;
+                base.vel.z = (origin_base.look.x.cos() * origin_base.look.y.cos() * drop_speed) as f64;
+                base.vel.y = (-origin_base.look.y.sin() * drop_speed + 0.1) as f64;
+                base.vel.x += (rand_yaw.cos() * rand_drop_speed) as f64;
+                base.vel.z += (rand_yaw.sin() * rand_drop_speed) as f64;
+                base.vel.y += rand_vel_y as f64;
+
+            }
+
+            item.frozen_time = 40;
+            item.stack = stack;
+
+        });
+
+        world.spawn_entity(entity);
+        // The player's random generator may have been used.
+        world.set_entity(self.entity_id, &origin);
+
+    }
+
     /// Update the chunks sent to this player.
     pub fn update_chunks(player_id: u32) {
 
@@ -1467,6 +1490,7 @@ impl StdbServerPlayer {
                 if query!(|view: StdbChunkView| view.chunk_id == chunk_id && view.observer_id == player_id).next().is_none() {
                     let _ = StdbChunkView::insert(StdbChunkView {
                         view_id: 0,
+                        dimension_id: player_entity.dimension_id,
                         chunk_id,
                         observer_id: player_id,
                     });
@@ -1501,279 +1525,204 @@ impl StdbServerPlayer {
 
     }
 
-    //// Make this player pickup an item stack, the stack and its size is modified
-    //// regarding the amount actually picked up.
-    // pub fn pickup_stack(&mut self, stack: &mut ItemStack) {
-    //
-    //     let mut inv = InventoryHandle::new(&mut self.main_inv[..]);
-    //     inv.push_front(stack);
-    //
-    //     // Update the associated slots in the player inventory.
-    //     for index in inv.iter_changes() {
-    //         self.send_main_inv_item(index);
-    //     }
-    //
-    // }
+    /// Make this player pickup an item stack, the stack and its size is modified
+    /// regarding the amount actually picked up.
+    pub fn pickup_stack(&self, stack: &mut ItemStack) {
 
-    //// For the given block position, close any window that may be linked to it. This is
-    //// usually called when the block entity or crafting table is removed.
-    // pub fn close_block_window(&mut self, world: &mut World, target_pos: IVec3) {
-    //
-    //     let contains = match self.window.kind {
-    //         WindowKind::Player => false,
-    //         WindowKind::Furnace { pos } |
-    //         WindowKind::Dispenser { pos } |
-    //         WindowKind::CraftingTable { pos } =>
-    //             pos == target_pos,
-    //         WindowKind::Chest { ref pos } =>
-    //             pos.iter().any(|&pos| pos == target_pos),
-    //     };
-    //
-    //     if contains {
-    //         self.close_window(world, None, true);
-    //     }
-    //
-    // }
+        let Some(mut inventory) = StdbPlayerInventory::filter_by_entity_id(&self.entity_id) else { return };
 
-    //// If this player has a window opened for the given position, this will update the
-    //// displayed storage according to the given storage event.
-    // pub fn update_block_window_storage(&mut self, target_pos: IVec3, storage: BlockEntityStorage, stack: ItemStack) {
-    //
-    //     match self.window.kind {
-    //         WindowKind::Chest { ref pos } => {
-    //             if let Some(row) = pos.iter().position(|&pos| pos == target_pos) {
-    //
-    //                 if let BlockEntityStorage::Standard(index) = storage {
-    //                     self.send(OutPacket::WindowSetItem(proto::WindowSetItemPacket {
-    //                         window_id: self.window.id,
-    //                         slot: row as i16 * 27 + index as i16,
-    //                         stack: stack.to_non_empty(),
-    //                     }));
-    //                 }
-    //
-    //             }
-    //         }
-    //         WindowKind::Furnace { pos } => {
-    //             if pos == target_pos {
-    //
-    //                 let slot = match storage {
-    //                     BlockEntityStorage::FurnaceInput => 0,
-    //                     BlockEntityStorage::FurnaceFuel => 1,
-    //                     BlockEntityStorage::FurnaceOutput => 2,
-    //                     _ => return,
-    //                 };
-    //
-    //                 self.send(OutPacket::WindowSetItem(proto::WindowSetItemPacket {
-    //                     window_id: self.window.id,
-    //                     slot,
-    //                     stack: stack.to_non_empty(),
-    //                 }));
-    //
-    //             }
-    //         }
-    //         WindowKind::Dispenser { pos } => {
-    //             if pos == target_pos {
-    //                 if let BlockEntityStorage::Standard(index) = storage {
-    //
-    //                     self.send(OutPacket::WindowSetItem(proto::WindowSetItemPacket {
-    //                         window_id: self.window.id,
-    //                         slot: index as i16,
-    //                         stack: stack.to_non_empty(),
-    //                     }));
-    //
-    //                 }
-    //             }
-    //         }
-    //         _ => {}  // Not handled.
-    //     }
-    // }
+        let mut inv = InventoryHandle::new(&mut inventory.main_inv[..]);
+        inv.push_front(stack);
 
-    //// If this player has a window opened for the given position, this will update the
-    //// displayed storage according to the given storage event.
-    // pub fn update_block_window_progress(&mut self, target_pos: IVec3, progress: BlockEntityProgress, value: u16) {
-    //
-    //     if let WindowKind::Furnace { pos } = self.window.kind {
-    //         if pos == target_pos {
-    //
-    //             let bar_id = match progress {
-    //                 BlockEntityProgress::FurnaceSmeltTime => 0,
-    //                 BlockEntityProgress::FurnaceBurnRemainingTime => 1,
-    //                 BlockEntityProgress::FurnaceBurnMaxTime => 2,
-    //             };
-    //
-    //             self.send(OutPacket::WindowProgressBar(proto::WindowProgressBarPacket {
-    //                 window_id: self.window.id,
-    //                 bar_id,
-    //                 value: value as i16,
-    //             }));
-    //
-    //         }
-    //     }
-    //
-    // }
+        // The changed slots are sent by the proxy when the inventory is updated.
+        if inv.iter_changes().next().is_some() {
+            StdbPlayerInventory::update_by_entity_id(&self.entity_id, inventory);
+        }
+
+    }
 
 }
 
-//// A pointer to a slot in an inventory.
-// struct SlotHandle<'a> {
-//     /// True if the client is able to drop item into this stack, if not then it can only
-//     /// pickup the item stack.
-//     kind: SlotKind<'a>,
-//     notify: SlotNotify,
-// }
+/// The block entities referenced by a window, loaded while handling a click.
+#[derive(Clone)]
+struct WindowBlockEntities {
+    /// The chests of a chest window, in window order.
+    chests: Vec<StdbChestBlockEntity>,
+    /// The furnace of a furnace window.
+    furnace: Option<StdbFurnaceBlockEntity>,
+    /// The dispenser of a dispenser window.
+    dispenser: Option<StdbDispenserBlockEntity>,
+}
 
-//// Represent a major slot kind.
-// enum SlotKind<'a> {
-//     /// A standard slot referencing a single item stack.
-//     Standard {
-//         /// The stack referenced by this slot handle.
-//         stack: &'a mut ItemStack,
-//         /// The access kind to this slot.
-//         access: SlotAccess,
-//         /// The maximum stack size this slot can accept.
-//         max_size: u16,
-//     },
-//     /// The slot represent a crafting result.
-//     CraftingResult {
-//         /// The crafting grid item stacks.
-//         craft_inv: &'a mut [ItemStack; 9],
-//         /// The crafting tracker for the player.
-//         craft_tracker: &'a mut CraftTracker,
-//     },
-// }
+impl WindowBlockEntities {
 
-//// Represent the kind of drop rule to apply to this slot.
-// #[derive(Clone, Copy)]
-// enum SlotAccess {
-//     /// The cursor is able to pickup and drop items into this slot.
-//     PickupDrop,
-//     /// The cursor isn't able to drop items into this slot, it can only pickup. The field
-//     /// gives the minimum number of items that can be picked up at the same time.
-//     /// Typically used for crafting because only a full recipe result can be picked up.
-//     Pickup(u16),
-//     /// This slot only accepts helmet armor items.
-//     ArmorHelmet,
-//     /// This slot only accepts chestplate armor items.
-//     ArmorChestplate,
-//     /// This slot only accepts leggings armor items.
-//     ArmorLeggings,
-//     /// This slot only accepts boots armor items.
-//     ArmorBoots,
-// }
+    /// Load the block entities referenced by the given window, this returns none if any
+    /// of these block entities no longer exists.
+    fn load(window: &StdbWindow) -> Option<Self> {
+        let mut ret = Self { chests: Vec::new(), furnace: None, dispenser: None };
+        match &window.kind {
+            StdbWindowKind::Chest(pos) => {
+                ret.chests = pos.iter()
+                    .map(|&pos| StdbChestBlockEntity::find(pos.into()))
+                    .collect::<Option<_>>()?;
+            }
+            StdbWindowKind::Furnace(pos) => {
+                ret.furnace = Some(StdbFurnaceBlockEntity::find((*pos).into())?);
+            }
+            StdbWindowKind::Dispenser(pos) => {
+                ret.dispenser = Some(StdbDispenserBlockEntity::find((*pos).into())?);
+            }
+            _ => {}
+        }
+        Some(ret)
+    }
 
-//// Type of notification that will be triggered when the slot gets modified.
-// enum SlotNotify {
-//     /// The modification of the slot has no effect.
-//     None,
-//     /// The modification of the slot requires the crafting matrix to be resent.
-//     /// This should only be used for craft matrix windows, where the craft result is in
-//     /// slot 0.
-//     Craft {
-//         /// For each craft inventory stack a client slot number. If not present, this
-//         /// means that the crafting matrix should not be updated. If the slot should not
-//         /// be sent to the client, then the value must be negative.
-//         mapping: Option<&'static [i16; 9]>,
-//         /// True if the craft result should be updated from matrix and resent.
-//         modified: bool,
-//     },
-//     /// A block entity storage event need to be pushed to the world.
-//     BlockEntityStorageEvent {
-//         /// The position of the block entity.
-//         pos: IVec3,
-//         /// The index of the inventory stack that is modified.
-//         storage: BlockEntityStorage,
-//         /// If the stack is actually modified, this is the new item stack at the index.
-//         stack: Option<ItemStack>,
-//     }
-// }
+    /// Only update the block entities that have been modified since the given previous
+    /// state, each update is sent by the proxy to every player viewing the block.
+    fn save(self, prev: Self) {
 
-// impl<'a> SlotHandle<'a> {
-//
-//     /// Get the maximum stack size for that slot.
-//     fn max_stack_size(&self) -> u16 {
-//         match self.kind {
-//             SlotKind::Standard { max_size, .. } => max_size,
-//             SlotKind::CraftingResult { .. } => 64,
-//         }
-//     }
-//
-//     /// Get the access rule to this slot.
-//     fn get_access(&self) -> SlotAccess {
-//         match self.kind {
-//             SlotKind::Standard { access, .. } => access,
-//             SlotKind::CraftingResult { ref craft_tracker, .. } =>
-//                 SlotAccess::Pickup(craft_tracker.recipe().map(|stack| stack.size).unwrap_or(0)),
-//         }
-//     }
-//
-//     /// Get the stack in this slot.
-//     fn get_stack(&mut self) -> ItemStack {
-//         match &self.kind {
-//             SlotKind::Standard { stack, .. } => **stack,
-//             SlotKind::CraftingResult { craft_tracker, .. } =>
-//                 craft_tracker.recipe().unwrap_or_default()
-//         }
-//     }
-//
-//     /// Set the stack in this slot, called if `is_valid` previously returned `true`, if
-//     /// the latter return `false`, this function can only be called with `EMPTY` stack.
-//     ///
-//     /// This function also push the slot changes that happened into `slot_changes` of the
-//     /// server player temporary vector.
-//     fn set_stack(&mut self, new_stack: ItemStack) {
-//
-//         match &mut self.kind {
-//             SlotKind::Standard { stack, .. } => {
-//                 **stack = new_stack;
-//             }
-//             SlotKind::CraftingResult {
-//                 craft_inv,
-//                 craft_tracker,
-//             } => {
-//                 craft_tracker.consume(*craft_inv);
-//             }
-//         }
-//
-//         match &mut self.notify {
-//             SlotNotify::None => {}
-//             SlotNotify::Craft { modified, .. } => *modified = true,
-//             SlotNotify::BlockEntityStorageEvent { stack, .. } => *stack = Some(new_stack),
-//         }
-//
-//     }
-//
-// }
+        for (chest, prev_chest) in self.chests.into_iter().zip(prev.chests) {
+            if chest.inv != prev_chest.inv {
+                StdbChestBlockEntity::update_by_block_entity_id(&chest.block_entity_id.clone(), chest);
+            }
+        }
 
-// impl SlotAccess {
-//
-//     fn can_drop(self, stack: ItemStack) -> bool {
-//         match self {
-//             SlotAccess::PickupDrop => true,
-//             SlotAccess::Pickup(_) => false,
-//             SlotAccess::ArmorHelmet => matches!(stack.id,
-//                 item::LEATHER_HELMET |
-//                 item::GOLD_HELMET |
-//                 item::CHAIN_HELMET |
-//                 item::IRON_HELMET |
-//                 item::DIAMOND_HELMET) || stack.id == block::PUMPKIN as u16,
-//             SlotAccess::ArmorChestplate => matches!(stack.id,
-//                 item::LEATHER_CHESTPLATE |
-//                 item::GOLD_CHESTPLATE |
-//                 item::CHAIN_CHESTPLATE |
-//                 item::IRON_CHESTPLATE |
-//                 item::DIAMOND_CHESTPLATE),
-//             SlotAccess::ArmorLeggings => matches!(stack.id,
-//                 item::LEATHER_LEGGINGS |
-//                 item::GOLD_LEGGINGS |
-//                 item::CHAIN_LEGGINGS |
-//                 item::IRON_LEGGINGS |
-//                 item::DIAMOND_LEGGINGS),
-//             SlotAccess::ArmorBoots => matches!(stack.id,
-//                 item::LEATHER_BOOTS |
-//                 item::GOLD_BOOTS |
-//                 item::CHAIN_BOOTS |
-//                 item::IRON_BOOTS |
-//                 item::DIAMOND_BOOTS),
-//         }
-//     }
-// }
+        if let Some(furnace) = self.furnace {
+            if Some(&furnace) != prev.furnace.as_ref() {
+                StdbFurnaceBlockEntity::update_by_block_entity_id(&furnace.block_entity_id.clone(), furnace);
+            }
+        }
+
+        if let (Some(dispenser), Some(prev_dispenser)) = (self.dispenser, prev.dispenser) {
+            if dispenser.inv != prev_dispenser.inv {
+                StdbDispenserBlockEntity::update_by_block_entity_id(&dispenser.block_entity_id.clone(), dispenser);
+            }
+        }
+
+    }
+
+}
+
+/// A pointer to a slot in an inventory.
+struct SlotHandle<'a> {
+    /// True if the client is able to drop item into this stack, if not then it can only
+    /// pickup the item stack.
+    kind: SlotKind<'a>,
+}
+
+/// Represent a major slot kind.
+enum SlotKind<'a> {
+    /// A standard slot referencing a single item stack.
+    Standard {
+        /// The stack referenced by this slot handle.
+        stack: &'a mut ItemStack,
+        /// The access kind to this slot.
+        access: SlotAccess,
+        /// The maximum stack size this slot can accept.
+        max_size: u16,
+    },
+    /// The slot represent a crafting result.
+    CraftingResult {
+        /// The crafting grid item stacks.
+        craft_inv: &'a mut [ItemStack; 9],
+        /// The current result of the crafting grid.
+        craft_result: ItemStack,
+    },
+}
+
+/// Represent the kind of drop rule to apply to this slot.
+#[derive(Clone, Copy)]
+enum SlotAccess {
+    /// The cursor is able to pickup and drop items into this slot.
+    PickupDrop,
+    /// The cursor isn't able to drop items into this slot, it can only pickup. The field
+    /// gives the minimum number of items that can be picked up at the same time.
+    /// Typically used for crafting because only a full recipe result can be picked up.
+    Pickup(u16),
+    /// This slot only accepts helmet armor items.
+    ArmorHelmet,
+    /// This slot only accepts chestplate armor items.
+    ArmorChestplate,
+    /// This slot only accepts leggings armor items.
+    ArmorLeggings,
+    /// This slot only accepts boots armor items.
+    ArmorBoots,
+}
+
+impl<'a> SlotHandle<'a> {
+
+    /// Get the maximum stack size for that slot.
+    fn max_stack_size(&self) -> u16 {
+        match self.kind {
+            SlotKind::Standard { max_size, .. } => max_size,
+            SlotKind::CraftingResult { .. } => 64,
+        }
+    }
+
+    /// Get the access rule to this slot.
+    fn get_access(&self) -> SlotAccess {
+        match self.kind {
+            SlotKind::Standard { access, .. } => access,
+            SlotKind::CraftingResult { craft_result, .. } => SlotAccess::Pickup(craft_result.size),
+        }
+    }
+
+    /// Get the stack in this slot.
+    fn get_stack(&mut self) -> ItemStack {
+        match &self.kind {
+            SlotKind::Standard { stack, .. } => **stack,
+            SlotKind::CraftingResult { craft_result, .. } => *craft_result,
+        }
+    }
+
+    /// Set the stack in this slot, called if `can_drop` previously returned `true`, if
+    /// the latter return `false`, this function can only be called with `EMPTY` stack.
+    fn set_stack(&mut self, new_stack: ItemStack) {
+        match &mut self.kind {
+            SlotKind::Standard { stack, .. } => {
+                **stack = new_stack;
+            }
+            SlotKind::CraftingResult { craft_inv, .. } => {
+                let mut craft_tracker = CraftTracker::default();
+                craft_tracker.update(craft_inv);
+                craft_tracker.consume(craft_inv);
+            }
+        }
+    }
+
+}
+
+impl SlotAccess {
+
+    fn can_drop(self, stack: ItemStack) -> bool {
+        match self {
+            SlotAccess::PickupDrop => true,
+            SlotAccess::Pickup(_) => false,
+            SlotAccess::ArmorHelmet => matches!(stack.id,
+                item::LEATHER_HELMET |
+                item::GOLD_HELMET |
+                item::CHAIN_HELMET |
+                item::IRON_HELMET |
+                item::DIAMOND_HELMET) || stack.id == block::PUMPKIN as u16,
+            SlotAccess::ArmorChestplate => matches!(stack.id,
+                item::LEATHER_CHESTPLATE |
+                item::GOLD_CHESTPLATE |
+                item::CHAIN_CHESTPLATE |
+                item::IRON_CHESTPLATE |
+                item::DIAMOND_CHESTPLATE),
+            SlotAccess::ArmorLeggings => matches!(stack.id,
+                item::LEATHER_LEGGINGS |
+                item::GOLD_LEGGINGS |
+                item::CHAIN_LEGGINGS |
+                item::IRON_LEGGINGS |
+                item::DIAMOND_LEGGINGS),
+            SlotAccess::ArmorBoots => matches!(stack.id,
+                item::LEATHER_BOOTS |
+                item::GOLD_BOOTS |
+                item::CHAIN_BOOTS |
+                item::IRON_BOOTS |
+                item::DIAMOND_BOOTS),
+        }
+    }
+
+}
@@ -303,6 +303,15 @@ pub struct PlaceBlockPacket {
     pub stack: Option<ItemStack>,
 }
 
+/// Packet 15
+#[derive(Debug, Clone, SpacetimeType)]
+pub struct StdbPlaceBlockPacket {
+    pub x: i32,
+    pub y: i8,
+    pub z: i32,
+    pub direction: u8,
+}
+
 /// Packet 16
 #[derive(Debug, Clone)]
 pub struct HandSlotPacket {
@@ -650,6 +659,18 @@ pub struct WindowClickPacket {
     pub stack: Option<ItemStack>,
 }
 
+/// Packet 102
+#[derive(Debug, Clone, SpacetimeType)]
+pub struct StdbWindowClickPacket {
+    pub window_id: u8,
+    pub slot: i16,
+    pub right_click: bool,
+    pub shift_click: bool,
+    pub transaction_id: u16,
+    /// The stack the client thinks is in the clicked slot, empty if none.
+    pub stack: ItemStack,
+}
+
 /// Packet 103
 #[derive(Debug, Clone)]
 pub struct WindowSetItemPacket {
@@ -687,6 +708,14 @@ pub struct WindowTransactionPacket {
     pub accepted: bool,
 }
 
+/// Packet 106
+#[derive(Debug, Clone, SpacetimeType)]
+pub struct StdbWindowTransactionPacket {
+    pub window_id: u8,
+    pub transaction_id: u16,
+    pub accepted: bool,
+}
+
 /// Packet 130
 #[derive(Debug, Clone)]
 pub struct UpdateSignPacket {
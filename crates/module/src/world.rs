@@ -5,16 +5,19 @@ use std::time::Instant;
 
 use glam::{DVec3, IVec3, Vec2};
 use spacetimedb::{query, spacetimedb, SpacetimeType};
-use mc173_module::block;
+use mc173_module::{block, item};
 use mc173_module::chunk::calc_chunk_pos;
 use mc173_module::chunk_cache::ChunkCache;
+use mc173_module::entity::{BaseKind, EntityKind, ProjectileKind};
+use mc173_module::entity::component::{StdbEntity, StdbLivingEntity};
 use mc173_module::geom::Face;
-use mc173_module::stdb::chunk::{ChunkUpdateType, StdbBlockSetUpdate, StdbChunk, StdbChunkUpdate};
+use mc173_module::item::ItemStack;
+use mc173_module::stdb::chunk::{ChunkUpdateType, StdbBlockSetUpdate, StdbChunk, StdbChunkUpdate, StdbChunkView};
 use mc173_module::world::{LightKind, StdbWorld};
 use crate::proto::{self, OutPacket};
 use crate::config;
 use crate::entity::{StdbEntityTracker, StdbEntityView};
-use crate::player::{StdbConnectionStatus, StdbEntity, StdbServerPlayer, StdbTrackedPlayer};
+use crate::player::{StdbConnectionStatus, StdbPlayerInventory, StdbServerPlayer, StdbTrackedPlayer, StdbWindow};
 /// A single world in the server, this structure keep tracks of players and entities
 /// tracked by players.
 // pub struct StdbServerWorld {
@@ -304,7 +307,13 @@ impl StdbServerWorld {
 
         let player_entity = StdbEntity::filter_by_entity_id(&player.entity_id).unwrap();
         for other_entity in StdbEntity::filter_by_dimension_id(&player_entity.dimension_id) {
-            if other_entity.entity_id != player_entity.entity_id {
+            // Other entities are sent through their chunk, only players are tracked.
+            if other_entity.kind == EntityKind::Human && other_entity.entity_id != player_entity.entity_id {
+                // Dead players are hidden until they respawn.
+                if StdbLivingEntity::filter_by_entity_id(&other_entity.entity_id).is_some_and(|living| living.health == 0) {
+                    continue;
+                }
+
                 // TODO(jdetter): Check distance
                 let _ = StdbEntityView::insert(StdbEntityView {
                     view_id: 0,
@@ -332,6 +341,99 @@ impl StdbServerWorld {
         // player_index
     }
 
+    /// Handle a player respawning in this world, its chunk and entity views are created
+    /// again so that its client receives the surrounding chunks and players again.
+    pub fn handle_player_respawn(&mut self, player: StdbServerPlayer) {
+
+        for view in StdbChunkView::filter_by_observer_id(&player.entity_id).collect::<Vec<_>>() {
+            StdbChunkView::delete_by_view_id(&view.view_id);
+        }
+
+        for view in StdbEntityView::filter_by_observer_id(&player.entity_id).collect::<Vec<_>>() {
+            StdbEntityView::delete_by_view_id(&view.view_id);
+        }
+
+        self.handle_player_join(player);
+
+    }
+
+    /// Handle the death of a player, its window is closed and its whole inventory is
+    /// dropped around it.
+    ///
+    /// REF: InventoryPlayer::dropAllItems
+    pub fn handle_player_death(&self, world: &mut StdbWorld, player: &StdbServerPlayer) {
+
+        let (Some(mut window), Some(mut inventory)) = (
+            StdbWindow::filter_by_entity_id(&player.entity_id),
+            StdbPlayerInventory::filter_by_entity_id(&player.entity_id),
+        ) else { return };
+
+        player.close_window(world, &mut window, &mut inventory);
+
+        let stacks = inventory.main_inv.iter_mut()
+            .chain(inventory.armor_inv.iter_mut())
+            .chain(inventory.craft_inv.iter_mut())
+            .chain(std::iter::once(&mut inventory.cursor_stack));
+
+        for stack in stacks {
+            if !stack.is_empty() {
+                player.drop_stack(world, *stack, true);
+                *stack = ItemStack::EMPTY;
+            }
+        }
+
+        inventory.update_craft();
+
+        StdbPlayerInventory::update_by_entity_id(&player.entity_id, inventory);
+        StdbWindow::update_by_entity_id(&player.entity_id, window);
+
+    }
+
+    /// Hide a dead player from other players, this is done once its death animation is
+    /// over and until it respawns.
+    pub fn handle_player_hide(&self, player: &StdbServerPlayer) {
+        for view in StdbEntityView::filter_by_target_id(&player.entity_id).collect::<Vec<_>>() {
+            StdbEntityView::delete_by_view_id(&view.view_id);
+        }
+    }
+
+    /// Handle an entity pickup world event, the picked up entity is removed if its stack
+    /// has been fully picked up. This returns true if the entity has been picked up, the
+    /// pickup is only played by clients in such case.
+    pub fn handle_entity_pickup(&self, world: &mut StdbWorld, id: u32, target_id: u32) -> bool {
+
+        let Some(mut target) = world.get_entity(target_id) else { return false };
+        let Some(player) = StdbServerPlayer::filter_by_entity_id(&id) else {
+            // This works only on entities handled by players.
+            return false
+        };
+
+        // Used only for picking arrow.
+        let mut arrow_stack = ItemStack::new_single(item::ARROW, 0);
+
+        let stack = match &mut target.1 {
+            BaseKind::Item(item)
+                => &mut item.stack,
+            BaseKind::Projectile(projectile, ProjectileKind::Arrow(_))
+                if projectile.shake == 0
+                => &mut arrow_stack,
+            // Other entities cannot be picked up.
+            _ => return false,
+        };
+
+        player.pickup_stack(stack);
+
+        // If the item stack has been emptied, kill the entity.
+        if stack.size == 0 {
+            world.remove_entity(target_id, "picked up");
+            true
+        } else {
+            world.set_entity(target_id, &target);
+            false
+        }
+
+    }
+
     /// Handle a player leaving this world, this should remove its entity. The `lost`
     /// argument indicates if the player is leaving because of a lost connection or not.
     /// If the connection was not lost, chunks and entities previously tracked by the
@@ -457,46 +559,6 @@ impl StdbServerWorld {
     //     }
     // }
 
-    // /// Handle an entity pickup world event.
-    // fn handle_entity_pickup(&mut self, id: u32, target_id: u32) {
-    //
-    //     let Some(Entity(_, target_kind)) = self.world.get_entity_mut(target_id) else { return };
-    //     let Some(player) = self.players.iter_mut().find(|p| p.entity_id == id) else {
-    //         // This works only on entities handled by players.
-    //         return
-    //     };
-    //
-    //     // Used only for picking arrow.
-    //     let mut arrow_stack = ItemStack::new_single(item::ARROW, 0);
-    //
-    //     let stack = match target_kind {
-    //         BaseKind::Item(item)
-    //             => &mut item.stack,
-    //         BaseKind::Projectile(projectile, ProjectileKind::Arrow(_))
-    //             if projectile.shake == 0
-    //             => &mut arrow_stack,
-    //         // Other entities cannot be picked up.
-    //         _ => return,
-    //     };
-    //
-    //     player.pickup_stack(stack);
-    //
-    //     // If the item stack has been emptied, kill the entity.
-    //     if stack.size == 0 {
-    //         self.world.remove_entity(target_id, "picked up");
-    //     }
-    //
-    //     for player in &self.players {
-    //         if player.tracked_entities.contains(&target_id) {
-    //             player.send(OutPacket::EntityPickup(proto::EntityPickupPacket {
-    //                 entity_id: id,
-    //                 picked_entity_id: target_id,
-    //             }));
-    //         }
-    //     }
-    //
-    // }
-    //
     // /// Handle an entity damage event.
     // fn handle_entity_damage(&mut self, id: u32) {
     //